@@ -11,7 +11,7 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("almost-all")
                 .long("all")
                 .multiple(true)
-                .help("Do not ignore entries starting with ."),
+                .help("Do not ignore entries starting with ., and list the implied . and .."),
         )
         .arg(
             Arg::with_name("almost-all")
@@ -32,6 +32,23 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("When to use terminal colours"),
         )
+        .arg(
+            Arg::with_name("color-scheme")
+                .long("color-scheme")
+                .possible_values(&["light", "dark", "auto"])
+                .multiple(true)
+                .number_of_values(1)
+                .help("Use the default colors meant for a light or a dark background, guessed from COLORFGBG with auto"),
+        )
+        .arg(
+            Arg::with_name("theme")
+                .long("theme")
+                .takes_value(true)
+                .value_name("path")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Use the colors of a YAML theme file [env: LSD_THEME]"),
+        )
         .arg(
             Arg::with_name("icon")
                 .long("icon")
@@ -53,6 +70,27 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("Whether to use fancy or unicode icons"),
         )
+        .arg(
+            Arg::with_name("icon-spacing")
+                .long("icon-spacing")
+                .possible_value("1")
+                .possible_value("2")
+                .default_value("1")
+                .multiple(true)
+                .number_of_values(1)
+                .help("The number of spaces between the icons and the names"),
+        )
+        .arg(
+            Arg::with_name("hyperlink")
+                .long("hyperlink")
+                .possible_value("always")
+                .possible_value("auto")
+                .possible_value("never")
+                .default_value("never")
+                .multiple(true)
+                .number_of_values(1)
+                .help("When to attach hyperlinks to the file names"),
+        )
         .arg(
             Arg::with_name("indicators")
                 .short("F")
@@ -60,6 +98,16 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Append indicator (one of */=>@|) at the end of the file names"),
         )
+        .arg(
+            Arg::with_name("indicator-types")
+                .long("indicator-types")
+                .multiple(true)
+                .number_of_values(1)
+                .require_delimiter(true)
+                .possible_values(&["dir", "exec", "symlink", "pipe", "socket"])
+                .value_name("types")
+                .help("Append the indicators of the given types of files only, separated by commas"),
+        )
         .arg(
             Arg::with_name("long")
                 .short("l")
@@ -67,11 +115,56 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display extended file metadata as a table"),
         )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .multiple(true)
+                .help("Print the listing as a JSON array, without colors or icons"),
+        )
+        .arg(
+            Arg::with_name("zero")
+                .long("zero")
+                .multiple(true)
+                .conflicts_with("json")
+                .help("Print only the paths of the listing, each ended by a NUL character"),
+        )
+        .arg(
+            Arg::with_name("dired")
+                .short("D")
+                .long("dired")
+                .multiple(true)
+                .conflicts_with_all(&["json", "zero"])
+                .help("Print the byte offsets of the names after the listing, for the dired mode of Emacs"),
+        )
+        .arg(
+            Arg::with_name("stdin")
+                .long("stdin")
+                .multiple(true)
+                .help("Read the paths to list from the standard input, one per line"),
+        )
+        .arg(
+            Arg::with_name("null")
+                .short("0")
+                .long("null")
+                .multiple(true)
+                .help("Read the paths from the standard input delimited by NUL characters"),
+        )
+        .arg(
+            Arg::with_name("progress")
+                .long("progress")
+                .multiple(true)
+                .help("Show a spinner on stderr while the entries are read, when it is a terminal"),
+        )
         .arg(
             Arg::with_name("ignore-config")
                 .long("ignore-config")
                 .help("Ignore the configuration file"),
         )
+        .arg(
+            Arg::with_name("directory-config")
+                .long("directory-config")
+                .help("Merge the .lsd.yaml of the listed directory over the configuration file"),
+        )
         .arg(
             Arg::with_name("oneline")
                 .short("1")
@@ -79,6 +172,33 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display one entry per line"),
         )
+        .arg(
+            Arg::with_name("width")
+                .long("width")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("cols")
+                .validator(validate_width_argument)
+                .help("Use the given number of columns instead of the terminal width for the grid, 0 means one entry per line"),
+        )
+        .arg(
+            Arg::with_name("truncate-names")
+                .long("truncate-names")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("cols")
+                .validator(validate_truncate_names_argument)
+                .help("Truncate the names wider than the given number of columns with an ellipsis, except in the tree layout"),
+        )
+        .arg(
+            Arg::with_name("padding")
+                .long("padding")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("num")
+                .validator(validate_padding_argument)
+                .help("Use the given number of spaces between the columns of the output"),
+        )
         .arg(
             Arg::with_name("recursive")
                 .short("R")
@@ -100,6 +220,20 @@ pub fn build() -> App<'static, 'static> {
                 .conflicts_with("recursive")
                 .help("Recurse into directories and present the result as a tree"),
         )
+        .arg(
+            Arg::with_name("tree-indent")
+                .long("tree-indent")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("cols")
+                .validator(validate_tree_indent_argument)
+                .help("The number of columns each depth of the tree layout is indented by [default: 3]"),
+        )
+        .arg(
+            Arg::with_name("tree-counts")
+                .long("tree-counts")
+                .help("Display the number of listed entries of the directories in the tree layout"),
+        )
         .arg(
             Arg::with_name("depth")
                 .long("depth")
@@ -112,6 +246,7 @@ pub fn build() -> App<'static, 'static> {
             Arg::with_name("directory-only")
                 .short("d")
                 .long("directory-only")
+                .visible_alias("directory")
                 .conflicts_with("all")
                 .conflicts_with("almost-all")
                 .conflicts_with("depth")
@@ -125,17 +260,115 @@ pub fn build() -> App<'static, 'static> {
                 .possible_value("default")
                 .possible_value("short")
                 .possible_value("bytes")
+                .possible_value("binary")
                 .default_value("default")
                 .multiple(true)
                 .number_of_values(1)
                 .help("How to display size"),
         )
+        .arg(
+            Arg::with_name("permission")
+                .long("permission")
+                .possible_value("rwx")
+                .possible_value("octal")
+                .default_value("rwx")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to display permissions"),
+        )
+        .arg(
+            Arg::with_name("size-precision")
+                .long("size-precision")
+                .possible_values(&["0", "1", "2", "3"])
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("digits")
+                .help("The number of decimal places of the human readable sizes"),
+        )
+        .arg(
+            Arg::with_name("size-fixed-width")
+                .long("size-fixed-width")
+                .multiple(true)
+                .help("Pad the human readable sizes to the widest possible value"),
+        )
+        .arg(
+            Arg::with_name("dirsize")
+                .long("dirsize")
+                .possible_value("size")
+                .possible_value("count")
+                .default_value("size")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Whether to display the size of the directories or their number of entries"),
+        )
+        .arg(
+            Arg::with_name("quoting-style")
+                .long("quoting-style")
+                .possible_value("literal")
+                .possible_value("shell")
+                .possible_value("shell-always")
+                .possible_value("c")
+                .default_value("literal")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to quote file names"),
+        )
+        .arg(
+            Arg::with_name("block-size")
+                .long("block-size")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("size")
+                .validator(validate_block_size_argument)
+                .help("Display the sizes as a number of blocks of the given size, like 1K, 1M or 512"),
+        )
+        .arg(
+            Arg::with_name("kibibytes")
+                .short("k")
+                .long("kibibytes")
+                .multiple(true)
+                .help("Display the sizes as a number of 1K blocks, like --block-size 1K"),
+        )
+        .arg(
+            Arg::with_name("numeric-uid-gid")
+                .short("n")
+                .long("numeric-uid-gid")
+                .multiple(true)
+                .help("Display the numeric user and group ids instead of their names"),
+        )
+        .arg(
+            Arg::with_name("across")
+                .short("x")
+                .long("across")
+                .multiple(true)
+                .help("Fill the grid row by row, across then down, instead of column by column"),
+        )
         .arg(
             Arg::with_name("total-size")
                 .long("total-size")
                 .multiple(true)
                 .help("Display the total size of directories"),
         )
+        .arg(
+            Arg::with_name("block-usage")
+                .long("block-usage")
+                .overrides_with("apparent-size")
+                .multiple(true)
+                .help("Display the size allocated on the disk for the files, like du"),
+        )
+        .arg(
+            Arg::with_name("apparent-size")
+                .long("apparent-size")
+                .overrides_with("block-usage")
+                .multiple(true)
+                .help("Display the apparent size of the files (default)"),
+        )
+        .arg(
+            Arg::with_name("summary")
+                .long("summary")
+                .multiple(true)
+                .help("Display the number of entries and their combined size below the content of each directory"),
+        )
         .arg(
             Arg::with_name("date")
                 .long("date")
@@ -143,7 +376,56 @@ pub fn build() -> App<'static, 'static> {
                 .default_value("date")
                 .multiple(true)
                 .number_of_values(1)
-                .help("How to display date [possible values: date, relative, +date-time-format]"),
+                .help("How to display date [possible values: date, relative, full-iso, long-iso, iso, locale, +date-time-format]"),
+        )
+        .arg(
+            Arg::with_name("full-time")
+                .long("full-time")
+                .multiple(true)
+                .help("Display extended file metadata as a table, with the full-iso dates, like --long --time-style full-iso"),
+        )
+        .arg(
+            Arg::with_name("time-style")
+                .long("time-style")
+                .validator(validate_time_style_argument)
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to display date, like GNU ls [possible values: full-iso, long-iso, iso, locale, +date-time-format]"),
+        )
+        .arg(
+            Arg::with_name("date-color")
+                .long("date-color")
+                .multiple(true)
+                .help("Color the dates on a gradient, from the recent ones to the dimmed old ones"),
+        )
+        .arg(
+            Arg::with_name("mount-points")
+                .long("mount-points")
+                .multiple(true)
+                .help("Mark the mount points with [mount] after their name"),
+        )
+        .arg(
+            Arg::with_name("size-color")
+                .long("size-color")
+                .multiple(true)
+                .help("Color the sizes on a heatmap, from the small files to the large ones"),
+        )
+        .arg(
+            Arg::with_name("writable-color")
+                .long("writable-color")
+                .multiple(true)
+                .help("Highlight the permissions of the files writable by their group or by the others"),
+        )
+        .arg(
+            Arg::with_name("time")
+                .long("time")
+                .possible_value("modified")
+                .possible_value("accessed")
+                .possible_value("changed")
+                .possible_value("created")
+                .multiple(true)
+                .number_of_values(1)
+                .help("Which time stamp to display and to sort by"),
         )
         .arg(
             Arg::with_name("timesort")
@@ -154,6 +436,7 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("versionsort")
                 .overrides_with("sort")
                 .multiple(true)
+                .overrides_with("nosort")
                 .help("Sort by time modified"),
         )
         .arg(
@@ -165,6 +448,7 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("versionsort")
                 .overrides_with("sort")
                 .multiple(true)
+                .overrides_with("nosort")
                 .help("Sort by size"),
         )
         .arg(
@@ -176,6 +460,7 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("versionsort")
                 .overrides_with("sort")
                 .multiple(true)
+                .overrides_with("nosort")
                 .help("Sort by file extension"),
         )
         .arg(
@@ -187,20 +472,42 @@ pub fn build() -> App<'static, 'static> {
                 .overrides_with("sizesort")
                 .overrides_with("extensionsort")
                 .overrides_with("sort")
+                .overrides_with("nosort")
                 .help("Natural sort of (version) numbers within text"),
         )
         .arg(
             Arg::with_name("sort")
                 .long("sort")
                 .multiple(true)
-                .possible_values(&["size", "time", "version", "extension"])
+                .number_of_values(1)
+                .require_delimiter(true)
+                .possible_values(&["size", "time", "version", "extension", "name", "none"])
                 .takes_value(true)
                 .value_name("WORD")
                 .overrides_with("timesort")
                 .overrides_with("sizesort")
                 .overrides_with("extensionsort")
                 .overrides_with("versionsort")
-                .help("sort by WORD instead of name")
+                .overrides_with("nosort")
+                .help("sort by WORD instead of name, the next WORDs breaking the ties")
+        )
+        .arg(
+            Arg::with_name("nosort")
+                .short("U")
+                .long("no-sort")
+                .multiple(true)
+                .overrides_with("timesort")
+                .overrides_with("sizesort")
+                .overrides_with("extensionsort")
+                .overrides_with("versionsort")
+                .overrides_with("sort")
+                .help("Do not sort. List the entries in directory order"),
+        )
+        .arg(
+            Arg::with_name("sort-locale")
+                .long("sort-locale")
+                .multiple(true)
+                .help("Compare the names with the collation of the locale, like ls, so that the accented letters sort with the plain ones"),
         )
         .arg(
             Arg::with_name("reverse")
@@ -220,6 +527,12 @@ pub fn build() -> App<'static, 'static> {
                 .number_of_values(1)
                 .help("Sort the directories then the files"),
         )
+        .arg(
+            Arg::with_name("group-directories-first")
+                .long("group-directories-first")
+                .multiple(true)
+                .help("Sort the directories then the files, like --group-dirs first"),
+        )
         .arg(
             Arg::with_name("blocks")
                 .long("blocks")
@@ -231,16 +544,30 @@ pub fn build() -> App<'static, 'static> {
                     "user",
                     "group",
                     "size",
+                    "device",
                     "date",
                     "name",
                     "inode",
+                    "links",
+                    "git",
+                    "xattr",
+                    "filetype",
+                    "context",
+                    "flags",
+                    "attributes",
                 ])
                 .help("Specify the blocks that will be displayed and in what order"),
         )
+        .arg(
+            Arg::with_name("header")
+                .long("header")
+                .multiple(true)
+                .help("Display block headers"),
+        )
         .arg(
             Arg::with_name("classic")
             .long("classic")
-            .help("Enable classic mode (no colors or icons)"),
+            .help("Enable classic mode (no colors or icons), which the later options override"),
         )
         .arg(
             Arg::with_name("no-symlink")
@@ -248,6 +575,17 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Do not display symlink target"),
         )
+        .arg(
+            Arg::with_name("symlink-target")
+                .long("symlink-target")
+                .possible_value("raw")
+                .possible_value("absolute")
+                .possible_value("relative")
+                .default_value("raw")
+                .multiple(true)
+                .number_of_values(1)
+                .help("How to display the symlink targets: as stored, as absolute paths or relative to the link"),
+        )
         .arg(
             Arg::with_name("ignore-glob")
                 .short("I")
@@ -258,6 +596,70 @@ pub fn build() -> App<'static, 'static> {
                 .default_value("")
                 .help("Do not display files/directories with names matching the glob pattern(s). More than one can be specified by repeating the argument"),
         )
+        .arg(
+            Arg::with_name("hide")
+                .long("hide")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("pattern")
+                .help("Do not display the entries with names matching the glob pattern, unless --all or --almost-all is passed. More than one can be specified by repeating the argument"),
+        )
+        .arg(
+            Arg::with_name("gitignore")
+                .long("gitignore")
+                .multiple(true)
+                .help("Do not list the entries ignored by git, unless they are given as arguments"),
+        )
+        .arg(
+            Arg::with_name("dim-ignored")
+                .long("dim-ignored")
+                .multiple(true)
+                .help("Dim the names of the entries ignored by git, which are still listed"),
+        )
+        .arg(
+            Arg::with_name("type")
+                .long("type")
+                .possible_values(&["file", "dir", "symlink", "executable", "pipe", "socket"])
+                .multiple(true)
+                .number_of_values(1)
+                .help("Only display files of the given type. More than one can be specified by repeating the argument"),
+        )
+        .arg(
+            Arg::with_name("larger-than")
+                .long("larger-than")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("size")
+                .validator(validate_block_size_argument)
+                .help("Only display the files of at least the given size, like 10K or 1M"),
+        )
+        .arg(
+            Arg::with_name("smaller-than")
+                .long("smaller-than")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("size")
+                .validator(validate_block_size_argument)
+                .help("Only display the files smaller than the given size, like 10K or 1M"),
+        )
+        .arg(
+            Arg::with_name("newer-than")
+                .long("newer-than")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("date")
+                .validator(validate_date_bound_argument)
+                .help("Only display the files more recent than the given duration ago, like 2h or 7d, or than the given date, like 2020-01-31"),
+        )
+        .arg(
+            Arg::with_name("older-than")
+                .long("older-than")
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("date")
+                .validator(validate_date_bound_argument)
+                .help("Only display the files older than the given duration ago, like 2h or 7d, or than the given date, like 2020-01-31"),
+        )
         .arg(
             Arg::with_name("inode")
                 .short("i")
@@ -265,6 +667,26 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("Display the index number of each file"),
         )
+        .arg(
+            Arg::with_name("extended")
+                .short("@")
+                .long("extended")
+                .multiple(true)
+                .help("Display an @ next to the files with extended attributes, and their names in the long listing"),
+        )
+        .arg(
+            Arg::with_name("context")
+                .short("Z")
+                .long("context")
+                .multiple(true)
+                .help("Display the SELinux security context of the files"),
+        )
+        .arg(
+            Arg::with_name("git")
+                .long("git")
+                .multiple(true)
+                .help("Display the git status of each file"),
+        )
         .arg(
             Arg::with_name("dereference")
                 .short("L")
@@ -272,15 +694,188 @@ pub fn build() -> App<'static, 'static> {
                 .multiple(true)
                 .help("When showing file information for a symbolic link, show information for the file the link references rather than for the link itself"),
         )
+        .arg(
+            Arg::with_name("dereference-columns")
+                .long("dereference-columns")
+                .possible_values(&["size", "type", "date"])
+                .multiple(true)
+                .number_of_values(1)
+                .require_delimiter(true)
+                .value_name("column")
+                .help("Show the size, the file type or the date of the file a symbolic link references in the given columns, while still showing the link"),
+        )
+        .arg(
+            Arg::with_name("dereference-command-line")
+                .short("H")
+                .long("dereference-command-line")
+                .multiple(true)
+                .help("Follow the symbolic links given on the command line, but not the ones found in the listed directories"),
+        )
+        .arg(
+            Arg::with_name("resolve-symlinks")
+                .long("resolve-symlinks")
+                .multiple(true)
+                .help("Display the file type and the permissions of the symbolic link targets, while still showing the links"),
+        )
+}
+
+fn validate_width_argument(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err("the width has to be a non-negative integer".to_owned()),
+    }
+}
+
+fn validate_truncate_names_argument(arg: String) -> Result<(), String> {
+    match arg.parse::<usize>() {
+        Ok(width) if width > 0 => Ok(()),
+        _ => Err("the width has to be a positive integer".to_owned()),
+    }
+}
+
+/// The largest number of spaces accepted between the columns of the output.
+pub const MAX_PADDING: usize = 16;
+
+fn validate_padding_argument(arg: String) -> Result<(), String> {
+    match parse_padding(&arg) {
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "the padding has to be an integer between 0 and {}",
+            MAX_PADDING
+        )),
+    }
+}
+
+/// Parse the number of spaces between the columns of the output, which must not be larger than
+/// [MAX_PADDING].
+pub fn parse_padding(value: &str) -> Option<usize> {
+    match value.parse::<usize>() {
+        Ok(padding) if padding <= MAX_PADDING => Some(padding),
+        _ => None,
+    }
+}
+
+/// The largest number of columns each depth of the tree layout can be indented by.
+pub const MAX_TREE_INDENT: usize = 8;
+
+fn validate_tree_indent_argument(arg: String) -> Result<(), String> {
+    match parse_tree_indent(&arg) {
+        Some(_) => Ok(()),
+        None => Err(format!(
+            "the indentation has to be an integer between 1 and {}",
+            MAX_TREE_INDENT
+        )),
+    }
+}
+
+/// Parse the number of columns each depth of the tree layout is indented by, which must be
+/// between 1 and [MAX_TREE_INDENT].
+pub fn parse_tree_indent(value: &str) -> Option<usize> {
+    match value.parse::<usize>() {
+        Ok(indent) if (1..=MAX_TREE_INDENT).contains(&indent) => Some(indent),
+        _ => None,
+    }
+}
+
+fn validate_block_size_argument(arg: String) -> Result<(), String> {
+    match parse_block_size(&arg) {
+        Some(_) => Ok(()),
+        None => Err(
+            "the size has to be a positive integer, optionally followed by K, M, G, KB, MB or GB"
+                .to_owned(),
+        ),
+    }
+}
+
+/// Parse a block size like `1K`, `4M` or `512` into a number of bytes. The `K`, `M` and `G`
+/// suffixes are powers of 1024, while `KB`, `MB` and `GB` are powers of 1000.
+pub fn parse_block_size(value: &str) -> Option<u64> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split);
+
+    let multiplier = match suffix {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        "KB" => 1000,
+        "MB" => 1000 * 1000,
+        "GB" => 1000 * 1000 * 1000,
+        _ => return None,
+    };
+
+    match number.parse::<u64>() {
+        Ok(number) if number > 0 => number.checked_mul(multiplier),
+        _ => None,
+    }
+}
+
+fn validate_date_bound_argument(arg: String) -> Result<(), String> {
+    match parse_date_bound(&arg, time::get_time()) {
+        Some(_) => Ok(()),
+        None => Err("the date has to be a duration like 30s, 15m, 2h, 7d or 2w, or a date like 2020-01-31 or 2020-01-31T12:00:00".to_owned()),
+    }
+}
+
+/// Parse a date bound, either a duration before `now` like `30s`, `15m`, `2h`, `7d` or `2w`, or
+/// a date like `2020-01-31` or `2020-01-31T12:00:00` in the local time zone.
+pub fn parse_date_bound(value: &str, now: time::Timespec) -> Option<time::Timespec> {
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (number, suffix) = value.split_at(split);
+
+    let multiplier = match suffix {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return parse_local_date(value),
+    };
+
+    let seconds = number.parse::<i64>().ok()?.checked_mul(multiplier)?;
+    Some(now - time::Duration::seconds(seconds))
+}
+
+/// Parse an ISO date, with an optional time, in the local time zone.
+fn parse_local_date(value: &str) -> Option<time::Timespec> {
+    let date = ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d"]
+        .iter()
+        .find_map(|format| time::strptime(value, format).ok())?;
+
+    // The parsed date is in UTC, it is shifted by the offset of the local time zone at that date.
+    let utc = date.to_timespec();
+    Some(utc - time::Duration::seconds(i64::from(time::at(utc).tm_utcoff)))
 }
 
 fn validate_date_argument(arg: String) -> Result<(), String> {
     if arg.starts_with('+') {
         validate_time_format(&arg).map_err(|err| err.to_string())
-    } else if &arg == "date" || &arg == "relative" {
+    } else if &arg == "date" || &arg == "relative" || TIME_STYLES.contains(&arg.as_str()) {
         Result::Ok(())
     } else {
-        Result::Err("possible values: date, relative, +date-time-format".to_owned())
+        Result::Err(
+            "possible values: date, relative, full-iso, long-iso, iso, locale, +date-time-format"
+                .to_owned(),
+        )
+    }
+}
+
+/// The presets of the "time-style" argument, named like the ones of GNU `ls`.
+const TIME_STYLES: &[&str] = &["full-iso", "long-iso", "iso", "locale"];
+
+fn validate_time_style_argument(arg: String) -> Result<(), String> {
+    if arg.starts_with('+') {
+        validate_time_format(&arg).map_err(|err| err.to_string())
+    } else if TIME_STYLES.contains(&arg.as_str()) {
+        Result::Ok(())
+    } else {
+        Result::Err(
+            "possible values: full-iso, long-iso, iso, locale, +date-time-format".to_owned(),
+        )
     }
 }
 
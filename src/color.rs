@@ -1,7 +1,13 @@
+use crate::config_file::Config;
+use crate::core::ExitCode;
+use crate::flags::ColorScheme;
+use crate::meta::GitStatus;
+use crate::print_error;
 use ansi_term::{ANSIString, Colour, Style};
 use lscolors::{Indicator, LsColors};
 use std::collections::HashMap;
 use std::path::Path;
+use yaml_rust::Yaml;
 
 #[allow(dead_code)]
 #[derive(Hash, Debug, Eq, PartialEq, Clone)]
@@ -16,11 +22,15 @@ pub enum Elem {
     Dir {
         uid: bool,
     },
+    /// Directories on another device than their parent
+    MountPoint,
     Pipe,
     BlockDevice,
     CharDevice,
     Socket,
     Special,
+    /// Dotfiles revealed by `--all` or `--almost-all`
+    Hidden,
 
     /// Permissions
     Read,
@@ -28,11 +38,20 @@ pub enum Elem {
     Exec,
     ExecSticky,
     NoAccess,
+    Octal,
+    /// Permissions of the files writable by their group or by the others, when highlighted
+    GroupWritable,
+    OtherWritable,
+    /// Permissions of the directories writable by the others without the sticky bit
+    OtherWritableDir,
 
     /// Last Time Modified
     DayOld,
     HourOld,
     Older,
+    /// Last Time Modified, on the gradient of the dates
+    WeekOld,
+    Ancient,
 
     /// User / Group Name
     User,
@@ -43,11 +62,40 @@ pub enum Elem {
     FileLarge,
     FileMedium,
     FileSmall,
+    /// File Size, on the heatmap of the sizes
+    SizeSmall,
+    SizeMedium,
+    SizeLarge,
 
     /// INode
     INode {
         valid: bool,
     },
+
+    /// Hard links
+    Links {
+        valid: bool,
+    },
+
+    /// Git
+    GitStatus {
+        status: GitStatus,
+    },
+
+    /// Extended attributes
+    XAttr,
+
+    /// Number of listed entries of the directories in the tree layout
+    TreeCount,
+
+    /// SELinux security context
+    Context,
+
+    /// Entries ignored by git, when they are dimmed
+    GitIgnored,
+
+    /// Flags set by chflags
+    FileFlags,
 }
 
 impl Elem {
@@ -66,17 +114,47 @@ pub enum Theme {
     NoLscolors,
 }
 
+/// The sizes in bytes from which the files are medium and large on the heatmap of the sizes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SizeThresholds {
+    pub medium: u64,
+    pub large: u64,
+}
+
+/// The default `SizeThresholds` are a kilobyte and a megabyte.
+impl Default for SizeThresholds {
+    fn default() -> Self {
+        Self {
+            medium: 1024,
+            large: 1024 * 1024,
+        }
+    }
+}
+
+lazy_static! {
+    /// The built-in colors are only built once, the first time they are used, and are only read
+    /// afterwards, so that they can be shared between threads.
+    static ref DARK_SCHEME_COLOUR_MAP: HashMap<Elem, Colour> = Colors::get_dark_scheme_colour_map();
+    static ref LIGHT_SCHEME_COLOUR_MAP: HashMap<Elem, Colour> = Colors::get_light_scheme_colour_map();
+}
+
 pub struct Colors {
-    colors: Option<HashMap<Elem, Colour>>,
+    colors: Option<&'static HashMap<Elem, Colour>>,
     lscolors: Option<LsColors>,
+    /// The colors of the theme file, which take precedence over the other ones.
+    theme: HashMap<Elem, Colour>,
+    /// The colors of the file names by extension, from the theme file. The extensions are
+    /// lowercase and may be compound, like "tar.gz".
+    extensions: HashMap<String, Colour>,
+    size_thresholds: SizeThresholds,
 }
 
 impl Colors {
     pub fn new(theme: Theme) -> Self {
         let colors = match theme {
             Theme::NoColor => None,
-            Theme::Default => Some(Self::get_light_theme_colour_map()),
-            Theme::NoLscolors => Some(Self::get_light_theme_colour_map()),
+            Theme::Default => Some(&*DARK_SCHEME_COLOUR_MAP),
+            Theme::NoLscolors => Some(&*DARK_SCHEME_COLOUR_MAP),
         };
         let lscolors = match theme {
             Theme::NoColor => None,
@@ -84,23 +162,321 @@ impl Colors {
             Theme::NoLscolors => None,
         };
 
-        Self { colors, lscolors }
+        Self {
+            colors,
+            lscolors,
+            theme: HashMap::new(),
+            extensions: HashMap::new(),
+            size_thresholds: SizeThresholds::default(),
+        }
+    }
+
+    /// Use the default colors meant for the background of the given scheme, which are the ones
+    /// of the dark scheme unless it is [Light](ColorScheme::Light). Nothing changes when the
+    /// colors are disabled.
+    pub fn with_scheme(mut self, scheme: ColorScheme) -> Self {
+        if self.colors.is_some() && scheme == ColorScheme::Light {
+            self.colors = Some(&*LIGHT_SCHEME_COLOUR_MAP);
+        }
+
+        self
+    }
+
+    /// Use the colors of the YAML theme file at `path` instead of the built-in ones and those of
+    /// `LS_COLORS`. The elements missing from the file keep their colors, and nothing changes
+    /// when the colors are disabled.
+    pub fn with_theme_file(mut self, path: &Path) -> Self {
+        if self.colors.is_none() {
+            return self;
+        }
+
+        let config = match Config::from_file(path) {
+            Some(config) => config,
+            None => {
+                print_error!("lsd: {}: could not open the theme file\n", path.display());
+                ExitCode::MinorIssue.set_if_greater();
+                return self;
+            }
+        };
+
+        match &config.yaml {
+            Some(Yaml::Hash(hash)) => {
+                for (key, value) in hash {
+                    let key = match key.as_str() {
+                        Some(key) => key,
+                        None => {
+                            config.print_warning("The theme keys have to be strings.");
+                            continue;
+                        }
+                    };
+                    if key == "size-thresholds" {
+                        self.size_thresholds = Self::size_thresholds_from_yaml(value, &config);
+                        continue;
+                    }
+                    if key == "extensions" {
+                        self.extensions = Self::extensions_from_yaml(value, &config);
+                        continue;
+                    }
+                    let elems = match Self::elems_from_theme_key(key) {
+                        Some(elems) => elems,
+                        None => {
+                            config.print_warning(&format!("Unknown theme key: {}", key));
+                            continue;
+                        }
+                    };
+                    match Self::colour_from_yaml(value) {
+                        Some(colour) => {
+                            for elem in elems {
+                                self.theme.insert(elem, colour);
+                            }
+                        }
+                        None => config.print_invalid_value_warning(key, &format!("{:?}", value)),
+                    }
+                }
+            }
+            Some(_) => config.print_warning("The theme has to be an object."),
+            None => {}
+        }
+
+        self
+    }
+
+    /// Get the thresholds of the heatmap of the sizes from the "size-thresholds" value of a theme
+    /// file. The missing and invalid thresholds keep their default values.
+    fn size_thresholds_from_yaml(value: &Yaml, config: &Config) -> SizeThresholds {
+        let default = SizeThresholds::default();
+        if !matches!(value, Yaml::Hash(_)) {
+            config.print_wrong_type_warning("size-thresholds", "object");
+            return default;
+        }
+
+        let threshold = |key: &str, default: u64| match &value[key] {
+            Yaml::BadValue => default,
+            Yaml::Integer(bytes) if *bytes >= 0 => *bytes as u64,
+            other => {
+                config.print_invalid_value_warning(
+                    &format!("size-thresholds->{}", key),
+                    &format!("{:?}", other),
+                );
+                default
+            }
+        };
+
+        SizeThresholds {
+            medium: threshold("medium", default.medium),
+            large: threshold("large", default.large),
+        }
+    }
+
+    /// Get the colors of the file names by extension from the "extensions" value of a theme
+    /// file. The leading dots of the extensions are optional and their case is ignored.
+    fn extensions_from_yaml(value: &Yaml, config: &Config) -> HashMap<String, Colour> {
+        let mut extensions = HashMap::new();
+        let hash = match value {
+            Yaml::Hash(hash) => hash,
+            _ => {
+                config.print_wrong_type_warning("extensions", "object");
+                return extensions;
+            }
+        };
+
+        for (extension, colour) in hash {
+            let extension = match extension.as_str() {
+                Some(extension) => extension.trim_start_matches('.').to_lowercase(),
+                None => {
+                    config.print_warning("The extensions have to be strings.");
+                    continue;
+                }
+            };
+            match Self::colour_from_yaml(colour) {
+                Some(colour) => {
+                    extensions.insert(extension, colour);
+                }
+                None => config.print_invalid_value_warning(
+                    &format!("extensions->{}", extension),
+                    &format!("{:?}", colour),
+                ),
+            }
+        }
+
+        extensions
+    }
+
+    /// The color of the file at `path` from the "extensions" of the theme file. A compound
+    /// extension like "tar.gz" is matched before the simple one, as the longest matching
+    /// extension is used.
+    fn extension_colour(&self, path: &Path) -> Option<Colour> {
+        if self.extensions.is_empty() {
+            return None;
+        }
+
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        name.match_indices('.')
+            .filter(|(index, _)| *index > 0)
+            .find_map(|(index, _)| self.extensions.get(&name[index + 1..]))
+            .copied()
+    }
+
+    pub fn size_thresholds(&self) -> SizeThresholds {
+        self.size_thresholds
+    }
+
+    /// Get the elements colored by a key of a theme file.
+    fn elems_from_theme_key(key: &str) -> Option<Vec<Elem>> {
+        let elems = match key {
+            "user" => vec![Elem::User],
+            "group" => vec![Elem::Group],
+            "read" => vec![Elem::Read],
+            "write" => vec![Elem::Write],
+            "exec" => vec![Elem::Exec],
+            "exec-sticky" => vec![Elem::ExecSticky],
+            "no-access" => vec![Elem::NoAccess],
+            "octal" => vec![Elem::Octal],
+            "group-writable" => vec![Elem::GroupWritable],
+            "other-writable" => vec![Elem::OtherWritable],
+            "other-writable-dir" => vec![Elem::OtherWritableDir],
+            "file" => vec![
+                Elem::File {
+                    exec: false,
+                    uid: false,
+                },
+                Elem::File {
+                    exec: false,
+                    uid: true,
+                },
+            ],
+            "exec-file" => vec![
+                Elem::File {
+                    exec: true,
+                    uid: false,
+                },
+                Elem::File {
+                    exec: true,
+                    uid: true,
+                },
+            ],
+            "dir" => vec![Elem::Dir { uid: false }, Elem::Dir { uid: true }],
+            "mount-point" => vec![Elem::MountPoint],
+            "pipe" => vec![Elem::Pipe],
+            "symlink" => vec![Elem::SymLink],
+            "broken-symlink" => vec![Elem::BrokenSymLink],
+            "block-device" => vec![Elem::BlockDevice],
+            "char-device" => vec![Elem::CharDevice],
+            "socket" => vec![Elem::Socket],
+            "special" => vec![Elem::Special],
+            "hidden" => vec![Elem::Hidden],
+            "hour-old" => vec![Elem::HourOld],
+            "day-old" => vec![Elem::DayOld],
+            "week-old" => vec![Elem::WeekOld],
+            "older" => vec![Elem::Older],
+            "ancient" => vec![Elem::Ancient],
+            "non-file" => vec![Elem::NonFile],
+            "file-small" => vec![Elem::FileSmall],
+            "file-medium" => vec![Elem::FileMedium],
+            "file-large" => vec![Elem::FileLarge],
+            "size-small" => vec![Elem::SizeSmall],
+            "size-medium" => vec![Elem::SizeMedium],
+            "size-large" => vec![Elem::SizeLarge],
+            "inode" => vec![Elem::INode { valid: true }],
+            "no-inode" => vec![Elem::INode { valid: false }],
+            "links" => vec![Elem::Links { valid: true }],
+            "no-links" => vec![Elem::Links { valid: false }],
+            "xattr" => vec![Elem::XAttr],
+            "tree-count" => vec![Elem::TreeCount],
+            "context" => vec![Elem::Context],
+            "git-ignored" => vec![Elem::GitIgnored],
+            "file-flags" => vec![Elem::FileFlags],
+            _ => return None,
+        };
+
+        Some(elems)
+    }
+
+    /// Get a colour from a theme value, which is either a 256 colors code, the name of one of the
+    /// 8 basic colors or an "#rrggbb" hexadecimal code.
+    fn colour_from_yaml(value: &Yaml) -> Option<Colour> {
+        match value {
+            Yaml::Integer(code) if (0..=255).contains(code) => Some(Colour::Fixed(*code as u8)),
+            Yaml::String(name) => match name.as_str() {
+                "black" => Some(Colour::Black),
+                "red" => Some(Colour::Red),
+                "green" => Some(Colour::Green),
+                "yellow" => Some(Colour::Yellow),
+                "blue" => Some(Colour::Blue),
+                "purple" | "magenta" => Some(Colour::Purple),
+                "cyan" => Some(Colour::Cyan),
+                "white" => Some(Colour::White),
+                hex if hex.len() == 7 && hex.starts_with('#') => {
+                    let channel = |range| u8::from_str_radix(hex.get(range)?, 16).ok();
+                    Some(Colour::RGB(channel(1..3)?, channel(3..5)?, channel(5..7)?))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
     }
 
     pub fn colorize<'a>(&self, input: String, elem: &Elem) -> ColoredString<'a> {
         self.style(elem).paint(input)
     }
 
+    pub fn colorize_header<'a>(&self, input: String) -> ColoredString<'a> {
+        match self.colors {
+            Some(_) => Style::new().underline().paint(input),
+            None => Style::default().paint(input),
+        }
+    }
+
     pub fn colorize_using_path<'a>(
         &self,
         input: String,
         path: &Path,
         elem: &Elem,
     ) -> ColoredString<'a> {
-        let style_from_path = self.style_from_path(path);
-        match style_from_path {
-            Some(style_from_path) => style_from_path.paint(input),
-            None => self.colorize(input, elem),
+        self.style_using_path(path, elem).paint(input)
+    }
+
+    /// Colorize the name of a hidden entry. Directories keep their color and are dimmed, so they
+    /// can still be told apart, while the other entries use the color of [Elem::Hidden].
+    pub fn colorize_hidden_using_path<'a>(
+        &self,
+        input: String,
+        path: &Path,
+        elem: &Elem,
+    ) -> ColoredString<'a> {
+        match (&self.colors, elem) {
+            (None, _) => Style::default().paint(input),
+            (Some(_), Elem::Dir { .. }) | (Some(_), Elem::MountPoint) => {
+                self.style_using_path(path, elem).dimmed().paint(input)
+            }
+            (Some(_), _) => self.colorize(input, &Elem::Hidden),
+        }
+    }
+
+    /// Colorize the name of an entry ignored by git, dimmed in the color of [Elem::GitIgnored].
+    pub fn colorize_ignored<'a>(&self, input: String) -> ColoredString<'a> {
+        match &self.colors {
+            None => Style::default().paint(input),
+            Some(_) => self.style(&Elem::GitIgnored).dimmed().paint(input),
+        }
+    }
+
+    fn style_using_path(&self, path: &Path, elem: &Elem) -> Style {
+        if let Elem::File { .. } = elem {
+            if let Some(colour) = self.extension_colour(path) {
+                return Self::with_background(Style::default().fg(colour), elem);
+            }
+        }
+
+        // LS_COLORS has no color for the mount points, which would otherwise get the color of the
+        // directories from it.
+        if self.theme.contains_key(elem) || *elem == Elem::MountPoint {
+            return self.style(elem);
+        }
+
+        match self.style_from_path(path) {
+            Some(style_from_path) => style_from_path,
+            None => self.style(elem),
         }
     }
 
@@ -114,6 +490,10 @@ impl Colors {
     }
 
     fn style(&self, elem: &Elem) -> Style {
+        if self.theme.contains_key(elem) {
+            return self.style_default(elem);
+        }
+
         match &self.lscolors {
             Some(lscolors) => match self.get_indicator_from_elem(elem) {
                 Some(style) => {
@@ -129,18 +509,25 @@ impl Colors {
     }
 
     fn style_default(&self, elem: &Elem) -> Style {
-        if let Some(ref colors) = self.colors {
-            let style_fg = Style::default().fg(colors[elem]);
-            if elem.has_suid() {
-                style_fg.on(Colour::Fixed(124)) // Red3
-            } else {
-                style_fg
-            }
+        if let Some(colors) = self.colors {
+            let colour = self.theme.get(elem).unwrap_or(&colors[elem]);
+            Self::with_background(Style::default().fg(*colour), elem)
         } else {
             Style::default()
         }
     }
 
+    /// Add the background highlighting some elements to their style.
+    fn with_background(style_fg: Style, elem: &Elem) -> Style {
+        if elem.has_suid() {
+            style_fg.on(Colour::Fixed(124)) // Red3
+        } else if *elem == Elem::OtherWritableDir {
+            style_fg.on(Colour::Fixed(226)) // Yellow1
+        } else {
+            style_fg
+        }
+    }
+
     fn get_indicator_from_elem(&self, elem: &Elem) -> Option<Indicator> {
         let indicator_string = match elem {
             Elem::File { exec, uid } => match (exec, uid) {
@@ -177,7 +564,7 @@ impl Colors {
     // You can find the table for each color, code, and display at:
     //
     //https://jonasjacek.github.io/colors/
-    fn get_light_theme_colour_map() -> HashMap<Elem, Colour> {
+    fn get_dark_scheme_colour_map() -> HashMap<Elem, Colour> {
         let mut m = HashMap::new();
         // User / Group
         m.insert(Elem::User, Colour::Fixed(230)); // Cornsilk1
@@ -189,6 +576,10 @@ impl Colors {
         m.insert(Elem::Exec, Colour::Red);
         m.insert(Elem::ExecSticky, Colour::Purple);
         m.insert(Elem::NoAccess, Colour::Fixed(245)); // Grey
+        m.insert(Elem::Octal, Colour::Fixed(6)); // Teal
+        m.insert(Elem::GroupWritable, Colour::Fixed(214)); // Orange1
+        m.insert(Elem::OtherWritable, Colour::Fixed(196)); // Red1
+        m.insert(Elem::OtherWritableDir, Colour::Fixed(196)); // Red1
 
         // File Types
         m.insert(
@@ -221,6 +612,7 @@ impl Colors {
         ); // Green3
         m.insert(Elem::Dir { uid: true }, Colour::Fixed(33)); // DodgerBlue1
         m.insert(Elem::Dir { uid: false }, Colour::Fixed(33)); // DodgerBlue1
+        m.insert(Elem::MountPoint, Colour::Fixed(39)); // DeepSkyBlue1
         m.insert(Elem::Pipe, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::SymLink, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::BrokenSymLink, Colour::Fixed(124)); // Red3
@@ -228,22 +620,429 @@ impl Colors {
         m.insert(Elem::CharDevice, Colour::Fixed(172)); // Orange3
         m.insert(Elem::Socket, Colour::Fixed(44)); // DarkTurquoise
         m.insert(Elem::Special, Colour::Fixed(44)); // DarkTurquoise
+        m.insert(Elem::Hidden, Colour::Fixed(245)); // Grey
 
         // Last Time Modified
         m.insert(Elem::HourOld, Colour::Fixed(40)); // Green3
         m.insert(Elem::DayOld, Colour::Fixed(42)); // SpringGreen2
         m.insert(Elem::Older, Colour::Fixed(36)); // DarkCyan
+        m.insert(Elem::WeekOld, Colour::Fixed(36)); // DarkCyan
+        m.insert(Elem::Ancient, Colour::Fixed(242)); // Grey42
 
         // Last Time Modified
         m.insert(Elem::NonFile, Colour::Fixed(245)); // Grey
         m.insert(Elem::FileSmall, Colour::Fixed(229)); // Wheat1
         m.insert(Elem::FileMedium, Colour::Fixed(216)); // LightSalmon1
         m.insert(Elem::FileLarge, Colour::Fixed(172)); // Orange3
+        m.insert(Elem::SizeSmall, Colour::Fixed(40)); // Green3
+        m.insert(Elem::SizeMedium, Colour::Fixed(220)); // Gold1
+        m.insert(Elem::SizeLarge, Colour::Fixed(196)); // Red1
 
         // INode
         m.insert(Elem::INode { valid: true }, Colour::Fixed(13)); // Pink
         m.insert(Elem::INode { valid: false }, Colour::Fixed(245)); // Grey
 
+        // Hard links
+        m.insert(Elem::Links { valid: true }, Colour::Fixed(13)); // Pink
+        m.insert(Elem::Links { valid: false }, Colour::Fixed(245)); // Grey
+
+        // Extended attributes
+        m.insert(Elem::XAttr, Colour::Fixed(245)); // Grey
+
+        // Tree counts
+        m.insert(Elem::TreeCount, Colour::Fixed(110)); // LightSkyBlue3
+
+        // Security contexts
+        m.insert(Elem::Context, Colour::Fixed(137)); // LightSalmon3
+
+        // Entries ignored by git
+        m.insert(Elem::GitIgnored, Colour::Fixed(240)); // Grey35
+
+        // File flags
+        m.insert(Elem::FileFlags, Colour::Fixed(245)); // Grey
+
+        // Git
+        let git_colors = [
+            (GitStatus::Unmodified, Colour::Fixed(245)), // Grey
+            (GitStatus::Modified, Colour::Fixed(33)),    // DodgerBlue1
+            (GitStatus::TypeChange, Colour::Fixed(13)),  // Pink
+            (GitStatus::New, Colour::Fixed(40)),         // Green3
+            (GitStatus::Deleted, Colour::Fixed(124)),    // Red3
+            (GitStatus::Renamed, Colour::Fixed(184)),    // Yellow3
+            (GitStatus::Copied, Colour::Fixed(184)),     // Yellow3
+            (GitStatus::Conflicted, Colour::Fixed(124)), // Red3
+            (GitStatus::Untracked, Colour::Fixed(172)),  // Orange3
+            (GitStatus::Ignored, Colour::Fixed(245)),    // Grey
+        ];
+        for (status, colour) in git_colors.iter() {
+            m.insert(Elem::GitStatus { status: *status }, *colour);
+        }
+
         m
     }
+
+    /// The colors of the dark scheme which are too pale to be read on a light background are
+    /// replaced by darker shades of the same hues.
+    fn get_light_scheme_colour_map() -> HashMap<Elem, Colour> {
+        let mut m = Self::get_dark_scheme_colour_map();
+        // User / Group
+        m.insert(Elem::User, Colour::Fixed(94)); // Orange4
+        m.insert(Elem::Group, Colour::Fixed(101)); // Wheat4
+
+        // Permissions
+        m.insert(Elem::Write, Colour::Fixed(136)); // DarkGoldenrod
+        m.insert(Elem::NoAccess, Colour::Fixed(243)); // Grey46
+
+        // File Types
+        for &exec in &[false, true] {
+            for &uid in &[false, true] {
+                let colour = if exec {
+                    Colour::Fixed(28) // Green4
+                } else {
+                    Colour::Fixed(136) // DarkGoldenrod
+                };
+                m.insert(Elem::File { exec, uid }, colour);
+            }
+        }
+        m.insert(Elem::Dir { uid: true }, Colour::Fixed(25)); // DeepSkyBlue4
+        m.insert(Elem::Dir { uid: false }, Colour::Fixed(25)); // DeepSkyBlue4
+        m.insert(Elem::MountPoint, Colour::Fixed(31)); // DeepSkyBlue3
+        m.insert(Elem::Pipe, Colour::Fixed(30)); // DarkCyan
+        m.insert(Elem::SymLink, Colour::Fixed(30)); // DarkCyan
+        m.insert(Elem::BlockDevice, Colour::Fixed(30)); // DarkCyan
+        m.insert(Elem::CharDevice, Colour::Fixed(130)); // DarkOrange3
+        m.insert(Elem::Socket, Colour::Fixed(30)); // DarkCyan
+        m.insert(Elem::Special, Colour::Fixed(30)); // DarkCyan
+        m.insert(Elem::Hidden, Colour::Fixed(243)); // Grey46
+
+        // Last Time Modified
+        m.insert(Elem::HourOld, Colour::Fixed(28)); // Green4
+        m.insert(Elem::DayOld, Colour::Fixed(29)); // SpringGreen4
+        m.insert(Elem::Older, Colour::Fixed(23)); // DeepSkyBlue4
+        m.insert(Elem::WeekOld, Colour::Fixed(23)); // DeepSkyBlue4
+
+        // Size
+        m.insert(Elem::NonFile, Colour::Fixed(243)); // Grey46
+        m.insert(Elem::FileSmall, Colour::Fixed(101)); // Wheat4
+        m.insert(Elem::FileMedium, Colour::Fixed(166)); // DarkOrange3
+        m.insert(Elem::FileLarge, Colour::Fixed(124)); // Red3
+        m.insert(Elem::SizeSmall, Colour::Fixed(28)); // Green4
+        m.insert(Elem::SizeMedium, Colour::Fixed(136)); // DarkGoldenrod
+        m.insert(Elem::SizeLarge, Colour::Fixed(160)); // Red3
+
+        // INode and hard links
+        m.insert(Elem::INode { valid: true }, Colour::Fixed(127)); // Magenta3
+        m.insert(Elem::INode { valid: false }, Colour::Fixed(243)); // Grey46
+        m.insert(Elem::Links { valid: true }, Colour::Fixed(127)); // Magenta3
+        m.insert(Elem::Links { valid: false }, Colour::Fixed(243)); // Grey46
+
+        // Extended attributes, tree counts, security contexts and file flags
+        m.insert(Elem::XAttr, Colour::Fixed(243)); // Grey46
+        m.insert(Elem::TreeCount, Colour::Fixed(67)); // SteelBlue
+        m.insert(Elem::Context, Colour::Fixed(94)); // Orange4
+        m.insert(Elem::GitIgnored, Colour::Fixed(248)); // Grey66
+        m.insert(Elem::FileFlags, Colour::Fixed(243)); // Grey46
+
+        // Git
+        let git_colors = [
+            (GitStatus::Unmodified, Colour::Fixed(243)), // Grey46
+            (GitStatus::Modified, Colour::Fixed(25)),    // DeepSkyBlue4
+            (GitStatus::TypeChange, Colour::Fixed(127)), // Magenta3
+            (GitStatus::New, Colour::Fixed(28)),         // Green4
+            (GitStatus::Renamed, Colour::Fixed(136)),    // DarkGoldenrod
+            (GitStatus::Copied, Colour::Fixed(136)),     // DarkGoldenrod
+            (GitStatus::Untracked, Colour::Fixed(130)),  // DarkOrange3
+            (GitStatus::Ignored, Colour::Fixed(243)),    // Grey46
+        ];
+        for (status, colour) in git_colors.iter() {
+            m.insert(Elem::GitStatus { status: *status }, *colour);
+        }
+
+        m
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Colors, Elem, SizeThresholds, Theme};
+    use crate::flags::ColorScheme;
+    use ansi_term::{Colour, Style};
+    use lscolors::LsColors;
+    use std::collections::HashMap;
+    use std::fs::{self, File};
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn colors_with_ls_colors(ls_colors: &str) -> Colors {
+        Colors {
+            colors: Some(&*super::DARK_SCHEME_COLOUR_MAP),
+            lscolors: Some(LsColors::from_string(ls_colors)),
+            theme: HashMap::new(),
+            extensions: HashMap::new(),
+            size_thresholds: SizeThresholds::default(),
+        }
+    }
+
+    #[test]
+    fn test_ls_colors_directory() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+
+        let colors = colors_with_ls_colors("di=01;31:xx=42:*.txt=35");
+
+        assert_eq!(
+            Style::new().bold().fg(Colour::Red).paint("dir"),
+            colors.colorize_using_path(String::from("dir"), &dir_path, &Elem::Dir { uid: false })
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_ls_colors_executable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("script");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(0o755))
+            .expect("failed to set permissions");
+
+        let colors = colors_with_ls_colors("ex=01;32:xx=42");
+        let elem = Elem::File {
+            exec: true,
+            uid: false,
+        };
+
+        assert_eq!(
+            Style::new().bold().fg(Colour::Green).paint("script"),
+            colors.colorize_using_path(String::from("script"), &file_path, &elem)
+        );
+        assert_eq!(
+            Style::new().bold().fg(Colour::Green).paint("script"),
+            colors.colorize(String::from("script"), &elem)
+        );
+    }
+
+    #[test]
+    fn test_ls_colors_extension() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("notes.txt");
+        File::create(&file_path).expect("failed to create file");
+
+        let colors = colors_with_ls_colors("*.txt=35");
+        let elem = Elem::File {
+            exec: false,
+            uid: false,
+        };
+
+        assert_eq!(
+            Colour::Purple.paint("notes.txt"),
+            colors.colorize_using_path(String::from("notes.txt"), &file_path, &elem)
+        );
+    }
+
+    #[test]
+    fn test_ls_colors_fallback_to_theme() {
+        let colors = colors_with_ls_colors("di=01;31:not-a-capability");
+
+        assert_eq!(
+            Colour::Fixed(230).paint("user"),
+            colors.colorize(String::from("user"), &Elem::User)
+        );
+    }
+
+    #[test]
+    fn test_theme_file_overrides_only_its_elements() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let theme_path = tmp_dir.path().join("theme.yaml");
+        fs::write(&theme_path, "dir: 196\nunknown: 1\n").expect("failed to write theme");
+
+        let colors = Colors::new(Theme::NoLscolors).with_theme_file(&theme_path);
+
+        assert_eq!(
+            Colour::Fixed(196).paint("dir"),
+            colors.colorize(String::from("dir"), &Elem::Dir { uid: false })
+        );
+        assert_eq!(
+            Colour::Fixed(230).paint("user"),
+            colors.colorize(String::from("user"), &Elem::User)
+        );
+        assert_eq!(
+            Colour::Fixed(184).paint("file"),
+            colors.colorize(
+                String::from("file"),
+                &Elem::File {
+                    exec: false,
+                    uid: false
+                }
+            )
+        );
+    }
+
+    #[test]
+    fn test_theme_file_takes_precedence_over_ls_colors() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+        let theme_path = tmp_dir.path().join("theme.yaml");
+        fs::write(&theme_path, "dir: \"#ff8000\"\n").expect("failed to write theme");
+
+        let colors = colors_with_ls_colors("di=01;31").with_theme_file(&theme_path);
+
+        assert_eq!(
+            Colour::RGB(255, 128, 0).paint("dir"),
+            colors.colorize_using_path(String::from("dir"), &dir_path, &Elem::Dir { uid: false })
+        );
+    }
+
+    #[test]
+    fn test_theme_file_without_colors() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let theme_path = tmp_dir.path().join("theme.yaml");
+        fs::write(&theme_path, "dir: red\n").expect("failed to write theme");
+
+        let colors = Colors::new(Theme::NoColor).with_theme_file(&theme_path);
+
+        assert_eq!(
+            Style::default().paint("dir"),
+            colors.colorize(String::from("dir"), &Elem::Dir { uid: false })
+        );
+    }
+
+    #[test]
+    fn test_theme_colour_values() {
+        let value = |yaml: &str| yaml_rust::YamlLoader::load_from_str(yaml).unwrap()[0].clone();
+
+        assert_eq!(
+            Some(Colour::Fixed(33)),
+            Colors::colour_from_yaml(&value("33"))
+        );
+        assert_eq!(
+            Some(Colour::Purple),
+            Colors::colour_from_yaml(&value("magenta"))
+        );
+        assert_eq!(
+            Some(Colour::RGB(0, 255, 16)),
+            Colors::colour_from_yaml(&value("\"#00ff10\""))
+        );
+        assert_eq!(None, Colors::colour_from_yaml(&value("256")));
+        assert_eq!(None, Colors::colour_from_yaml(&value("\"#00ff1\"")));
+        assert_eq!(None, Colors::colour_from_yaml(&value("pink")));
+    }
+
+    #[test]
+    fn test_theme_file_size_thresholds() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let theme_path = tmp_dir.path().join("theme.yaml");
+        fs::write(
+            &theme_path,
+            "size-small: 2\nsize-thresholds:\n  large: 4096\n",
+        )
+        .expect("failed to write theme");
+
+        let colors = Colors::new(Theme::NoLscolors).with_theme_file(&theme_path);
+
+        assert_eq!(
+            SizeThresholds {
+                medium: 1024,
+                large: 4096,
+            },
+            colors.size_thresholds()
+        );
+        assert_eq!(
+            Colour::Fixed(2).paint("size"),
+            colors.colorize(String::from("size"), &Elem::SizeSmall)
+        );
+    }
+
+    #[test]
+    fn test_theme_file_extensions_match_the_longest_one() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let theme_path = tmp_dir.path().join("theme.yaml");
+        fs::write(
+            &theme_path,
+            "extensions:\n  tar.gz: red\n  .GZ: blue\n  md: 33\n",
+        )
+        .expect("failed to write theme");
+
+        let colors = colors_with_ls_colors("*.gz=35").with_theme_file(&theme_path);
+        let file = Elem::File {
+            exec: false,
+            uid: false,
+        };
+        let colorize =
+            |name: &str| colors.colorize_using_path(String::from(name), Path::new(name), &file);
+
+        assert_eq!(Colour::Red.paint("a.tar.gz"), colorize("a.tar.gz"));
+        assert_eq!(Colour::Blue.paint("b.gz"), colorize("b.gz"));
+        assert_eq!(Colour::Blue.paint("C.TAR.XZ.GZ"), colorize("C.TAR.XZ.GZ"));
+        assert_eq!(Colour::Fixed(33).paint("README.md"), colorize("README.md"));
+        assert_eq!(Style::default().paint(".md"), colorize(".md"));
+    }
+
+    #[test]
+    fn test_theme_file_extensions_only_color_the_files() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let theme_path = tmp_dir.path().join("theme.yaml");
+        fs::write(&theme_path, "extensions:\n  d: red\n").expect("failed to write theme");
+
+        let colors = Colors::new(Theme::NoLscolors).with_theme_file(&theme_path);
+
+        assert_eq!(
+            Colour::Fixed(33).paint("conf.d"),
+            colors.colorize_using_path(
+                String::from("conf.d"),
+                Path::new("conf.d"),
+                &Elem::Dir { uid: false }
+            )
+        );
+    }
+
+    #[test]
+    fn test_light_scheme_replaces_the_pale_colors() {
+        let dir = Elem::Dir { uid: false };
+
+        let dark = Colors::new(Theme::NoLscolors).with_scheme(ColorScheme::Dark);
+        assert_eq!(
+            Colour::Fixed(33).paint("dir"),
+            dark.colorize(String::from("dir"), &dir)
+        );
+
+        let light = Colors::new(Theme::NoLscolors).with_scheme(ColorScheme::Light);
+        assert_eq!(
+            Colour::Fixed(25).paint("dir"),
+            light.colorize(String::from("dir"), &dir)
+        );
+        assert_eq!(
+            Colour::Fixed(94).paint("user"),
+            light.colorize(String::from("user"), &Elem::User)
+        );
+        // The colors readable on both backgrounds are kept.
+        assert_eq!(
+            Colour::Green.paint("r"),
+            light.colorize(String::from("r"), &Elem::Read)
+        );
+    }
+
+    #[test]
+    fn test_light_scheme_covers_all_the_elements() {
+        let dark = Colors::get_dark_scheme_colour_map();
+        let light = Colors::get_light_scheme_colour_map();
+        let mut dark_elems: Vec<String> = dark.keys().map(|elem| format!("{:?}", elem)).collect();
+        let mut light_elems: Vec<String> = light.keys().map(|elem| format!("{:?}", elem)).collect();
+        dark_elems.sort();
+        light_elems.sort();
+
+        assert_eq!(dark_elems, light_elems);
+    }
+
+    #[test]
+    fn test_light_scheme_without_colors() {
+        let colors = Colors::new(Theme::NoColor).with_scheme(ColorScheme::Light);
+        assert_eq!(
+            Style::default().paint("dir"),
+            colors.colorize(String::from("dir"), &Elem::Dir { uid: false })
+        );
+    }
 }
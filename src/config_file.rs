@@ -2,18 +2,27 @@
 ///! this.
 use std::fs::File;
 use std::io::prelude::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::print_error;
 
 #[cfg(not(windows))]
 use xdg::BaseDirectories;
+use yaml_rust::yaml::Hash;
 use yaml_rust::{Yaml, YamlLoader};
 
 const CONF_DIR: &str = "lsd";
 const CONF_FILE_NAME: &str = "config";
 const YAML_LONG_EXT: &str = "yaml";
 const YAML_SHORT_EXT: &str = "yml";
+/// The name of the config file of a directory, which overrides the other config files when the
+/// directory is listed.
+const DIRECTORY_CONF_FILE_NAME: &str = ".lsd.yaml";
+/// The key of the paths of the config files to merge below a config file.
+const INCLUDE_KEY: &str = "include";
+/// The key enabling the config files of the directories, which is only read from the config files
+/// of the user.
+const DIRECTORY_CONFIG_KEY: &str = "directory-config";
 
 /// A struct to hold an optional file path [String] and an optional [Yaml], and provides methods
 /// around error handling in a config file.
@@ -62,39 +71,158 @@ impl Config {
             _ => return Self::with_none(),
         }
 
-        let mut out_config;
-        let mut config_file;
-        match File::open(&config_file_long_path) {
-            Ok(result) => {
-                config_file = result;
-                out_config = Self::with_file(config_file_long_path.as_path().display().to_string());
-            }
-            Err(_) => match File::open(&config_file_short_path) {
-                Ok(result) => {
-                    config_file = result;
-                    out_config =
-                        Self::with_file(config_file_short_path.as_path().display().to_string());
-                }
-                Err(_) => return Self::with_none(),
+        Self::from_file_with_includes(&config_file_long_path)
+            .or_else(|| Self::from_file_with_includes(&config_file_short_path))
+            .unwrap_or_else(Self::with_none)
+    }
+
+    /// Merge the config file of the directory listed by `inputs` over this Config, as the
+    /// preferences for listing that directory.
+    ///
+    /// As the listed directories may not be trusted, this only happens when `enabled` by the
+    /// command line or by the "directory-config" value of this Config. The file is only looked
+    /// for when the inputs are a single directory, and only in that directory: neither its
+    /// parents nor the other inputs use it. It is ignored with a warning unless it is owned by the
+    /// current user and only writable by them, and its "include" value is not followed. The
+    /// warnings of the merged Config point to the file of the directory.
+    pub fn with_directory_overrides(self, inputs: &[PathBuf], enabled: bool) -> Self {
+        let enabled = enabled
+            || matches!(&self.yaml, Some(yaml) if yaml[DIRECTORY_CONFIG_KEY] == Yaml::Boolean(true));
+        let dir = match inputs {
+            [dir] if enabled && dir.is_dir() => dir,
+            _ => return self,
+        };
+
+        let path = dir.join(DIRECTORY_CONF_FILE_NAME);
+        if !path.is_file() {
+            return self;
+        }
+        if !is_trusted(&path) {
+            Self::with_file(path.display().to_string()).print_warning(
+                "Ignoring the directory config file, which is not owned by the current user or \
+                 is writable by others",
+            );
+            return self;
+        }
+
+        match Self::from_file(&path) {
+            Some(Self {
+                file,
+                yaml: Some(overriding),
+            }) => Self {
+                file,
+                yaml: Some(match self.yaml {
+                    Some(base) => merge_yaml(base, overriding),
+                    None => overriding,
+                }),
             },
+            _ => self,
+        }
+    }
+
+    /// This reads the YAML file at the given path like [from_file](Config::from_file), and merges
+    /// the files listed by its "include" value below it.
+    ///
+    /// The value is a path or a list of paths, relative to the directory of the including file.
+    /// The included files are merged in order, so that each one overrides the previous ones, and
+    /// the including file overrides them all. The hashes are merged key by key, and the other
+    /// values, like the lists, replace the included ones. The cycles of includes are ignored with
+    /// a warning.
+    pub fn from_file_with_includes(path: &Path) -> Option<Self> {
+        Self::from_file_including(path, &mut Vec::new())
+    }
+
+    /// Read the file at `path` and its includes, `ancestors` being the canonical paths of the
+    /// files including it, to detect the cycles.
+    fn from_file_including(path: &Path, ancestors: &mut Vec<PathBuf>) -> Option<Self> {
+        let mut config = Self::from_file(path)?;
+        let includes = match &config.yaml {
+            Some(yaml) => config.include_paths(&yaml[INCLUDE_KEY], path),
+            None => return Some(config),
+        };
+        if includes.is_empty() {
+            return Some(config);
+        }
+
+        ancestors.push(path.canonicalize().unwrap_or_else(|_| path.to_path_buf()));
+        let mut merged = Yaml::Hash(Hash::new());
+        for include in includes {
+            let canonical = include.canonicalize().unwrap_or_else(|_| include.clone());
+            if ancestors.contains(&canonical) {
+                config.print_warning(&format!(
+                    "Ignoring the include cycle through {}",
+                    include.display()
+                ));
+                continue;
+            }
+
+            match Self::from_file_including(&include, ancestors) {
+                Some(Self {
+                    yaml: Some(yaml), ..
+                }) => merged = merge_yaml(merged, yaml),
+                Some(_) => {}
+                None => config.print_warning(&format!(
+                    "Could not open the included config file {}",
+                    include.display()
+                )),
+            }
         }
+        ancestors.pop();
 
-        let mut config_content = String::new();
-        if let Err(error) = config_file.read_to_string(&mut config_content) {
+        if let Some(Yaml::Hash(mut hash)) = config.yaml.take() {
+            hash.remove(&Yaml::String(INCLUDE_KEY.to_string()));
+            config.yaml = Some(merge_yaml(merged, Yaml::Hash(hash)));
+        }
+        Some(config)
+    }
+
+    /// The paths of the files included by the "include" value of the file at `path`.
+    fn include_paths(&self, value: &Yaml, path: &Path) -> Vec<PathBuf> {
+        let values = match value {
+            Yaml::BadValue => return Vec::new(),
+            Yaml::String(_) => std::slice::from_ref(value),
+            Yaml::Array(values) => values.as_slice(),
+            _ => {
+                self.print_wrong_type_warning(INCLUDE_KEY, "string or a list of strings");
+                return Vec::new();
+            }
+        };
+
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        values
+            .iter()
+            .filter_map(|value| match value {
+                Yaml::String(include) => Some(dir.join(include)),
+                _ => {
+                    self.print_wrong_type_warning(INCLUDE_KEY, "string or a list of strings");
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// This reads the YAML file at the given path. It returns [None] when the file can not be
+    /// opened, and a Config without a [Yaml] when its contents can not be read or parsed.
+    pub fn from_file(path: &Path) -> Option<Self> {
+        let mut file = File::open(path).ok()?;
+        let mut out_config = Self::with_file(path.display().to_string());
+
+        let mut content = String::new();
+        if let Err(error) = file.read_to_string(&mut content) {
             print_error!("Found a config file, but could not read it: {}", error);
-            return out_config;
+            return Some(out_config);
         }
 
-        match YamlLoader::load_from_str(&config_content) {
+        match YamlLoader::load_from_str(&content) {
             Ok(result) => {
                 if !result.is_empty() {
                     out_config.yaml = Some(result[0].clone());
                 }
-                out_config
+                Some(out_config)
             }
             Err(error) => {
                 print_error!("Error parsing config: {}\n", error);
-                out_config
+                Some(out_config)
             }
         }
     }
@@ -173,3 +301,205 @@ impl Config {
         ));
     }
 }
+
+/// Whether the file at `path` is owned by the current user and not writable by the group or the
+/// others, so that nobody else could have written it.
+#[cfg(unix)]
+fn is_trusted(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    match path.metadata() {
+        Ok(meta) => meta.uid() == users::get_current_uid() && meta.mode() & 0o022 == 0,
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_trusted(_: &Path) -> bool {
+    true
+}
+
+/// Merge the `overriding` [Yaml] over the `base` one. Two hashes are merged key by key, and any
+/// other `overriding` value replaces the `base` one.
+fn merge_yaml(base: Yaml, overriding: Yaml) -> Yaml {
+    match (base, overriding) {
+        (Yaml::Hash(mut base), Yaml::Hash(overriding)) => {
+            for (key, value) in overriding {
+                let merged = match base.remove(&key) {
+                    Some(base_value) => merge_yaml(base_value, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            Yaml::Hash(base)
+        }
+        (_, overriding) => overriding,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Config;
+
+    use std::fs;
+    use yaml_rust::YamlLoader;
+
+    fn yaml(yaml_string: &str) -> yaml_rust::Yaml {
+        YamlLoader::load_from_str(yaml_string).unwrap()[0].clone()
+    }
+
+    #[test]
+    fn test_include_is_overridden_by_the_including_file() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("shared")).unwrap();
+        fs::write(
+            tmp_dir.path().join("shared/base.yaml"),
+            "date: relative\nicons:\n  when: never\n  theme: unicode\nblocks:\n  - size\n  - name\n",
+        )
+        .unwrap();
+        let path = tmp_dir.path().join("config.yaml");
+        fs::write(
+            &path,
+            "include: shared/base.yaml\nicons:\n  when: always\nblocks:\n  - name\n",
+        )
+        .unwrap();
+
+        let config = Config::from_file_with_includes(&path).unwrap();
+        assert_eq!(
+            Some(yaml(
+                "date: relative\nicons:\n  theme: unicode\n  when: always\nblocks:\n  - name\n"
+            )),
+            config.yaml
+        );
+        assert_eq!(Some(path.display().to_string()), config.file);
+    }
+
+    #[test]
+    fn test_includes_override_each_other_in_order() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        fs::write(
+            tmp_dir.path().join("first.yaml"),
+            "date: relative\nlayout: tree\n",
+        )
+        .unwrap();
+        fs::write(tmp_dir.path().join("second.yaml"), "layout: oneline\n").unwrap();
+        let path = tmp_dir.path().join("config.yaml");
+        fs::write(&path, "include:\n  - first.yaml\n  - second.yaml\n").unwrap();
+
+        let config = Config::from_file_with_includes(&path).unwrap();
+        assert_eq!(Some(yaml("date: relative\nlayout: oneline\n")), config.yaml);
+    }
+
+    #[test]
+    fn test_include_cycle_is_ignored() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        fs::write(
+            tmp_dir.path().join("other.yaml"),
+            "include: config.yaml\ndate: relative\nlayout: tree\n",
+        )
+        .unwrap();
+        let path = tmp_dir.path().join("config.yaml");
+        fs::write(&path, "include: other.yaml\nlayout: grid\n").unwrap();
+
+        let config = Config::from_file_with_includes(&path).unwrap();
+        assert_eq!(Some(yaml("date: relative\nlayout: grid\n")), config.yaml);
+    }
+
+    #[test]
+    fn test_missing_include_is_ignored() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("config.yaml");
+        fs::write(&path, "include: missing.yaml\nlayout: tree\n").unwrap();
+
+        let config = Config::from_file_with_includes(&path).unwrap();
+        assert_eq!(Some(yaml("layout: tree\n")), config.yaml);
+    }
+
+    #[test]
+    fn test_directory_config_overrides_the_config() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join(".lsd.yaml");
+        fs::write(&path, "size: bytes\nicons:\n  when: never\n").unwrap();
+
+        let config = Config::with_yaml(yaml(
+            "size: short\nicons:\n  theme: unicode\nlayout: tree\n",
+        ))
+        .with_directory_overrides(&[tmp_dir.path().to_path_buf()], true);
+        assert_eq!(
+            Some(yaml(
+                "layout: tree\nsize: bytes\nicons:\n  theme: unicode\n  when: never\n"
+            )),
+            config.yaml
+        );
+        assert_eq!(Some(path.display().to_string()), config.file);
+    }
+
+    #[test]
+    fn test_directory_config_only_applies_to_its_directory() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        fs::create_dir(tmp_dir.path().join("sub")).unwrap();
+        fs::write(tmp_dir.path().join(".lsd.yaml"), "size: bytes\n").unwrap();
+        fs::write(tmp_dir.path().join("file"), "").unwrap();
+
+        for inputs in &[
+            vec![tmp_dir.path().join("sub")],
+            vec![tmp_dir.path().join("file")],
+            vec![tmp_dir.path().to_path_buf(), tmp_dir.path().join("sub")],
+        ] {
+            let config = Config::with_none().with_directory_overrides(inputs, true);
+            assert_eq!(None, config.yaml);
+        }
+    }
+
+    #[test]
+    fn test_directory_config_is_opt_in() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        fs::write(tmp_dir.path().join(".lsd.yaml"), "size: bytes\n").unwrap();
+        let inputs = [tmp_dir.path().to_path_buf()];
+
+        let config =
+            Config::with_yaml(yaml("layout: tree\n")).with_directory_overrides(&inputs, false);
+        assert_eq!(Some(yaml("layout: tree\n")), config.yaml);
+
+        let config = Config::with_yaml(yaml("directory-config: true\n"))
+            .with_directory_overrides(&inputs, false);
+        assert_eq!(
+            Some(yaml("directory-config: true\nsize: bytes\n")),
+            config.yaml
+        );
+    }
+
+    #[test]
+    fn test_directory_config_does_not_follow_includes() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        fs::write(tmp_dir.path().join("base.yaml"), "layout: tree\n").unwrap();
+        fs::write(
+            tmp_dir.path().join(".lsd.yaml"),
+            "include: base.yaml\nsize: bytes\n",
+        )
+        .unwrap();
+
+        let config =
+            Config::with_none().with_directory_overrides(&[tmp_dir.path().to_path_buf()], true);
+        assert_eq!(Some(yaml("include: base.yaml\nsize: bytes\n")), config.yaml);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_directory_config_writable_by_others_is_ignored() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join(".lsd.yaml");
+        fs::write(&path, "size: bytes\n").unwrap();
+        let inputs = [tmp_dir.path().to_path_buf()];
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o664)).unwrap();
+        let config = Config::with_none().with_directory_overrides(&inputs, true);
+        assert_eq!(None, config.yaml);
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let config = Config::with_none().with_directory_overrides(&inputs, true);
+        assert_eq!(Some(yaml("size: bytes\n")), config.yaml);
+    }
+}
@@ -1,19 +1,65 @@
 use crate::color::{self, Colors};
 use crate::display;
-use crate::flags::{ColorOption, Display, Flags, IconOption, IconTheme, Layout, SortOrder};
+use crate::flags::{
+    Block, ColorOption, DirGrouping, DirSize, Display, Flags, HyperlinkOption, IconOption,
+    IconTheme, Layout, SortColumn, SortOrder,
+};
 use crate::icon::{self, Icons};
-use crate::meta::Meta;
+use crate::meta::name::DisplayOption;
+use crate::meta::{AccessControl, FileType, GitCache, Meta, XAttrs};
+use crate::progress::Spinner;
 use crate::{print_error, print_output, sort};
-use std::path::PathBuf;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
 
-#[cfg(not(target_os = "windows"))]
-use std::io;
 #[cfg(not(target_os = "windows"))]
 use std::os::unix::io::AsRawFd;
 
 #[cfg(target_os = "windows")]
 use terminal_size::terminal_size;
 
+/// The exit status of lsd, like the one of GNU ls. The listing is still printed when an issue
+/// happens, the most severe issue only changes the status.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExitCode {
+    OK = 0,
+    /// The minor issues, like a broken link which can not be dereferenced.
+    MinorIssue = 1,
+    /// The I/O errors, when an entry can not be read or a directory can not be listed.
+    MajorIssue = 2,
+}
+
+/// The most severe issue which happened during the run, as an [ExitCode].
+static EXIT_CODE: AtomicU8 = AtomicU8::new(ExitCode::OK as u8);
+
+impl ExitCode {
+    /// Record that an issue of this severity happened, unless a more severe one did already.
+    pub fn set_if_greater(self) {
+        let code = self as u8;
+        let mut current = EXIT_CODE.load(Ordering::Relaxed);
+        while current < code {
+            match EXIT_CODE.compare_exchange(current, code, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break,
+                Err(actual) => current = actual,
+            }
+        }
+    }
+
+    /// The most severe issue recorded so far.
+    pub fn current() -> Self {
+        match EXIT_CODE.load(Ordering::Relaxed) {
+            0 => Self::OK,
+            1 => Self::MinorIssue,
+            _ => Self::MajorIssue,
+        }
+    }
+}
+
+/// The number of entries whose metadata is read at once when the entries are streamed, which is
+/// enough to keep the threads reading it busy.
+const STREAM_CHUNK_SIZE: usize = 256;
+
 pub struct Core {
     flags: Flags,
     icons: Icons,
@@ -23,7 +69,7 @@ pub struct Core {
 }
 
 impl Core {
-    pub fn new(flags: Flags) -> Self {
+    pub fn new(mut flags: Flags) -> Self {
         // Check through libc if stdout is a tty. Unix specific so not on windows.
         // Determine color output availability (and initialize color output (for Windows 10))
         #[cfg(not(target_os = "windows"))]
@@ -40,12 +86,19 @@ impl Core {
 
         let mut inner_flags = flags.clone();
 
-        let color_theme = match (tty_available && console_color_ok, flags.color.when) {
+        // A dumb terminal can not display the colors and the icons, even though it is a tty.
+        let styles_available = tty_available && ColorOption::terminal_supports_styles();
+
+        let color_theme = match (styles_available && console_color_ok, flags.color.when) {
             (_, ColorOption::Never) | (false, ColorOption::Auto) => color::Theme::NoColor,
             _ => color::Theme::Default,
         };
 
-        let icon_theme = match (tty_available, flags.icons.when, flags.icons.theme) {
+        // Forcing the colors means the output is going to be displayed anyway (ex: lsd | less -R),
+        // so the icons are shown as well unless they are disabled.
+        let styled_output = styles_available || flags.color.when == ColorOption::Always;
+
+        let icon_theme = match (styled_output, flags.icons.when, flags.icons.theme) {
             (_, IconOption::Never, _) | (false, IconOption::Auto, _) => icon::Theme::NoIcon,
             (_, _, IconTheme::Fancy) => icon::Theme::Fancy,
             (_, _, IconTheme::Unicode) => icon::Theme::Unicode,
@@ -59,22 +112,145 @@ impl Core {
             inner_flags.layout = Layout::OneLine;
         };
 
+        flags.hyperlink = match (tty_available, flags.hyperlink) {
+            (true, HyperlinkOption::Auto) => HyperlinkOption::Always,
+            (false, HyperlinkOption::Auto) => HyperlinkOption::Never,
+            (_, hyperlink) => hyperlink,
+        };
+        if flags.hyperlink == HyperlinkOption::Always {
+            flags.current_dir = std::env::current_dir().ok();
+        }
+
         let sorters = sort::assemble_sorters(&flags);
+        let icons = Icons::new(icon_theme)
+            .with_overrides(flags.icons.overrides.clone())
+            .with_spacing(flags.icons.spacing.0);
+
+        let mut colors = Colors::new(color_theme).with_scheme(flags.color.scheme.resolve());
+        if let Some(path) = &flags.color.theme.0 {
+            colors = colors.with_theme_file(path);
+        }
 
         Self {
             flags,
             //display: Display::new(inner_flags),
-            colors: Colors::new(color_theme),
-            icons: Icons::new(icon_theme),
+            colors,
+            icons,
             sorters,
         }
     }
 
-    pub fn run(self, paths: Vec<PathBuf>) {
+    pub fn run(self, paths: Vec<PathBuf>) -> ExitCode {
+        let paths = match self.flags.stdin.delimiter() {
+            Some(delimiter) => read_paths(io::stdin().lock(), delimiter),
+            None => paths,
+        };
+
+        if self.can_stream() {
+            // Like print_output, stop with success when stdout is closed before the end of the
+            // listing (ex: lsd -1 --sort none | head).
+            if self.stream(paths, io::stdout().lock()).is_err() {
+                std::process::exit(0);
+            }
+            return ExitCode::current();
+        }
+
+        // The spinner is cleared before the listing is printed, so that they do not interleave.
+        let spinner = if self.flags.progress.0 && stderr_is_tty() {
+            Some(Spinner::start(io::stderr()))
+        } else {
+            None
+        };
+
         let mut meta_list = self.fetch(paths);
 
+        self.filter(&mut meta_list);
         self.sort(&mut meta_list);
-        self.display(&meta_list)
+
+        if let Some(spinner) = spinner {
+            spinner.stop();
+        }
+        self.display(&meta_list);
+
+        ExitCode::current()
+    }
+
+    /// Whether the entries can be written as soon as they are listed. This is only the case of the
+    /// oneline layout showing nothing but the names, which are not aligned with each other, when
+    /// the entries are not sorted, grouped, recursed into nor summed up, as all of them would be
+    /// needed first.
+    fn can_stream(&self) -> bool {
+        let flags = &self.flags;
+        flags.layout == Layout::OneLine
+            && flags.blocks.0 == [Block::Name]
+            && flags.sorting.column == SortColumn::None
+            && flags.sorting.dir_grouping == DirGrouping::None
+            && !flags.recursion.enabled
+            && !flags.summary.0
+            && !flags.json.0
+            && !flags.zero.0
+            && !flags.dired.0
+    }
+
+    /// List the `paths` like [run](Core::run) does with the grid, writing each entry to `out` as
+    /// soon as it is read. The files given by the user come first, then the content of each
+    /// directory.
+    fn stream<W: io::Write>(&self, paths: Vec<PathBuf>, out: W) -> io::Result<()> {
+        let mut stream = display::Stream::new(out, &self.flags, &self.colors, &self.icons);
+        let list_dirs = self.flags.display != Display::DirectoryItself;
+        // The links to directories are listed themselves in the oneline layout.
+        let is_dir =
+            |meta: &Meta| list_dirs && matches!(meta.file_type, FileType::Directory { .. });
+        let dereference = self.flags.dereference.0 || self.flags.dereference_command_line.0;
+
+        let mut metas = Vec::with_capacity(paths.len());
+        for path in paths {
+            match Meta::from_path(&path, dereference) {
+                Ok(mut meta) => {
+                    meta.detect_mount_point();
+                    if is_dir(&meta) || Self::matches(&self.flags, &meta) {
+                        metas.push(meta);
+                    }
+                }
+                Err(err) => {
+                    print_error!("lsd: {}: {}\n", path.display(), err);
+                    ExitCode::MajorIssue.set_if_greater();
+                }
+            }
+        }
+
+        for meta in metas.iter().filter(|meta| !is_dir(meta)) {
+            stream.entry(meta, &DisplayOption::None)?;
+        }
+
+        let display_folder_path = display::should_display_folder_path(0, &metas, &self.flags);
+        for meta in metas.iter().filter(|meta| is_dir(meta)) {
+            let entries = match meta.read_dir() {
+                Some(entries) => entries,
+                None => continue,
+            };
+            if display_folder_path {
+                stream.folder_path(meta)?;
+            }
+
+            let display_option = DisplayOption::Relative {
+                base_path: &meta.path,
+            };
+            let mut written = Ok(());
+            let listed = meta.stream_entries(entries, &self.flags, STREAM_CHUNK_SIZE, |entry| {
+                if written.is_ok() && (entry.is_implied_dir() || Self::matches(&self.flags, &entry))
+                {
+                    written = stream.entry(&entry, &display_option);
+                }
+            });
+            written?;
+            if let Err(err) = listed {
+                print_error!("lsd: {}: {}\n", meta.path.display(), err);
+                ExitCode::MajorIssue.set_if_greater();
+            }
+        }
+
+        Ok(())
     }
 
     fn fetch(&self, paths: Vec<PathBuf>) -> Vec<Meta> {
@@ -85,44 +261,141 @@ impl Core {
             _ => 1,
         };
 
+        // The inputs given on the command line are also dereferenced with -H, unlike the entries
+        // found when recursing into them.
+        let dereference = self.flags.dereference.0 || self.flags.dereference_command_line.0;
+
         for path in paths {
-            let mut meta = match Meta::from_path(&path, self.flags.dereference.0) {
+            let mut meta = match Meta::from_path(&path, dereference) {
                 Ok(meta) => meta,
                 Err(err) => {
                     print_error!("lsd: {}: {}\n", path.display(), err);
+                    ExitCode::MajorIssue.set_if_greater();
                     continue;
                 }
             };
 
+            meta.detect_mount_point();
+
             match self.flags.display {
-                Display::DirectoryItself => {
-                    meta_list.push(meta);
-                }
+                Display::DirectoryItself => {}
                 _ => {
                     match meta.recurse_into(depth, &self.flags) {
-                        Ok(content) => {
-                            meta.content = content;
-                            meta_list.push(meta);
-                        }
+                        Ok(content) => meta.content = content,
                         Err(err) => {
                             print_error!("lsd: {}: {}\n", path.display(), err);
+                            ExitCode::MajorIssue.set_if_greater();
                             continue;
                         }
                     };
                 }
             };
+
+            // The status of the entries of the listed directories is computed when recursing
+            // into them, only the inputs which are displayed themselves are left.
+            if self.flags.blocks.0.contains(&Block::GitStatus)
+                && (meta.content.is_none() || self.flags.layout == Layout::Tree)
+            {
+                let parent = match path.parent() {
+                    Some(parent) if parent != Path::new("") => parent,
+                    _ => Path::new("."),
+                };
+                meta.git_status = GitCache::new(parent).get(&path);
+            }
+
+            if self.flags.blocks.0.contains(&Block::XAttr) {
+                meta.xattrs = XAttrs::from(path.as_path());
+            }
+
+            if self.flags.blocks.0.contains(&Block::Context) {
+                meta.access_control = AccessControl::from(path.as_path());
+            }
+
+            meta_list.push(meta);
         }
+        if !self.flags.dereference_columns.0.is_empty() {
+            for meta in meta_list.iter_mut() {
+                meta.dereference_columns(&self.flags.dereference_columns);
+            }
+        }
+
+        if self.flags.block_usage.0 {
+            for meta in meta_list.iter_mut() {
+                meta.use_block_usage();
+            }
+        }
+
         if self.flags.total_size.0 {
             for meta in &mut meta_list.iter_mut() {
-                meta.calculate_total_size();
+                meta.calculate_total_size(self.flags.block_usage.0);
+            }
+        }
+
+        if self.flags.dir_size == DirSize::Count {
+            for meta in meta_list.iter_mut() {
+                meta.count_dir_entries();
             }
         }
 
         meta_list
     }
 
+    /// Remove the entries whose type, size or date was not asked for. The listed directories themselves
+    /// are kept, as well as the directories containing a matching entry, so they still show up in
+    /// the tree layout.
+    fn filter(&self, metas: &mut Vec<Meta>) {
+        if self.flags.file_types.0.is_empty()
+            && self.flags.size_filter.is_empty()
+            && self.flags.date_filter.is_empty()
+        {
+            return;
+        }
+
+        for meta in metas.iter_mut() {
+            if let Some(ref mut content) = meta.content {
+                Self::filter_content(&self.flags, content);
+            }
+        }
+
+        metas.retain(|meta| meta.content.is_some() || Self::matches(&self.flags, meta));
+    }
+
+    fn filter_content(flags: &Flags, metas: &mut Vec<Meta>) {
+        for meta in metas.iter_mut() {
+            if let Some(ref mut content) = meta.content {
+                Self::filter_content(flags, content);
+            }
+        }
+
+        metas.retain(|meta| {
+            let has_entries = match &meta.content {
+                Some(content) => content.iter().any(|entry| !entry.is_implied_dir()),
+                None => false,
+            };
+
+            meta.is_implied_dir() || Self::matches(flags, meta) || has_entries
+        });
+    }
+
+    /// Whether the entry has one of the types, and a size and a date in the ranges asked for. The
+    /// size of the directories is only compared when it is their total size.
+    fn matches(flags: &Flags, meta: &Meta) -> bool {
+        let size_matches = match meta.file_type {
+            FileType::Directory { .. } if !flags.total_size.0 => true,
+            _ => flags.size_filter.matches(meta.size.get_bytes()),
+        };
+        let date = meta
+            .date
+            .get(flags.time_kind)
+            .map(|time| time.to_timespec());
+
+        flags.file_types.matches(meta.file_type) && size_matches && flags.date_filter.matches(date)
+    }
+
     fn sort(&self, metas: &mut Vec<Meta>) {
-        metas.sort_unstable_by(|a, b| sort::by_meta(&self.sorters, a, b));
+        // The sort is stable, so that the entries which are not told apart, all of them when
+        // sorting is disabled, stay in directory order.
+        metas.sort_by(|a, b| sort::by_meta(&self.sorters, a, b));
 
         for meta in metas {
             if let Some(ref mut content) = meta.content {
@@ -132,12 +405,168 @@ impl Core {
     }
 
     fn display(&self, metas: &[Meta]) {
-        let output = if self.flags.layout == Layout::Tree {
+        if self.flags.zero.0 {
+            // Like print_output!, stop with success when the output can not be written.
+            if io::stdout().write_all(&display::zero(metas)).is_err() {
+                std::process::exit(0);
+            }
+            return;
+        }
+
+        let mut output = if self.flags.json.0 {
+            display::json(metas, &self.flags)
+        } else if self.flags.layout == Layout::Tree {
             display::tree(&metas, &self.flags, &self.colors, &self.icons)
         } else {
             display::grid(&metas, &self.flags, &self.colors, &self.icons)
         };
+        if self.flags.dired.0 {
+            output = display::dired(&output);
+        }
 
         print_output!("{}", output);
     }
 }
+
+/// Whether the standard error is a terminal, where the spinner can be shown.
+#[cfg(not(target_os = "windows"))]
+fn stderr_is_tty() -> bool {
+    unsafe { libc::isatty(io::stderr().as_raw_fd()) == 1 }
+}
+
+/// The spinner is not shown on Windows, where only the terminal of the standard output is
+/// detected.
+#[cfg(target_os = "windows")]
+fn stderr_is_tty() -> bool {
+    false
+}
+
+/// Read the paths ended by `delimiter` from `reader`, skipping the empty ones. The carriage
+/// returns ending the lines are dropped as well.
+fn read_paths<R: Read>(mut reader: R, delimiter: u8) -> Vec<PathBuf> {
+    let mut input = Vec::new();
+    if let Err(err) = reader.read_to_end(&mut input) {
+        print_error!("lsd: stdin: {}\n", err);
+        ExitCode::MajorIssue.set_if_greater();
+    }
+
+    input
+        .split(|byte| *byte == delimiter)
+        .map(|path| match path.split_last() {
+            Some((b'\r', rest)) if delimiter == b'\n' => rest,
+            _ => path,
+        })
+        .filter(|path| !path.is_empty())
+        .map(path_from_bytes)
+        .collect()
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    PathBuf::from(OsStr::from_bytes(bytes))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> PathBuf {
+    PathBuf::from(String::from_utf8_lossy(bytes).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::{read_paths, Core};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Flags;
+
+    use std::fs::File;
+    use std::io::{self, Write};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_read_paths_by_line() {
+        let input: &[u8] = b"b.txt\n\na dir/c\r\nd";
+        assert_eq!(
+            vec![
+                PathBuf::from("b.txt"),
+                PathBuf::from("a dir/c"),
+                PathBuf::from("d")
+            ],
+            read_paths(input, b'\n')
+        );
+    }
+
+    #[test]
+    fn test_read_paths_by_null() {
+        let input: &[u8] = b"two\nlines\0other\0";
+        assert_eq!(
+            vec![PathBuf::from("two\nlines"), PathBuf::from("other")],
+            read_paths(input, b'\0')
+        );
+    }
+
+    fn core(args: &[&str]) -> Core {
+        let argv = [&["lsd"], args].concat();
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        Core::new(Flags::configure_from(&matches, &Config::with_none()).unwrap())
+    }
+
+    /// A writer keeping what is written between each flush.
+    #[derive(Default)]
+    struct Flushes {
+        pending: Vec<u8>,
+        flushed: Vec<String>,
+    }
+
+    impl Write for Flushes {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.pending.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            let pending = std::mem::take(&mut self.pending);
+            self.flushed.push(String::from_utf8(pending).unwrap());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_can_stream() {
+        assert!(core(&["--oneline", "--sort=none"]).can_stream());
+        assert!(!core(&["--oneline"]).can_stream());
+        assert!(!core(&["--sort=none"]).can_stream());
+        assert!(!core(&["--long", "--sort=none"]).can_stream());
+        assert!(!core(&["--oneline", "--sort=none", "--group-dirs=first"]).can_stream());
+        assert!(!core(&["--oneline", "--sort=none", "--recursive"]).can_stream());
+    }
+
+    #[test]
+    fn test_stream_flushes_each_entry() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        for name in &["a", "b", "c"] {
+            File::create(tmp_dir.path().join(name)).expect("failed to create file");
+        }
+        let file = tmp_dir.path().join("a");
+
+        let mut out = Flushes::default();
+        core(&["--oneline", "--sort=none"])
+            .stream(vec![file, tmp_dir.path().to_path_buf()], &mut out)
+            .unwrap();
+
+        // The file given by the user, then the path of the directory and each of its entries.
+        assert_eq!(5, out.flushed.len());
+        assert_eq!(
+            format!("{}\n", tmp_dir.path().join("a").display()),
+            out.flushed[0]
+        );
+        assert_eq!(format!("\n{}:\n", tmp_dir.path().display()), out.flushed[1]);
+        let mut entries = out.flushed[2..].to_vec();
+        entries.sort();
+        assert_eq!(vec!["a\n", "b\n", "c\n"], entries);
+        assert!(out.pending.is_empty());
+    }
+}
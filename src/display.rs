@@ -1,23 +1,62 @@
-use crate::color::{ColoredString, Colors};
-use crate::flags::{Block, Display, Flags, Layout};
+use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::{Block, Blocks, Display, Flags, Layout, PermissionFlag, SizeFlag};
 use crate::icon::Icons;
 use crate::meta::name::DisplayOption;
-use crate::meta::{FileType, Meta};
+use crate::meta::{FileType, Meta, Size};
 use ansi_term::{ANSIString, ANSIStrings};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::env;
+use std::io::{self, Write};
+use std::path::Path;
 use term_grid::{Cell, Direction, Filling, Grid, GridOptions};
 use terminal_size::terminal_size;
 use unicode_width::UnicodeWidthStr;
 
-const EDGE: &str = "\u{251c}\u{2500}\u{2500}"; // "├──"
-const LINE: &str = "\u{2502}  "; // "├  "
-const CORNER: &str = "\u{2514}\u{2500}\u{2500}"; // "└──"
-const BLANK: &str = "   ";
+const EDGE: &str = "\u{251c}"; // "├"
+const LINE: &str = "\u{2502}"; // "│"
+const CORNER: &str = "\u{2514}"; // "└"
+const RULE: &str = "\u{2500}"; // "─"
+
+/// The markers around the names of the entries and the paths of the listed directories in the
+/// output of the dired mode, replaced by their byte offsets. They are operating system commands,
+/// which take no width in the grid.
+pub const DIRED_NAME_START: &str = "\u{1b}]dired;name\u{7}";
+pub const DIRED_PATH_START: &str = "\u{1b}]dired;path\u{7}";
+pub const DIRED_END: &str = "\u{1b}]dired;end\u{7}";
+/// The start shared by all the markers of the dired mode.
+const DIRED_MARKER: &str = "\u{1b}]dired;";
+
+/// The branches drawn before the entries of the tree layout, which are all as wide as the
+/// indentation of a depth, like "├──", "│  ", "└──" and "   " with the default one of 3 columns.
+struct Branches {
+    edge: String,
+    line: String,
+    corner: String,
+    blank: String,
+}
+
+impl Branches {
+    fn new(indent: usize) -> Self {
+        let rule = RULE.repeat(indent - 1);
+        Self {
+            edge: String::from(EDGE) + &rule,
+            line: String::from(LINE) + &" ".repeat(indent - 1),
+            corner: String::from(CORNER) + &rule,
+            blank: " ".repeat(indent),
+        }
+    }
+}
 
 pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
-    let term_width = match terminal_size() {
-        Some((w, _)) => Some(w.0 as usize),
-        None => None,
+    let term_width = match flags.width.0 {
+        // A width of 0 means one entry per line, which is what the grid does without a width.
+        Some(0) => None,
+        Some(width) => Some(width),
+        None => match terminal_size() {
+            Some((w, _)) => Some(w.0 as usize),
+            None => columns_width(env::var("COLUMNS").ok()),
+        },
     };
 
     inner_display_grid(
@@ -31,8 +70,255 @@ pub fn grid(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> St
     )
 }
 
+/// Writes the entries of the oneline layout which only shows the names as soon as they are
+/// listed, instead of once all of them are. Each line is flushed on its own, so that the first
+/// entries of a large directory show up without waiting for the last ones.
+pub struct Stream<'a, W: Write> {
+    out: W,
+    flags: &'a Flags,
+    colors: &'a Colors,
+    icons: &'a Icons,
+    /// Whether anything was written yet, so that the first folder path does not start the output
+    /// with a blank line.
+    written: bool,
+}
+
+impl<'a, W: Write> Stream<'a, W> {
+    pub fn new(out: W, flags: &'a Flags, colors: &'a Colors, icons: &'a Icons) -> Self {
+        Self {
+            out,
+            flags,
+            colors,
+            icons,
+            written: false,
+        }
+    }
+
+    /// Write the line of an entry, like the grid does with one entry per line.
+    pub fn entry(&mut self, meta: &Meta, display_option: &DisplayOption) -> io::Result<()> {
+        let blocks = get_output(
+            meta,
+            self.colors,
+            self.icons,
+            self.flags,
+            display_option,
+            &HashMap::new(),
+        );
+        let mut line: String = blocks.iter().map(ToString::to_string).collect();
+        line.push('\n');
+        self.write(&line)
+    }
+
+    /// Write the path of a directory given by the user, above its entries.
+    pub fn folder_path(&mut self, meta: &Meta) -> io::Result<()> {
+        let mut output = String::new();
+        if self.written {
+            output.push('\n');
+        }
+        output += &display_folder_path(meta, self.flags);
+        self.write(&output)
+    }
+
+    fn write(&mut self, output: &str) -> io::Result<()> {
+        self.written = true;
+        self.out.write_all(output.as_bytes())?;
+        self.out.flush()
+    }
+}
+
+/// The width set by the `COLUMNS` environment variable, used when the output is not a terminal,
+/// like in the shells. The values other than a positive integer are ignored.
+fn columns_width(value: Option<String>) -> Option<usize> {
+    match value?.parse::<usize>() {
+        Ok(width) if width > 0 => Some(width),
+        _ => None,
+    }
+}
+
 pub fn tree(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> String {
-    inner_display_tree(metas, &flags, colors, icons, 0, "")
+    let columns = if flags.blocks.0.len() > 1 && flags.blocks.0[0] == Block::Name {
+        Some(TreeColumns::new(metas, flags, colors, icons))
+    } else {
+        None
+    };
+    inner_display_tree(metas, flags, colors, icons, 0, "", columns.as_ref())
+}
+
+/// The widths shared by the whole tree when the name is the first block, so that the blocks
+/// trailing the names line up across the depths.
+struct TreeColumns {
+    /// The width of the branches and the name of the widest entry.
+    name_width: usize,
+    padding_rules: HashMap<Block, usize>,
+}
+
+impl TreeColumns {
+    fn new(metas: &[Meta], flags: &Flags, colors: &Colors, icons: &Icons) -> Self {
+        let name_flags = Flags {
+            blocks: Blocks(vec![Block::Name]),
+            ..flags.clone()
+        };
+        let mut columns = Self {
+            name_width: 0,
+            padding_rules: HashMap::new(),
+        };
+        columns.include(metas, flags, &name_flags, colors, icons, 0);
+        columns
+    }
+
+    /// Widen the columns to fit the `metas` at `depth` and their content.
+    fn include(
+        &mut self,
+        metas: &[Meta],
+        flags: &Flags,
+        name_flags: &Flags,
+        colors: &Colors,
+        icons: &Icons,
+        depth: usize,
+    ) {
+        for (block, width) in get_padding_rules(metas, flags) {
+            let max_width = self.padding_rules.entry(block).or_insert(0);
+            *max_width = (*max_width).max(width);
+        }
+
+        for meta in metas {
+            let name = get_output(
+                meta,
+                colors,
+                icons,
+                name_flags,
+                &DisplayOption::FileName,
+                &HashMap::new(),
+            );
+            let width = tree_prefix_width(depth, flags.tree_indent.0)
+                + get_visible_width(&name[0].to_string());
+            self.name_width = self.name_width.max(width);
+
+            if let Some(content) = &meta.content {
+                self.include(content, flags, name_flags, colors, icons, depth + 1);
+            }
+        }
+    }
+}
+
+/// The width of the branches drawn before an entry at `depth` in the tree, which are `indent`
+/// columns wide.
+fn tree_prefix_width(depth: usize, indent: usize) -> usize {
+    match depth {
+        0 => 0,
+        // The parent branches, then the branch of the entry followed by a space.
+        _ => depth * indent + 1,
+    }
+}
+
+/// The representation of a [Meta] in the JSON output.
+#[derive(Serialize)]
+struct JsonEntry {
+    name: String,
+    path: String,
+    file_type: &'static str,
+    size: u64,
+    mtime: i64,
+    permissions: String,
+    user: String,
+    group: String,
+    symlink_target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    contents: Option<Vec<JsonEntry>>,
+}
+
+impl JsonEntry {
+    fn new(meta: &Meta, flags: &Flags, contents: Option<Vec<JsonEntry>>) -> Self {
+        Self {
+            name: meta.name.file_name().to_string(),
+            path: meta.path.to_string_lossy().to_string(),
+            file_type: meta.file_type.name(),
+            size: meta.size.get_bytes(),
+            mtime: meta.date.timestamp(),
+            permissions: format!("{:04o}", meta.permissions.octal()),
+            user: meta.owner.user(flags),
+            group: meta.owner.group(flags),
+            symlink_target: meta.symlink.symlink_string(flags.symlink_target),
+            contents,
+        }
+    }
+}
+
+pub fn json(metas: &[Meta], flags: &Flags) -> String {
+    let entries = if flags.layout == Layout::Tree {
+        json_tree_entries(metas, flags)
+    } else {
+        let mut entries = Vec::new();
+        for meta in metas {
+            // Like the grid, list the contents of the directories given by the
+            // user in place of the directories themselves.
+            match &meta.content {
+                Some(content) => json_flat_entries(content, flags, &mut entries),
+                None => entries.push(JsonEntry::new(meta, flags, None)),
+            }
+        }
+        entries
+    };
+
+    let mut output = serde_json::to_string(&entries).expect("failed to serialize to JSON");
+    output.push('\n');
+    output
+}
+
+fn json_flat_entries(metas: &[Meta], flags: &Flags, entries: &mut Vec<JsonEntry>) {
+    for meta in metas {
+        entries.push(JsonEntry::new(meta, flags, None));
+        if let Some(content) = &meta.content {
+            json_flat_entries(content, flags, entries);
+        }
+    }
+}
+
+fn json_tree_entries(metas: &[Meta], flags: &Flags) -> Vec<JsonEntry> {
+    metas
+        .iter()
+        .map(|meta| {
+            let contents = meta
+                .content
+                .as_ref()
+                .map(|content| json_tree_entries(content, flags));
+            JsonEntry::new(meta, flags, contents)
+        })
+        .collect()
+}
+
+/// The paths of the listing, each ended by a NUL character, for the tools like `xargs -0`. Like
+/// the grid, the contents of the directories given by the user are listed in place of the
+/// directories themselves, and the recursed into directories are followed by their contents.
+pub fn zero(metas: &[Meta]) -> Vec<u8> {
+    let mut output = Vec::new();
+    for meta in metas {
+        match &meta.content {
+            Some(content) => zero_entries(content, &mut output),
+            None => push_zero_path(&meta.path, &mut output),
+        }
+    }
+
+    output
+}
+
+fn zero_entries(metas: &[Meta], output: &mut Vec<u8>) {
+    for meta in metas {
+        push_zero_path(&meta.path, output);
+        if let Some(content) = &meta.content {
+            zero_entries(content, output);
+        }
+    }
+}
+
+/// Push the bytes of `path` followed by a NUL character. The paths are kept as they are on Unix,
+/// even when they are not valid UTF-8.
+fn push_zero_path(path: &Path, output: &mut Vec<u8>) {
+    #[cfg(unix)]
+    output.extend_from_slice(std::os::unix::ffi::OsStrExt::as_bytes(path.as_os_str()));
+    #[cfg(windows)]
+    output.extend_from_slice(path.to_string_lossy().as_bytes());
+    output.push(b'\0');
 }
 
 fn inner_display_grid(
@@ -49,12 +335,16 @@ fn inner_display_grid(
     let padding_rules = get_padding_rules(&metas, flags);
     let mut grid = match flags.layout {
         Layout::OneLine => Grid::new(GridOptions {
-            filling: Filling::Spaces(1),
+            filling: Filling::Spaces(flags.padding.blocks()),
             direction: Direction::LeftToRight,
         }),
         _ => Grid::new(GridOptions {
-            filling: Filling::Spaces(2),
-            direction: Direction::TopToBottom,
+            filling: Filling::Spaces(flags.padding.grid()),
+            direction: if flags.across.0 {
+                Direction::LeftToRight
+            } else {
+                Direction::TopToBottom
+            },
         }),
     };
 
@@ -63,11 +353,14 @@ fn inner_display_grid(
     // asked to display the directory itself (rather than its contents).
     let skip_dirs = (depth == 0) && (flags.display != Display::DirectoryItself);
 
+    let mut cells = Vec::new();
+    let mut displayed_metas = Vec::new();
+
     // print the files first.
     for meta in metas {
         // Maybe skip showing the directory meta now; show its contents later.
         if skip_dirs
-            && (matches!(meta.file_type, FileType::Directory{..})
+            && (matches!(meta.file_type, FileType::Directory { .. })
                 || (matches!(meta.file_type, FileType::SymLink { is_dir: true })
                     && flags.layout != Layout::OneLine))
         {
@@ -86,11 +379,32 @@ fn inner_display_grid(
         for block in blocks {
             let block_str = block.to_string();
 
-            grid.add(Cell {
+            cells.push(Cell {
                 width: get_visible_width(&block_str),
                 contents: block_str,
             });
         }
+
+        displayed_metas.push(meta);
+    }
+
+    // Only print a header above an actual listing, with one entry per line.
+    let display_header = flags.header.0
+        && flags.layout == Layout::OneLine
+        && flags.blocks.0.len() > 1
+        && !cells.is_empty();
+    if display_header {
+        for block in flags.blocks.0.iter() {
+            let header = colors.colorize_header(block.get_header().to_string());
+            grid.add(Cell {
+                width: block.get_header().len(),
+                contents: header.to_string(),
+            });
+        }
+    }
+
+    for cell in cells {
+        grid.add(cell);
     }
 
     if flags.layout == Layout::Grid {
@@ -106,17 +420,41 @@ fn inner_display_grid(
         } else {
             output += &grid.fit_into_columns(1).to_string();
         }
+    } else if flags.extended.0 {
+        let content = grid.fit_into_columns(flags.blocks.0.len()).to_string();
+        let mut lines = content.lines();
+
+        if display_header {
+            output += lines.next().unwrap();
+            output += "\n";
+        }
+
+        for (line, meta) in lines.zip(displayed_metas) {
+            output += line;
+            output += "\n";
+            output += &display_xattr_names(meta, "");
+        }
     } else {
         output += &grid.fit_into_columns(flags.blocks.0.len()).to_string();
     }
 
+    // The metas below the inputs given by the user are the content of a directory.
+    if flags.summary.0 && depth > 0 {
+        output += &display_summary(metas, colors, flags);
+    }
+
     let should_display_folder_path = should_display_folder_path(depth, &metas, &flags);
 
     // print the folder content
     for meta in metas {
         if meta.content.is_some() {
             if should_display_folder_path {
-                output += &display_folder_path(&meta);
+                // The headers are separated from what is listed before them, but the first one
+                // does not start the output with a blank line.
+                if !output.is_empty() {
+                    output.push('\n');
+                }
+                output += &display_folder_path(meta, flags);
             }
 
             let display_option = DisplayOption::Relative {
@@ -145,30 +483,45 @@ fn inner_display_tree(
     icons: &Icons,
     depth: usize,
     prefix: &str,
+    columns: Option<&TreeColumns>,
 ) -> String {
     let mut output = String::new();
     let last_idx = metas.len();
 
-    let padding_rules = get_padding_rules(&metas, flags);
+    let padding_rules = match columns {
+        Some(columns) => columns.padding_rules.clone(),
+        None => get_padding_rules(metas, flags),
+    };
 
     let mut grid = Grid::new(GridOptions {
-        filling: Filling::Spaces(1),
+        filling: Filling::Spaces(flags.padding.blocks()),
         direction: Direction::LeftToRight,
     });
 
     for meta in metas.iter() {
-        for block in get_output(
+        for (index, block) in get_output(
             &meta,
             &colors,
             &icons,
             &flags,
             &DisplayOption::FileName,
             &padding_rules,
-        ) {
-            let block_str = block.to_string();
+        )
+        .into_iter()
+        .enumerate()
+        {
+            let mut block_str = block.to_string();
+            let mut width = get_visible_width(&block_str);
+
+            // Pad the names to the width of the widest one of the whole tree.
+            if let (0, Some(columns)) = (index, columns) {
+                let name_width = columns.name_width - tree_prefix_width(depth, flags.tree_indent.0);
+                block_str += &" ".repeat(name_width.saturating_sub(width));
+                width = width.max(name_width);
+            }
 
             grid.add(Cell {
-                width: get_visible_width(&block_str),
+                width,
                 contents: block_str,
             });
         }
@@ -177,16 +530,19 @@ fn inner_display_tree(
     let content = grid.fit_into_columns(flags.blocks.0.len()).to_string();
     let mut lines = content.lines();
 
+    let branches = Branches::new(flags.tree_indent.0);
     for (idx, meta) in metas.iter().enumerate() {
-        let is_last_folder_elem = idx + 1 != last_idx;
+        // The last entry of a folder closes its branch, and its descendants no longer need the
+        // vertical line which links the following siblings.
+        let is_last_folder_elem = idx + 1 == last_idx;
 
         if depth > 0 {
             output += prefix;
 
             if is_last_folder_elem {
-                output += EDGE;
+                output += &branches.corner;
             } else {
-                output += CORNER;
+                output += &branches.edge;
             }
             output += " ";
         }
@@ -194,17 +550,21 @@ fn inner_display_tree(
         output += &String::from(lines.next().unwrap());
         output += "\n";
 
-        if meta.content.is_some() {
-            let mut new_prefix = String::from(prefix);
+        let mut new_prefix = String::from(prefix);
 
-            if depth > 0 {
-                if is_last_folder_elem {
-                    new_prefix += LINE;
-                } else {
-                    new_prefix += BLANK;
-                }
+        if depth > 0 {
+            if is_last_folder_elem {
+                new_prefix += &branches.blank;
+            } else {
+                new_prefix += &branches.line;
             }
+        }
 
+        if flags.extended.0 {
+            output += &display_xattr_names(meta, &new_prefix);
+        }
+
+        if meta.content.is_some() {
             output += &inner_display_tree(
                 &meta.content.as_ref().unwrap(),
                 &flags,
@@ -212,6 +572,7 @@ fn inner_display_tree(
                 icons,
                 depth + 1,
                 &new_prefix,
+                columns,
             );
         }
     }
@@ -219,7 +580,7 @@ fn inner_display_tree(
     output
 }
 
-fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bool {
+pub fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bool {
     if depth > 0 {
         true
     } else {
@@ -236,15 +597,125 @@ fn should_display_folder_path(depth: usize, metas: &[Meta], flags: &Flags) -> bo
     }
 }
 
-fn display_folder_path(meta: &Meta) -> String {
+fn display_folder_path(meta: &Meta, flags: &Flags) -> String {
     let mut output = String::new();
-    output.push('\n');
-    output += &meta.path.to_string_lossy();
+    if flags.dired.0 {
+        output += DIRED_PATH_START;
+        output += &meta.path.to_string_lossy();
+        output += DIRED_END;
+    } else {
+        output += &meta.path.to_string_lossy();
+    }
     output += ":\n";
 
     output
 }
 
+/// Turn the listing whose names and paths are marked for the dired mode of Emacs into the output
+/// of `ls --dired`. The lines but the empty ones are indented by two spaces, and are followed by
+/// the `//DIRED//` line of the byte offsets where each name starts and ends, then the
+/// `//SUBDIRED//` line of the ones of the paths of the listed directories, when there are any.
+pub fn dired(marked: &str) -> String {
+    let mut output = String::with_capacity(marked.len());
+    let mut names = Vec::new();
+    let mut paths = Vec::new();
+    let mut in_path = false;
+
+    // The lines, each with its newline.
+    let mut start = 0;
+    let ends = marked
+        .match_indices('\n')
+        .map(|(index, _)| index + 1)
+        .chain(std::iter::once(marked.len()));
+    for end in ends {
+        let line = &marked[start..end];
+        start = end;
+        if line.is_empty() {
+            continue;
+        }
+
+        if line != "\n" {
+            output += "  ";
+        }
+
+        let mut rest = line;
+        while let Some(index) = rest.find(DIRED_MARKER) {
+            output += &rest[..index];
+            rest = &rest[index..];
+
+            let offsets = if rest.starts_with(DIRED_NAME_START) {
+                rest = &rest[DIRED_NAME_START.len()..];
+                in_path = false;
+                &mut names
+            } else if rest.starts_with(DIRED_PATH_START) {
+                rest = &rest[DIRED_PATH_START.len()..];
+                in_path = true;
+                &mut paths
+            } else if rest.starts_with(DIRED_END) {
+                rest = &rest[DIRED_END.len()..];
+                if in_path {
+                    &mut paths
+                } else {
+                    &mut names
+                }
+            } else {
+                output += DIRED_MARKER;
+                rest = &rest[DIRED_MARKER.len()..];
+                continue;
+            };
+            offsets.push(output.len());
+        }
+        output += rest;
+    }
+
+    let line = |label: &str, offsets: &[usize]| {
+        let mut line = String::from(label);
+        for offset in offsets {
+            line += &format!(" {}", offset);
+        }
+        line + "\n"
+    };
+    output += &line("//DIRED//", &names);
+    if !paths.is_empty() {
+        output += &line("//SUBDIRED//", &paths);
+    }
+
+    output
+}
+
+/// The number of entries listed in a directory and their combined size.
+fn display_summary(metas: &[Meta], colors: &Colors, flags: &Flags) -> String {
+    let size = Size::new(Meta::total_bytes(metas));
+
+    let mut strings = vec![size.render_value(colors, flags)];
+    if !size.unit_string(flags).is_empty() {
+        if flags.size != SizeFlag::Short {
+            strings.push(ColoredString::from(" "));
+        }
+        strings.push(size.render_unit(colors, flags));
+    }
+
+    format!(
+        "{} {}, {}\n",
+        metas.len(),
+        if metas.len() == 1 { "entry" } else { "entries" },
+        ANSIStrings(&strings)
+    )
+}
+
+/// The names of the extended attributes of the entry, one per indented line.
+fn display_xattr_names(meta: &Meta, prefix: &str) -> String {
+    let mut output = String::new();
+    for name in meta.xattrs.names() {
+        output += prefix;
+        output += "    ";
+        output += name;
+        output += "\n";
+    }
+
+    output
+}
+
 fn get_output<'a>(
     meta: &'a Meta,
     colors: &'a Colors,
@@ -256,42 +727,95 @@ fn get_output<'a>(
     let mut strings: Vec<ANSIString> = Vec::new();
     for block in flags.blocks.0.iter() {
         match block {
-            Block::INode => strings.push(meta.inode.render(colors)),
+            Block::INode => strings.push(meta.inode.render(colors, padding_rules[&Block::INode])),
+            Block::Links => strings.push(meta.links.render(colors, padding_rules[&Block::Links])),
+            Block::XAttr => strings.push(meta.xattrs.render(colors)),
+            Block::Context => strings.push(
+                meta.access_control
+                    .render(colors, padding_rules[&Block::Context]),
+            ),
+            Block::FileFlags => strings.push(
+                meta.file_flags
+                    .render(colors, padding_rules[&Block::FileFlags]),
+            ),
+            Block::FileTypeChar => {
+                strings.push(meta.column_file_type(flags).render_ls_char(colors))
+            }
+            #[cfg(windows)]
+            Block::WinAttributes => strings.push(meta.attributes.render(colors)),
+            #[cfg(not(windows))]
+            Block::WinAttributes => {}
+            Block::GitStatus => match &meta.git_status {
+                Some(status) => strings.push(status.render(colors)),
+                None => strings.push(ColoredString::from("  ")),
+            },
             Block::Permission => {
-                let s: &[ColoredString] = &[
-                    meta.file_type.render(colors),
-                    meta.permissions.render(colors),
-                ];
-                let res = ANSIStrings(s).to_string();
-                strings.push(ColoredString::from(res));
+                // The resolved links are described by their target, when it exists.
+                let (file_type, permissions) = match (
+                    flags.resolve_symlinks.0,
+                    meta.symlink.target_type(),
+                    meta.symlink.target_permissions(),
+                ) {
+                    (true, Some(file_type), Some(permissions)) => (file_type, permissions),
+                    _ => (meta.column_file_type(flags), &meta.permissions),
+                };
+
+                match flags.permission {
+                    PermissionFlag::Rwx => {
+                        let s: &[ColoredString] = &[
+                            file_type.render(colors),
+                            permissions.render(colors, file_type, flags),
+                        ];
+                        let res = ANSIStrings(s).to_string();
+                        strings.push(ColoredString::from(res));
+                    }
+                    PermissionFlag::Octal => strings.push(permissions.render_octal(colors)),
+                }
             }
-            Block::User => strings.push(meta.owner.render_user(colors)),
-            Block::Group => strings.push(meta.owner.render_group(colors)),
+            Block::User => strings.push(meta.owner.render_user(colors, flags)),
+            Block::Group => strings.push(meta.owner.render_group(colors, flags)),
             Block::Size => strings.push(meta.size.render(
                 colors,
                 &flags,
                 padding_rules[&Block::SizeValue],
             )),
             Block::SizeValue => strings.push(meta.size.render_value(colors, flags)),
+            Block::Device if meta.device.is_device() => {
+                strings.push(meta.device.render(colors, padding_rules[&Block::Device]))
+            }
+            Block::Device => strings.push(meta.size.render(
+                colors,
+                flags,
+                padding_rules[&Block::SizeValue],
+            )),
             Block::Date => strings.push(meta.date.render(colors, &flags)),
             Block::Name => {
-                let s: String =
-                    if flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid {
-                        ANSIStrings(&[
-                            meta.name.render(colors, icons, &display_option),
-                            meta.indicator.render(&flags),
-                        ])
-                        .to_string()
-                    } else {
-                        ANSIStrings(&[
-                            meta.name.render(colors, icons, &display_option),
-                            meta.indicator.render(&flags),
-                            meta.symlink.render(colors, &flags),
-                        ])
-                        .to_string()
-                    };
-
-                strings.push(ColoredString::from(s));
+                let mut name_strings = vec![
+                    meta.name.render(colors, icons, display_option, flags),
+                    meta.indicator.render(flags),
+                ];
+                if !(flags.no_symlink.0 || flags.dereference.0 || flags.layout == Layout::Grid) {
+                    name_strings.push(meta.symlink.render(colors, flags));
+                }
+                // The directories which are their own ancestors are not recursed into again.
+                if meta.cycle {
+                    name_strings
+                        .push(colors.colorize(String::from(" [recursion]"), &Elem::NonFile));
+                }
+                if flags.mount_points.0 && meta.name.mount_point {
+                    name_strings.push(colors.colorize(String::from(" [mount]"), &Elem::NonFile));
+                }
+                // The count of the entries follows the directories whose content is listed,
+                // without the implied `.` and `..`.
+                if let (true, Layout::Tree, Some(content)) =
+                    (flags.tree_counts.0, flags.layout, &meta.content)
+                {
+                    let listed = content.iter().filter(|meta| !meta.is_implied_dir());
+                    let count = format!(" ({})", listed.count());
+                    name_strings.push(colors.colorize(count, &Elem::TreeCount));
+                }
+
+                strings.push(ColoredString::from(ANSIStrings(&name_strings).to_string()));
             }
         };
     }
@@ -299,24 +823,54 @@ fn get_output<'a>(
     strings
 }
 
+/// The number of columns the input takes in a terminal. The ANSI escape sequences of the colors
+/// and of the hyperlinks take none, and the double-width characters, like CJK ones, take two.
 fn get_visible_width(input: &str) -> usize {
-    let mut nb_invisible_char = 0;
+    UnicodeWidthStr::width(strip_escape_sequences(input).as_str())
+}
 
-    // If the input has color, do not compute the length contributed by the color to the actual length
-    for (idx, _) in input.match_indices("\u{1b}[") {
-        let (_, s) = input.split_at(idx);
+/// The input without its ANSI escape sequences: the control sequences like the `ESC [ 1;34 m` of
+/// the colors, and the operating system commands like the `ESC ] 8 ;; url ESC \` of the
+/// hyperlinks.
+fn strip_escape_sequences(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
 
-        let m_pos = s.find('m');
-        if let Some(len) = m_pos {
-            nb_invisible_char += len
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            output.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            // A control sequence ends with a character between `@` and `~`.
+            Some('[') => {
+                for c in chars.by_ref() {
+                    if ('@'..='~').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            // An operating system command ends with a BEL or with the string terminator `ESC \`.
+            Some(']') => {
+                while let Some(c) = chars.next() {
+                    if c == '\u{7}' {
+                        break;
+                    } else if c == '\u{1b}' {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
         }
     }
 
-    UnicodeWidthStr::width(input) - nb_invisible_char
+    output
 }
 
 fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
-    let mut max_value_length: usize = 0;
+    let mut max_value_length: usize = flags.size_format.value_width();
 
     for meta in metas {
         let value_len = meta.size.value_string(flags).len();
@@ -332,20 +886,73 @@ fn detect_size_lengths(metas: &[Meta], flags: &Flags) -> usize {
 fn get_padding_rules(metas: &[Meta], flags: &Flags) -> HashMap<Block, usize> {
     let mut padding_rules: HashMap<Block, usize> = HashMap::new();
 
-    if flags.blocks.0.contains(&Block::Size) {
+    if flags.blocks.0.contains(&Block::Size) || flags.blocks.0.contains(&Block::Device) {
         let size_val = detect_size_lengths(&metas, &flags);
 
         padding_rules.insert(Block::SizeValue, size_val);
     }
 
+    if flags.blocks.0.contains(&Block::Device) {
+        let device_len = metas
+            .iter()
+            .map(|meta| meta.device.value_string().len())
+            .max()
+            .unwrap_or(0);
+
+        padding_rules.insert(Block::Device, device_len);
+    }
+
+    if flags.blocks.0.contains(&Block::INode) {
+        let inode_len = metas
+            .iter()
+            .map(|meta| meta.inode.value_string().len())
+            .max()
+            .unwrap_or(0);
+
+        padding_rules.insert(Block::INode, inode_len);
+    }
+
+    if flags.blocks.0.contains(&Block::Context) {
+        let context_len = metas
+            .iter()
+            .map(|meta| meta.access_control.value_string().len())
+            .max()
+            .unwrap_or(0);
+
+        padding_rules.insert(Block::Context, context_len);
+    }
+
+    if flags.blocks.0.contains(&Block::FileFlags) {
+        let file_flags_len = metas
+            .iter()
+            .map(|meta| meta.file_flags.value_string().len())
+            .max()
+            .unwrap_or(0);
+
+        padding_rules.insert(Block::FileFlags, file_flags_len);
+    }
+
+    if flags.blocks.0.contains(&Block::Links) {
+        let links_len = metas
+            .iter()
+            .map(|meta| meta.links.value_string().len())
+            .max()
+            .unwrap_or(0);
+
+        padding_rules.insert(Block::Links, links_len);
+    }
+
     padding_rules
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::app;
     use crate::color;
     use crate::color::Colors;
+    use crate::config_file::Config;
+    use crate::flags::{Across, TreeCounts, TreeIndent};
     use crate::icon;
     use crate::icon::Icons;
     use crate::meta::{FileType, Name};
@@ -374,6 +981,7 @@ mod tests {
                 &Colors::new(color::Theme::NoColor),
                 &Icons::new(icon::Theme::NoIcon),
                 &DisplayOption::FileName,
+                &Flags::default(),
             );
 
             assert_eq!(get_visible_width(&output), *l);
@@ -406,6 +1014,7 @@ mod tests {
                     &Colors::new(color::Theme::NoColor),
                     &Icons::new(icon::Theme::Fancy),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -438,6 +1047,7 @@ mod tests {
                     &Colors::new(color::Theme::NoLscolors),
                     &Icons::new(icon::Theme::NoIcon),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -474,6 +1084,7 @@ mod tests {
                     &Colors::new(color::Theme::NoColor),
                     &Icons::new(icon::Theme::NoIcon),
                     &DisplayOption::FileName,
+                    &Flags::default(),
                 )
                 .to_string();
 
@@ -484,4 +1095,448 @@ mod tests {
             assert_eq!(get_visible_width(&output), *l);
         }
     }
+
+    /// The entries `root`, `root/a.txt` and `root/sub`, whose path, owner, size and modification
+    /// time are set to known values.
+    #[cfg(unix)]
+    fn json_fixture() -> (tempfile::TempDir, Meta, Meta, Meta) {
+        use crate::meta::Owner;
+        use std::fs::{self, File};
+        use std::io::Write;
+        use std::os::unix::fs::PermissionsExt;
+        use std::path::PathBuf;
+        use std::process::Command;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("a.txt");
+        File::create(&file_path)
+            .expect("failed to create file")
+            .write_all(b"abc")
+            .expect("failed to write file");
+        let dir_path = tmp_dir.path().join("sub");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+
+        let meta = |path: &Path, mode: u32, json_path: &str, size: u64| {
+            fs::set_permissions(path, fs::Permissions::from_mode(mode))
+                .expect("failed to set permissions");
+            let success = Command::new("touch")
+                .env("TZ", "UTC")
+                .args(&["-m", "-t", "202001010000"])
+                .arg(path)
+                .status()
+                .unwrap()
+                .success();
+            assert!(success, "failed to touch file");
+
+            let mut meta = Meta::from_path(path, false).unwrap();
+            meta.path = PathBuf::from(json_path);
+            meta.name = Name::new(&meta.path, meta.file_type);
+            meta.owner = Owner::new("user".to_owned(), "group".to_owned());
+            meta.size = Size::new(size);
+            meta
+        };
+
+        let file = meta(&file_path, 0o644, "root/a.txt", 3);
+        let dir = meta(&dir_path, 0o755, "root/sub", 4096);
+        let root = meta(tmp_dir.path(), 0o755, "root", 4096);
+        (tmp_dir, root, file, dir)
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_display_json_flattens_directory_contents() {
+        let (_tmp_dir, mut root, file, dir) = json_fixture();
+        root.content = Some(vec![file, dir]);
+
+        let output = json(&[root], &Flags::default());
+
+        assert_eq!(
+            concat!(
+                r#"[{"name":"a.txt","path":"root/a.txt","file_type":"file","size":3,"#,
+                r#""mtime":1577836800,"permissions":"0644","user":"user","group":"group","#,
+                r#""symlink_target":null},"#,
+                r#"{"name":"sub","path":"root/sub","file_type":"directory","size":4096,"#,
+                r#""mtime":1577836800,"permissions":"0755","user":"user","group":"group","#,
+                r#""symlink_target":null}]"#,
+                "\n"
+            ),
+            output
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_display_json_tree_nests_contents() {
+        let (_tmp_dir, mut root, file, mut dir) = json_fixture();
+        dir.content = Some(vec![]);
+        root.content = Some(vec![file, dir]);
+
+        let flags = Flags {
+            layout: Layout::Tree,
+            ..Default::default()
+        };
+        let output = json(&[root], &flags);
+
+        assert_eq!(
+            concat!(
+                r#"[{"name":"root","path":"root","file_type":"directory","size":4096,"#,
+                r#""mtime":1577836800,"permissions":"0755","user":"user","group":"group","#,
+                r#""symlink_target":null,"contents":["#,
+                r#"{"name":"a.txt","path":"root/a.txt","file_type":"file","size":3,"#,
+                r#""mtime":1577836800,"permissions":"0644","user":"user","group":"group","#,
+                r#""symlink_target":null},"#,
+                r#"{"name":"sub","path":"root/sub","file_type":"directory","size":4096,"#,
+                r#""mtime":1577836800,"permissions":"0755","user":"user","group":"group","#,
+                r#""symlink_target":null,"contents":[]}]}]"#,
+                "\n"
+            ),
+            output
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_display_zero_ends_the_paths_with_nul_characters() {
+        let (_tmp_dir, root, file, dir) = json_fixture();
+        let mut root = root;
+        let mut dir = dir;
+        dir.content = Some(vec![file.clone()]);
+        root.content = Some(vec![file.clone(), dir.clone()]);
+
+        let expected = format!(
+            "{}\0{}\0{}\0",
+            file.path.display(),
+            dir.path.display(),
+            file.path.display()
+        );
+        assert_eq!(expected.into_bytes(), zero(&[root]));
+    }
+
+    #[test]
+    fn test_display_get_visible_width_with_hyperlinks() {
+        let flags = Flags {
+            hyperlink: crate::flags::HyperlinkOption::Always,
+            ..Default::default()
+        };
+
+        for (s, l) in &[("ASCII1234-_", 11), ("制作样本。", 10), ("🔬", 2)] {
+            let path = Path::new(s);
+            let name = Name::new(
+                path,
+                FileType::File {
+                    exec: false,
+                    uid: false,
+                },
+            );
+            let output = name
+                .render(
+                    &Colors::new(color::Theme::NoLscolors),
+                    &Icons::new(icon::Theme::NoIcon),
+                    &DisplayOption::FileName,
+                    &flags,
+                )
+                .to_string();
+
+            assert!(output.starts_with("\u{1b}]8;;file://"));
+            assert_eq!(get_visible_width(&output), *l);
+        }
+    }
+
+    #[test]
+    fn test_display_get_visible_width_of_escape_sequences() {
+        for (s, l) in &[
+            ("\u{1b}[38;5;184m日本語.txt\u{1b}[0m", 10),
+            ("\u{1b}[1;34mcrab🦀.rs\u{1b}[0m", 9),
+            // Sequences which do not end with an `m`, and a command ended with a BEL.
+            ("\u{1b}[Kmore\u{1b}[2J", 4),
+            ("\u{1b}]8;;file:///tmp/émoji\u{7}é🔬\u{1b}]8;;\u{7}", 3),
+        ] {
+            assert_eq!(get_visible_width(s), *l);
+        }
+    }
+
+    #[test]
+    fn test_display_strip_escape_sequences() {
+        assert_eq!(
+            "日本語.txt",
+            strip_escape_sequences("\u{1b}]8;;file:///日本語.txt\u{1b}\\\u{1b}[38;5;184m日本語.txt\u{1b}[0m\u{1b}]8;;\u{1b}\\")
+        );
+        assert_eq!("plain", strip_escape_sequences("plain"));
+    }
+
+    /// The grid of the files `a`, `b`, `c` and `d`, narrow enough to have two columns.
+    fn two_column_grid(flags: &Flags) -> String {
+        use std::fs::File;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let metas: Vec<Meta> = ["a", "b", "c", "d"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false).unwrap()
+            })
+            .collect();
+
+        inner_display_grid(
+            &DisplayOption::FileName,
+            &metas,
+            flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon),
+            0,
+            Some(5),
+        )
+    }
+
+    #[test]
+    fn test_display_grid_fills_columns_down_by_default() {
+        assert_eq!("a  c\nb  d\n", two_column_grid(&Flags::default()));
+    }
+
+    #[test]
+    fn test_display_grid_fills_rows_across() {
+        let flags = Flags {
+            across: Across(true),
+            ..Flags::default()
+        };
+        assert_eq!("a  b\nc  d\n", two_column_grid(&flags));
+    }
+
+    #[test]
+    fn test_display_columns_width() {
+        assert_eq!(Some(120), columns_width(Some("120".to_string())));
+        assert_eq!(None, columns_width(None));
+        assert_eq!(None, columns_width(Some("0".to_string())));
+        assert_eq!(None, columns_width(Some("-80".to_string())));
+        assert_eq!(None, columns_width(Some("wide".to_string())));
+    }
+
+    #[cfg(unix)]
+    fn name_first_flags(layout: &str) -> Flags {
+        let argv = vec![
+            "lsd",
+            layout,
+            "--blocks",
+            "name,permission,size",
+            "--size",
+            "bytes",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        Flags::configure_from(&matches, &Config::with_none()).unwrap()
+    }
+
+    /// Create the file at `path` with `size` bytes and the 644 permissions.
+    #[cfg(unix)]
+    fn create_file(path: &Path, size: usize) {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        fs::write(path, "x".repeat(size)).expect("failed to write file");
+        fs::set_permissions(path, fs::Permissions::from_mode(0o644))
+            .expect("failed to set permissions");
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_display_oneline_name_first() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let metas: Vec<Meta> = [("a_long_name", 3), ("b", 10)]
+            .iter()
+            .map(|(name, size)| {
+                let path = tmp_dir.path().join(name);
+                create_file(&path, *size);
+                Meta::from_path(&path, false).unwrap()
+            })
+            .collect();
+
+        let output = inner_display_grid(
+            &DisplayOption::FileName,
+            &metas,
+            &name_first_flags("--oneline"),
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon),
+            0,
+            None,
+        );
+
+        assert_eq!(
+            "a_long_name .rw-r--r--  3 \nb           .rw-r--r-- 10 \n",
+            output
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_display_tree_name_first_aligns_the_trailing_blocks() {
+        use std::fs;
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = |name: &str| tmp_dir.path().join(name);
+        create_file(&path("a"), 3);
+        fs::create_dir(path("sub")).expect("failed to create dir");
+        fs::set_permissions(path("sub"), fs::Permissions::from_mode(0o755))
+            .expect("failed to set permissions");
+        create_file(&path("sub/long_name"), 10);
+
+        let mut sub = Meta::from_path(&path("sub"), false).unwrap();
+        sub.content = Some(vec![Meta::from_path(&path("sub/long_name"), false).unwrap()]);
+        let dir_size = sub.size.get_bytes().to_string();
+        let mut meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        meta.name = Name::new(Path::new("root"), meta.file_type);
+        let root_size = meta.size.get_bytes().to_string();
+        meta.content = Some(vec![Meta::from_path(&path("a"), false).unwrap(), sub]);
+
+        let flags = Flags {
+            layout: Layout::Tree,
+            ..name_first_flags("--long")
+        };
+        let output = tree(
+            &[meta],
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon),
+        );
+
+        // The names are padded to the widest one, "long_name" at depth 2, and the sizes to the
+        // widest of the whole tree. The sizes in bytes are followed by their empty unit.
+        let width = root_size.len().max(dir_size.len());
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(4, lines.len());
+        assert!(lines[0].starts_with(&format!("{:<16} d", "root")));
+        assert_eq!(
+            format!("├── {:<12} .rw-r--r-- {:>w$} ", "a", 3, w = width),
+            lines[1]
+        );
+        assert_eq!(
+            format!("└── {:<12} drwxr-xr-x {:>w$} ", "sub", dir_size, w = width),
+            lines[2]
+        );
+        assert_eq!(
+            format!("   └── long_name .rw-r--r-- {:>w$} ", 10, w = width),
+            lines[3]
+        );
+    }
+
+    #[test]
+    fn test_display_tree_counts_are_colored_apart_from_the_names() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = |name: &str| tmp_dir.path().join(name);
+        std::fs::create_dir(path("sub")).expect("failed to create dir");
+        create_file(&path("sub/a"), 1);
+        create_file(&path("sub/b"), 1);
+
+        let mut meta = Meta::from_path(&path("sub"), false).unwrap();
+        meta.content = Some(vec![
+            Meta::from_path(&path("sub/a"), false).unwrap(),
+            Meta::from_path(&path("sub/b"), false).unwrap(),
+        ]);
+
+        let flags = Flags {
+            layout: Layout::Tree,
+            tree_counts: TreeCounts(true),
+            ..Flags::default()
+        };
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let output = tree(
+            &[meta.clone()],
+            &flags,
+            &colors,
+            &Icons::new(icon::Theme::NoIcon),
+        );
+
+        let name = colors.colorize(String::from("sub"), &Elem::Dir { uid: false });
+        let count = colors.colorize(String::from(" (2)"), &Elem::TreeCount);
+        assert_ne!(name.style_ref(), count.style_ref());
+        assert!(output.lines().next().unwrap().ends_with(&count.to_string()));
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let output = tree(&[meta], &flags, &colors, &Icons::new(icon::Theme::NoIcon));
+        assert_eq!("sub (2)", output.lines().next().unwrap());
+    }
+
+    #[test]
+    fn test_display_tree_counts_leave_out_the_implied_dirs() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = |name: &str| tmp_dir.path().join(name);
+        std::fs::create_dir(path("empty")).expect("failed to create dir");
+
+        let mut meta = Meta::from_path(&path("empty"), false).unwrap();
+        let mut current = meta.clone();
+        current.name.name = ".".to_owned();
+        let mut parent = Meta::from_path(&path("empty/.."), false).unwrap();
+        parent.name.name = "..".to_owned();
+        meta.content = Some(vec![current, parent]);
+
+        let flags = Flags {
+            layout: Layout::Tree,
+            tree_counts: TreeCounts(true),
+            ..Flags::default()
+        };
+        let output = tree(
+            &[meta],
+            &flags,
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon),
+        );
+
+        assert_eq!("empty (0)", output.lines().next().unwrap());
+    }
+
+    #[test]
+    fn test_display_tree_indent() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let path = |name: &str| tmp_dir.path().join(name);
+        std::fs::create_dir(path("sub")).expect("failed to create dir");
+        create_file(&path("sub/inner"), 1);
+        create_file(&path("top"), 1);
+
+        let render = |indent| {
+            let mut sub = Meta::from_path(&path("sub"), false).unwrap();
+            sub.content = Some(vec![Meta::from_path(&path("sub/inner"), false).unwrap()]);
+            let mut meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+            meta.name = Name::new(Path::new("root"), meta.file_type);
+            meta.content = Some(vec![sub, Meta::from_path(&path("top"), false).unwrap()]);
+
+            let flags = Flags {
+                layout: Layout::Tree,
+                tree_indent: TreeIndent(indent),
+                ..Flags::default()
+            };
+            tree(
+                &[meta],
+                &flags,
+                &Colors::new(color::Theme::NoColor),
+                &Icons::new(icon::Theme::NoIcon),
+            )
+        };
+
+        assert_eq!("root\n├─ sub\n│ └─ inner\n└─ top\n", render(2));
+        assert_eq!("root\n├─── sub\n│   └─── inner\n└─── top\n", render(4));
+        assert_eq!(
+            "root\n├── sub\n│  └── inner\n└── top\n",
+            render(TreeIndent::default().0)
+        );
+    }
+
+    #[test]
+    fn test_display_dired_replaces_the_markers_by_offsets() {
+        let marked = format!(
+            "{p}dir{e}:\n-rw {n}a{e}\n\n{n}bb{e} => c\n",
+            p = DIRED_PATH_START,
+            n = DIRED_NAME_START,
+            e = DIRED_END
+        );
+
+        assert_eq!(
+            "  dir:\n  -rw a\n\n  bb => c\n//DIRED// 13 14 18 20\n//SUBDIRED// 2 5\n",
+            dired(&marked)
+        );
+    }
+
+    #[test]
+    fn test_display_dired_without_listed_directories() {
+        assert_eq!("//DIRED//\n", dired(""));
+    }
 }
@@ -1,45 +1,120 @@
+pub mod across;
+pub mod block_size;
+pub mod block_usage;
 pub mod blocks;
 pub mod color;
 pub mod date;
+pub mod date_color;
+pub mod date_filter;
 pub mod dereference;
+pub mod dereference_command_line;
+pub mod dim_ignored;
+pub mod dir_size;
+pub mod dired;
 pub mod display;
+pub mod extended;
+pub mod file_types;
+pub mod git_ignore;
+pub mod header;
+pub mod hide;
+pub mod hyperlink;
 pub mod icons;
 pub mod ignore_globs;
 pub mod indicators;
+pub mod json;
 pub mod layout;
+pub mod mount_points;
+pub mod numeric_uid_gid;
+pub mod padding;
+pub mod permission;
+pub mod progress;
+pub mod quoting_style;
 pub mod recursion;
+pub mod resolve_symlinks;
 pub mod size;
+pub mod size_color;
+pub mod size_filter;
+pub mod size_format;
 pub mod sorting;
+pub mod stdin;
+pub mod summary;
 pub mod symlink_arrow;
+pub mod symlink_target;
 pub mod symlinks;
 pub mod total_size;
+pub mod tree_counts;
+pub mod tree_indent;
+pub mod truncate_names;
+pub mod width;
+pub mod writable_color;
+pub mod zero;
 
+pub use across::Across;
+pub use block_size::BlockSize;
+pub use block_usage::BlockUsage;
 pub use blocks::Block;
 pub use blocks::Blocks;
 pub use color::Color;
 pub use color::ColorOption;
+pub use color::ColorScheme;
 pub use date::DateFlag;
-pub use dereference::Dereference;
+pub use date::TimeKind;
+pub use date_color::DateColor;
+pub use date_filter::DateFilter;
+pub use dereference::{Dereference, DereferenceColumn, DereferenceColumns};
+pub use dereference_command_line::DereferenceCommandLine;
+pub use dim_ignored::DimIgnored;
+pub use dir_size::DirSize;
+pub use dired::Dired;
 pub use display::Display;
+pub use extended::Extended;
+pub use file_types::FileTypes;
+pub use git_ignore::GitIgnore;
+pub use header::Header;
+pub use hide::Hide;
+pub use hyperlink::HyperlinkOption;
 pub use icons::IconOption;
 pub use icons::IconTheme;
 pub use icons::Icons;
 pub use ignore_globs::IgnoreGlobs;
+pub use indicators::IndicatorChars;
+pub use indicators::IndicatorKind;
 pub use indicators::Indicators;
+pub use json::Json;
 pub use layout::Layout;
+pub use mount_points::MountPoints;
+pub use numeric_uid_gid::NumericUidGid;
+pub use padding::Padding;
+pub use permission::PermissionFlag;
+pub use progress::Progress;
+pub use quoting_style::QuotingStyle;
 pub use recursion::Recursion;
+pub use resolve_symlinks::ResolveSymlinks;
 pub use size::SizeFlag;
+pub use size_color::SizeColor;
+pub use size_filter::SizeFilter;
+pub use size_format::SizeFormat;
 pub use sorting::DirGrouping;
 pub use sorting::SortColumn;
 pub use sorting::SortOrder;
 pub use sorting::Sorting;
+pub use stdin::Stdin;
+pub use summary::Summary;
 pub use symlink_arrow::SymlinkArrow;
+pub use symlink_target::SymlinkTarget;
 pub use symlinks::NoSymlink;
 pub use total_size::TotalSize;
+pub use tree_counts::TreeCounts;
+pub use tree_indent::TreeIndent;
+pub use truncate_names::TruncateNames;
+pub use width::Width;
+pub use writable_color::WritableColor;
+pub use zero::Zero;
 
 use crate::config_file::Config;
 
 use clap::{ArgMatches, Error};
+use std::path::PathBuf;
 
 #[cfg(doc)]
 use yaml_rust::Yaml;
@@ -47,21 +122,62 @@ use yaml_rust::Yaml;
 /// A struct to hold all set configuration flags for the application.
 #[derive(Clone, Debug, Default)]
 pub struct Flags {
+    pub across: Across,
+    pub block_size: BlockSize,
+    pub block_usage: BlockUsage,
     pub blocks: Blocks,
     pub color: Color,
     pub date: DateFlag,
+    pub date_color: DateColor,
+    pub date_filter: DateFilter,
     pub dereference: Dereference,
+    pub dereference_columns: DereferenceColumns,
+    pub dereference_command_line: DereferenceCommandLine,
+    pub dir_size: DirSize,
     pub display: Display,
     pub display_indicators: Indicators,
+    pub indicator_chars: IndicatorChars,
+    pub extended: Extended,
+    pub file_types: FileTypes,
+    pub git_ignore: GitIgnore,
+    pub dim_ignored: DimIgnored,
+    pub header: Header,
+    pub hide: Hide,
+    pub hyperlink: HyperlinkOption,
+    /// The working directory the relative paths of the hyperlinks are resolved against. It is
+    /// only looked up once, when the hyperlinks are printed.
+    pub current_dir: Option<PathBuf>,
     pub icons: Icons,
     pub ignore_globs: IgnoreGlobs,
+    pub json: Json,
+    pub dired: Dired,
     pub layout: Layout,
+    pub mount_points: MountPoints,
+    pub numeric_uid_gid: NumericUidGid,
+    pub padding: Padding,
+    pub permission: PermissionFlag,
+    pub progress: Progress,
     pub no_symlink: NoSymlink,
+    pub quoting_style: QuotingStyle,
     pub recursion: Recursion,
+    pub resolve_symlinks: ResolveSymlinks,
     pub size: SizeFlag,
+    pub size_color: SizeColor,
+    pub size_filter: SizeFilter,
+    pub size_format: SizeFormat,
     pub sorting: Sorting,
+    pub stdin: Stdin,
+    pub summary: Summary,
     pub total_size: TotalSize,
+    pub tree_counts: TreeCounts,
+    pub tree_indent: TreeIndent,
+    pub truncate_names: TruncateNames,
     pub symlink_arrow: SymlinkArrow,
+    pub symlink_target: SymlinkTarget,
+    pub time_kind: TimeKind,
+    pub width: Width,
+    pub writable_color: WritableColor,
+    pub zero: Zero,
 }
 
 impl Flags {
@@ -69,25 +185,80 @@ impl Flags {
     ///
     /// # Errors
     ///
-    /// This can return an [Error], when either the building of the ignore globs or the parsing of
-    /// the recursion depth parameter fails.
+    /// This can return an [Error], when either the building of the ignore or hide globs or the
+    /// parsing of the recursion depth parameter fails.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
+        let stdin = Stdin::configure_from(matches, config);
+        let mut display = Display::configure_from(matches, config);
+        let mut sorting = Sorting::configure_from(matches, config);
+
+        // The paths read from the standard input are listed themselves, in the order they are
+        // given unless a sort is requested on the command line.
+        if stdin != Stdin::Disabled {
+            display = Display::DirectoryItself;
+            if SortColumn::from_arg_matches(matches).is_none() {
+                sorting.column = SortColumn::None;
+            }
+            if DirGrouping::from_arg_matches(matches).is_none() {
+                sorting.dir_grouping = DirGrouping::None;
+            }
+        }
+
         Ok(Self {
+            across: Across::configure_from(matches, config),
+            block_size: BlockSize::configure_from(matches, config),
+            block_usage: BlockUsage::configure_from(matches, config),
             blocks: Blocks::configure_from(matches, config)?,
             color: Color::configure_from(matches, config),
             date: DateFlag::configure_from(matches, config),
+            date_color: DateColor::configure_from(matches, config),
+            date_filter: DateFilter::configure_from(matches, config),
             dereference: Dereference::configure_from(matches, config),
-            display: Display::configure_from(matches, config),
+            dereference_columns: DereferenceColumns::configure_from(matches, config),
+            dereference_command_line: DereferenceCommandLine::configure_from(matches, config),
+            dir_size: DirSize::configure_from(matches, config),
+            display,
             layout: Layout::configure_from(matches, config),
+            mount_points: MountPoints::configure_from(matches, config),
+            numeric_uid_gid: NumericUidGid::configure_from(matches, config),
+            padding: Padding::configure_from(matches, config),
+            permission: PermissionFlag::configure_from(matches, config),
+            progress: Progress::configure_from(matches, config),
             size: SizeFlag::configure_from(matches, config),
+            size_color: SizeColor::configure_from(matches, config),
+            size_filter: SizeFilter::configure_from(matches, config),
+            size_format: SizeFormat::configure_from(matches, config),
             display_indicators: Indicators::configure_from(matches, config),
+            indicator_chars: IndicatorChars::configure_from(matches, config),
+            extended: Extended::configure_from(matches, config),
+            file_types: FileTypes::configure_from(matches, config),
+            git_ignore: GitIgnore::configure_from(matches, config),
+            dim_ignored: DimIgnored::configure_from(matches, config),
+            header: Header::configure_from(matches, config),
+            hide: Hide::configure_from(matches)?,
+            hyperlink: HyperlinkOption::configure_from(matches, config),
+            current_dir: None,
             icons: Icons::configure_from(matches, config),
             ignore_globs: IgnoreGlobs::configure_from(matches, config)?,
+            json: Json::configure_from(matches, config),
+            dired: Dired::configure_from(matches, config),
             no_symlink: NoSymlink::configure_from(matches, config),
+            quoting_style: QuotingStyle::configure_from(matches, config),
             recursion: Recursion::configure_from(matches, config)?,
-            sorting: Sorting::configure_from(matches, config),
+            resolve_symlinks: ResolveSymlinks::configure_from(matches, config),
+            sorting,
+            stdin,
+            summary: Summary::configure_from(matches, config),
             total_size: TotalSize::configure_from(matches, config),
+            tree_counts: TreeCounts::configure_from(matches, config),
+            tree_indent: TreeIndent::configure_from(matches, config),
+            truncate_names: TruncateNames::configure_from(matches, config),
             symlink_arrow: SymlinkArrow::configure_from(matches, config),
+            symlink_target: SymlinkTarget::configure_from(matches, config),
+            time_kind: TimeKind::configure_from(matches, config),
+            width: Width::configure_from(matches, config),
+            writable_color: WritableColor::configure_from(matches, config),
+            zero: Zero::configure_from(matches, config),
         })
     }
 }
@@ -98,10 +269,12 @@ pub trait Configurable<T>
 where
     T: std::default::Default,
 {
-    /// Returns a value from either [ArgMatches], a [Config] or a [Default] value. The first value
-    /// that is not [None] is used. The order of precedence for the value used is:
+    /// Returns a value from either [ArgMatches], a [Config], the environment or a [Default]
+    /// value. The first value that is not [None] is used. The order of precedence for the value
+    /// used is:
     /// - [from_arg_matches](Configurable::from_arg_matches)
     /// - [from_config](Configurable::from_config)
+    /// - [from_environment](Configurable::from_environment)
     /// - [Default::default]
     ///
     /// # Note
@@ -111,6 +284,10 @@ where
     fn configure_from(matches: &ArgMatches, config: &Config) -> T {
         let mut result: T = Default::default();
 
+        if let Some(value) = Self::from_environment() {
+            result = value;
+        }
+
         if let Some(value) = Self::from_config(config) {
             result = value;
         }
@@ -128,4 +305,94 @@ where
     /// The method to implement the value fetching from a configuration file. This should return
     /// [None], if the [Config] does not have a [Yaml].
     fn from_config(config: &Config) -> Option<T>;
+
+    /// The method to implement the value fetching from environment variables. By default the
+    /// environment is not used and this returns [None].
+    fn from_environment() -> Option<T> {
+        None
+    }
+}
+
+/// Whether the "classic" argument overrides the argument named `name`. This is the case when
+/// "classic" is passed and `name` is not passed after it, so that "classic" only sets the
+/// defaults of the plain `ls` output, and the arguments passed after it override them.
+fn classic_overrides(matches: &ArgMatches, name: &str) -> bool {
+    match matches.index_of("classic") {
+        Some(classic_index) => {
+            // The indices of the default value of an argument which is not passed are not
+            // meaningful, so only the passed occurrences are compared.
+            matches.occurrences_of(name) == 0
+                || matches
+                    .indices_of(name)
+                    .and_then(|indices| indices.max())
+                    .unwrap_or_default()
+                    < classic_index
+        }
+        None => false,
+    }
+}
+
+/// The index of the last occurrence of the argument called `name`, when it is passed.
+fn last_index(matches: &ArgMatches, name: &str) -> Option<usize> {
+    if matches.occurrences_of(name) == 0 {
+        return None;
+    }
+    matches.indices_of(name)?.max()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{classic_overrides, Display, Flags, SortColumn};
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    #[test]
+    fn test_classic_overrides_without_classic() {
+        let argv = vec!["lsd", "--color", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(!classic_overrides(&matches, "color"));
+    }
+
+    #[test]
+    fn test_classic_overrides_missing_argument() {
+        let argv = vec!["lsd", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(classic_overrides(&matches, "color"));
+    }
+
+    #[test]
+    fn test_classic_overrides_previous_argument() {
+        let argv = vec!["lsd", "--color", "always", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(classic_overrides(&matches, "color"));
+    }
+
+    #[test]
+    fn test_classic_is_overridden_by_later_argument() {
+        let argv = vec!["lsd", "--classic", "--color", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(!classic_overrides(&matches, "color"));
+
+        let argv = vec!["lsd", "--color", "never", "--classic", "--color=always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(!classic_overrides(&matches, "color"));
+    }
+
+    #[test]
+    fn test_stdin_lists_the_paths_themselves_in_order() {
+        let argv = vec!["lsd", "--stdin", "--recursive"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        assert_eq!(Display::DirectoryItself, flags.display);
+        assert_eq!(SortColumn::None, flags.sorting.column);
+    }
+
+    #[test]
+    fn test_stdin_keeps_a_requested_sort() {
+        let argv = vec!["lsd", "--stdin", "--timesort"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        assert_eq!(SortColumn::Time, flags.sorting.column);
+    }
 }
@@ -0,0 +1,154 @@
+//! This module defines the [BlockSize] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app;
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing the block size, in bytes, used to count the size of the files. When it is
+/// set, the size is shown as a plain number of blocks instead of a human readable size.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct BlockSize(pub Option<u64>);
+
+impl Configurable<Self> for BlockSize {
+    /// Get a potential `BlockSize` from [ArgMatches].
+    ///
+    /// If the "block-size" argument is passed, its parsed value is returned in a [Some].
+    /// Otherwise if the "kibibytes" argument is passed, a block size of 1024 bytes is returned in
+    /// a [Some]. If neither of them is passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if let Some(value) = matches.value_of("block-size") {
+            match app::parse_block_size(value) {
+                Some(size) => Some(Self(Some(size))),
+                None => panic!("This should not be reachable!"),
+            }
+        } else if matches.is_present("kibibytes") {
+            Some(Self(Some(1024)))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `BlockSize` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a valid [String](Yaml::String) or a positive
+    /// [Integer](Yaml::Integer) value pointed to by "block-size", this returns the block size in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["block-size"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match app::parse_block_size(value) {
+                    Some(size) => Some(Self(Some(size))),
+                    None => {
+                        config.print_invalid_value_warning("block-size", value);
+                        None
+                    }
+                },
+                Yaml::Integer(value) if *value > 0 => Some(Self(Some(*value as u64))),
+                Yaml::Integer(value) => {
+                    config.print_invalid_value_warning("block-size", &value.to_string());
+                    None
+                }
+                _ => {
+                    config.print_wrong_type_warning("block-size", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BlockSize;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_parse_block_size() {
+        assert_eq!(Some(512), app::parse_block_size("512"));
+        assert_eq!(Some(1024), app::parse_block_size("1K"));
+        assert_eq!(Some(4 * 1024 * 1024), app::parse_block_size("4M"));
+        assert_eq!(Some(1000), app::parse_block_size("1KB"));
+        assert_eq!(None, app::parse_block_size("0"));
+        assert_eq!(None, app::parse_block_size("K"));
+        assert_eq!(None, app::parse_block_size("1X"));
+        assert_eq!(None, app::parse_block_size("-1"));
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, BlockSize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_block_size() {
+        let argv = vec!["lsd", "--block-size", "1M"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(BlockSize(Some(1024 * 1024))),
+            BlockSize::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_kibibytes() {
+        let argv = vec!["lsd", "-k"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(BlockSize(Some(1024))),
+            BlockSize::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--block-size", "1X"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, BlockSize::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_string() {
+        let yaml_string = "block-size: 1K";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(BlockSize(Some(1024))),
+            BlockSize::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_integer() {
+        let yaml_string = "block-size: 512";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(BlockSize(Some(512))),
+            BlockSize::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "block-size: 0";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, BlockSize::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,128 @@
+//! This module defines the [BlockUsage] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing whether to display the size allocated on the disk for the files, like `du`,
+/// instead of their apparent size. Both differ for the sparse files and the small files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct BlockUsage(pub bool);
+
+impl Configurable<Self> for BlockUsage {
+    /// Get a potential `BlockUsage` value from [ArgMatches].
+    ///
+    /// If the "block-usage" argument is passed, this returns a `BlockUsage` with value `true` in a
+    /// [Some]. If the "apparent-size" argument is passed instead, this returns a `BlockUsage` with
+    /// value `false` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("block-usage") {
+            Some(Self(true))
+        } else if matches.is_present("apparent-size") {
+            Some(Self(false))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `BlockUsage` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains the [Boolean](Yaml::Boolean) value pointed to by
+    /// "block-usage", this returns its value as the value of the `BlockUsage`, in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["block-usage"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("block-usage", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::BlockUsage;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, BlockUsage::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--block-usage"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(BlockUsage(true)),
+            BlockUsage::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_last_one_wins() {
+        let argv = vec!["lsd", "--block-usage", "--apparent-size"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(BlockUsage(false)),
+            BlockUsage::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--apparent-size", "--block-usage"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(BlockUsage(true)),
+            BlockUsage::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, BlockUsage::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, BlockUsage::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let yaml_string = "block-usage: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(BlockUsage(true)),
+            BlockUsage::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let yaml_string = "block-usage: false";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(BlockUsage(false)),
+            BlockUsage::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
@@ -14,15 +14,24 @@ pub struct Blocks(pub Vec<Block>);
 
 impl Blocks {
     /// Returns a value from either [ArgMatches], a [Config] or a default value.
-    /// Unless the "long" argument is passed, this returns [Default::default]. Otherwise the first
-    /// value, that is not [None], is used. The order of precedence for the value used is:
+    /// Unless the "long" or the "full-time" argument is passed, this returns [Default::default].
+    /// Otherwise the first value, that is not [None], is used. The order of precedence for the
+    /// value used is:
     /// - [from_arg_matches](Blocks::from_arg_matches)
     /// - [from_config](Blocks::from_config)
     /// - [long](Blocks::long)
     ///
+    /// If the "oneline" argument is passed without the "long" one, the blocks passed with the
+    /// "blocks" argument are used, and only the [Name](Block::Name) block otherwise. The blocks of
+    /// the configuration file are meant for the long layout and are not used.
+    ///
     /// No matter if the "long" argument was passed, if the "inode" argument is passed and the
     /// `Blocks` does not contain a [Block] of variant [INode](Block::INode) yet, one is prepended
-    /// to the returned value.
+    /// to the returned value. Likewise if the "git" argument is passed, a [Block] of variant
+    /// [GitStatus](Block::GitStatus) is inserted before the [Name](Block::Name) block, and if the
+    /// "extended" argument is passed, a [Block] of variant [XAttr](Block::XAttr) is inserted after
+    /// the [Permission](Block::Permission) block. If the "context" argument is passed, a [Block] of
+    /// variant [Context](Block::Context) is inserted before the [Name](Block::Name) block.
     ///
     /// # Note
     ///
@@ -34,19 +43,24 @@ impl Blocks {
     /// This errors if any of the [ArgMatches] parameter arguments causes [Block]'s implementation
     /// of [TryFrom::try_from] to return an [Err].
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
-        let mut result: Result<Self, Error> = if matches.is_present("long") {
+        let long = matches.is_present("long") || matches.is_present("full-time");
+        let mut result: Result<Self, Error> = if long {
             Ok(Self::long())
         } else {
             Ok(Default::default())
         };
 
-        if matches.is_present("long") {
+        if long {
             if config.has_yaml() {
                 if let Some(value) = Self::from_config(config) {
                     result = Ok(value);
                 }
             }
 
+            if let Some(value) = Self::from_arg_matches(matches) {
+                result = value;
+            }
+        } else if matches.is_present("oneline") && matches.occurrences_of("blocks") > 0 {
             if let Some(value) = Self::from_arg_matches(matches) {
                 result = value;
             }
@@ -58,6 +72,24 @@ impl Blocks {
             }
         }
 
+        if matches.is_present("git") {
+            if let Ok(blocks) = result.as_mut() {
+                blocks.optional_insert_git_status();
+            }
+        }
+
+        if matches.is_present("extended") {
+            if let Ok(blocks) = result.as_mut() {
+                blocks.optional_insert_xattr();
+            }
+        }
+
+        if matches.is_present("context") {
+            if let Ok(blocks) = result.as_mut() {
+                blocks.optional_insert_context();
+            }
+        }
+
         result
     }
 
@@ -98,15 +130,24 @@ impl Blocks {
     /// Get a potential `Blocks` struct from a [Config].
     ///
     /// If the Config's [Yaml] contains an [Array](Yaml::Array) value pointed to by "blocks", each
-    /// of its [String](Yaml::String) values is returned in a `Blocks` in a [Some]. Otherwise it
+    /// of its [String](Yaml::String) values is returned in a `Blocks` in a [Some]. A
+    /// [String](Yaml::String) value is split on its commas, like the "blocks" argument. The
+    /// values which are not block names are skipped with a warning, and when none is left this
     /// returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(yaml) = &config.yaml {
             match &yaml["blocks"] {
                 Yaml::BadValue => None,
                 Yaml::Array(values) => Self::from_yaml_array(values, config),
+                Yaml::String(value) => Self::from_names(
+                    value
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|name| !name.is_empty()),
+                    config,
+                ),
                 _ => {
-                    config.print_wrong_type_warning("blocks", "array");
+                    config.print_wrong_type_warning("blocks", "array or a comma separated string");
                     None
                 }
             }
@@ -118,14 +159,23 @@ impl Blocks {
     /// Get a [Blocks] from a [Yaml] array. The [Config] is used to log warnings about wrong values
     /// in a Yaml.
     fn from_yaml_array(values: &[Yaml], config: &Config) -> Option<Self> {
+        let names = values.iter().filter_map(|value| match value {
+            Yaml::String(value) => Some(value.as_str()),
+            _ => {
+                config.print_warning("The blocks config values have to be strings.");
+                None
+            }
+        });
+        Self::from_names(names, config)
+    }
+
+    /// Get a [Blocks] from block names, warning about the unknown ones with the [Config].
+    fn from_names<'a>(names: impl Iterator<Item = &'a str>, config: &Config) -> Option<Self> {
         let mut blocks: Vec<Block> = vec![];
-        for array_el in values.iter() {
-            match array_el {
-                Yaml::String(value) => match Block::try_from(value.as_str()) {
-                    Ok(block) => blocks.push(block),
-                    Err(err) => config.print_warning(&err),
-                },
-                _ => config.print_warning("The blocks config values have to be strings."),
+        for name in names {
+            match Block::try_from(name) {
+                Ok(block) => blocks.push(block),
+                Err(err) => config.print_warning(&err),
             }
         }
         if blocks.is_empty() {
@@ -167,6 +217,47 @@ impl Blocks {
             self.prepend_inode()
         }
     }
+
+    /// Inserts a [Block] of variant [GitStatus](Block::GitStatus) right before the
+    /// [Name](Block::Name) block, or at the end if there is none, if `self` does not already
+    /// contain a Block of that variant.
+    fn optional_insert_git_status(&mut self) {
+        if !self.0.contains(&Block::GitStatus) {
+            match self.0.iter().position(|block| *block == Block::Name) {
+                Some(index) => self.0.insert(index, Block::GitStatus),
+                None => self.0.push(Block::GitStatus),
+            }
+        }
+    }
+
+    /// Inserts a [Block] of variant [XAttr](Block::XAttr) right after the
+    /// [Permission](Block::Permission) block, or before the [Name](Block::Name) block if there is
+    /// none, if `self` does not already contain a Block of that variant.
+    fn optional_insert_xattr(&mut self) {
+        if !self.0.contains(&Block::XAttr) {
+            let index = match self.0.iter().position(|block| *block == Block::Permission) {
+                Some(index) => index + 1,
+                None => self
+                    .0
+                    .iter()
+                    .position(|block| *block == Block::Name)
+                    .unwrap_or(self.0.len()),
+            };
+            self.0.insert(index, Block::XAttr);
+        }
+    }
+
+    /// Inserts a [Block] of variant [Context](Block::Context) right before the
+    /// [Name](Block::Name) block, or at the end if there is none, if `self` does not already
+    /// contain a Block of that variant.
+    fn optional_insert_context(&mut self) {
+        if !self.0.contains(&Block::Context) {
+            match self.0.iter().position(|block| *block == Block::Name) {
+                Some(index) => self.0.insert(index, Block::Context),
+                None => self.0.push(Block::Context),
+            }
+        }
+    }
 }
 
 /// The default value for `Blocks` contains a [Vec] of [Name](Block::Name).
@@ -184,9 +275,45 @@ pub enum Block {
     Group,
     Size,
     SizeValue,
+    /// The major and minor numbers of the device nodes, and the size of the other files.
+    Device,
     Date,
     Name,
     INode,
+    Links,
+    GitStatus,
+    XAttr,
+    /// The type of the file, like the first character of `ls -l`.
+    FileTypeChar,
+    /// The SELinux security context, which is only read on Linux.
+    Context,
+    /// The flags set by `chflags`, which are only read on the BSDs and macOS.
+    FileFlags,
+    /// The Windows attributes, which can only be displayed on Windows.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    WinAttributes,
+}
+
+impl Block {
+    /// The label of the block, shown in the header row.
+    pub fn get_header(&self) -> &'static str {
+        match self {
+            Self::Permission => "Permissions",
+            Self::User => "User",
+            Self::Group => "Group",
+            Self::Size | Self::SizeValue | Self::Device => "Size",
+            Self::Date => "Date Modified",
+            Self::Name => "Name",
+            Self::INode => "INode",
+            Self::Links => "Links",
+            Self::GitStatus => "Git",
+            Self::XAttr => "@",
+            Self::FileTypeChar => "T",
+            Self::Context => "Security Context",
+            Self::FileFlags => "Flags",
+            Self::WinAttributes => "Attr",
+        }
+    }
 }
 
 impl TryFrom<&str> for Block {
@@ -199,9 +326,22 @@ impl TryFrom<&str> for Block {
             "group" => Ok(Self::Group),
             "size" => Ok(Self::Size),
             "size_value" => Ok(Self::SizeValue),
+            "device" => Ok(Self::Device),
             "date" => Ok(Self::Date),
             "name" => Ok(Self::Name),
             "inode" => Ok(Self::INode),
+            "links" => Ok(Self::Links),
+            "git" => Ok(Self::GitStatus),
+            "xattr" => Ok(Self::XAttr),
+            "filetype" => Ok(Self::FileTypeChar),
+            "context" => Ok(Self::Context),
+            "flags" => Ok(Self::FileFlags),
+            #[cfg(windows)]
+            "attributes" => Ok(Self::WinAttributes),
+            #[cfg(not(windows))]
+            "attributes" => Err(String::from(
+                "The attributes block is only available on Windows",
+            )),
             _ => Err(format!("Not a valid block name: {}", &string)),
         }
     }
@@ -257,6 +397,17 @@ mod test_blocks {
         assert_eq_ok!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_with_full_time() {
+        let argv = vec!["lsd", "--full-time"];
+        let target = Ok::<_, Error>(Blocks::long());
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
     #[test]
     fn test_configure_from_with_blocks_and_without_long() {
         let argv = vec!["lsd", "--blocks", "permission"];
@@ -268,6 +419,30 @@ mod test_blocks {
         assert_eq_ok!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_with_oneline() {
+        let argv = vec!["lsd", "--oneline"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::Name]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml_string = "blocks:\n  - permission\n  - name";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let result = Blocks::configure_from(&matches, &Config::with_yaml(yaml));
+
+        assert_eq_ok!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_with_blocks_and_oneline() {
+        let argv = vec!["lsd", "-1", "--blocks", "size,name"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::Size, Block::Name]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
     #[test]
     fn test_configure_from_with_blocks_and_long() {
         let argv = vec!["lsd", "--long", "--blocks", "permission"];
@@ -293,6 +468,93 @@ mod test_blocks {
         assert_eq_ok!(result, target);
     }
 
+    #[test]
+    fn test_configure_from_with_git() {
+        let argv = vec!["lsd", "--git"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::GitStatus, Block::Name]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_insert_git_before_name_with_long() {
+        let argv = vec!["lsd", "--long", "--blocks", "name,size", "--git"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::GitStatus, Block::Name, Block::Size]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_ignore_insert_git_with_long() {
+        let argv = vec!["lsd", "--long", "--blocks", "git,size", "--git"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::GitStatus, Block::Size]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_insert_context_before_name() {
+        let argv = vec!["lsd", "--long", "--blocks", "size,name", "--git", "-Z"];
+        let target = Ok::<_, Error>(Blocks(vec![
+            Block::Size,
+            Block::GitStatus,
+            Block::Context,
+            Block::Name,
+        ]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+
+        let argv = vec!["lsd", "--context"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::Context, Block::Name]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_insert_xattr_after_permission() {
+        let argv = vec!["lsd", "--long", "-@"];
+        let target = Ok::<_, Error>(Blocks(vec![
+            Block::Permission,
+            Block::XAttr,
+            Block::User,
+            Block::Group,
+            Block::Size,
+            Block::Date,
+            Block::Name,
+        ]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
+    #[test]
+    fn test_configure_from_insert_xattr_before_name() {
+        let argv = vec!["lsd", "--long", "--blocks", "size,name", "--extended"];
+        let target = Ok::<_, Error>(Blocks(vec![Block::Size, Block::XAttr, Block::Name]));
+
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let result = Blocks::configure_from(&matches, &Config::with_none());
+
+        assert_eq_ok!(result, target);
+    }
+
     #[test]
     fn test_configure_from_prepend_inode_without_long() {
         let argv = vec!["lsd", "--blocks", "permission", "--inode"];
@@ -480,6 +742,34 @@ mod test_blocks {
         let blocks = Blocks(vec![Block::Permission, Block::Date]);
         assert_eq!(Some(blocks), Blocks::from_config(&Config::with_yaml(yaml)));
     }
+
+    #[test]
+    fn test_from_config_comma_string() {
+        let yaml_string = "blocks: \"permission, size,name\"";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let blocks = Blocks(vec![Block::Permission, Block::Size, Block::Name]);
+        assert_eq!(Some(blocks), Blocks::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_comma_string_invalid_is_ignored() {
+        let yaml_string = "blocks: permission,foo,name,";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let blocks = Blocks(vec![Block::Permission, Block::Name]);
+        assert_eq!(Some(blocks), Blocks::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_config_without_valid_block_falls_back_to_long() {
+        let yaml_string = "blocks: foo,bar";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let argv = vec!["lsd", "--long"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Blocks::long(),
+            Blocks::configure_from(&matches, &Config::with_yaml(yaml)).unwrap()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -521,6 +811,21 @@ mod test_block {
         assert_eq!(Ok(Block::SizeValue), Block::try_from("size_value"));
     }
 
+    #[test]
+    fn test_device() {
+        assert_eq!(Ok(Block::Device), Block::try_from("device"));
+    }
+
+    #[test]
+    fn test_context() {
+        assert_eq!(Ok(Block::Context), Block::try_from("context"));
+    }
+
+    #[test]
+    fn test_flags() {
+        assert_eq!(Ok(Block::FileFlags), Block::try_from("flags"));
+    }
+
     #[test]
     fn test_date() {
         assert_eq!(Ok(Block::Date), Block::try_from("date"));
@@ -535,4 +840,36 @@ mod test_block {
     fn test_inode() {
         assert_eq!(Ok(Block::INode), Block::try_from("inode"));
     }
+
+    #[test]
+    fn test_links() {
+        assert_eq!(Ok(Block::Links), Block::try_from("links"));
+    }
+
+    #[test]
+    fn test_git() {
+        assert_eq!(Ok(Block::GitStatus), Block::try_from("git"));
+    }
+
+    #[test]
+    fn test_filetype() {
+        assert_eq!(Ok(Block::FileTypeChar), Block::try_from("filetype"));
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn test_attributes() {
+        assert_eq!(Ok(Block::WinAttributes), Block::try_from("attributes"));
+    }
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_attributes_not_on_windows() {
+        assert_eq!(
+            Err(String::from(
+                "The attributes block is only available on Windows"
+            )),
+            Block::try_from("attributes")
+        );
+    }
 }
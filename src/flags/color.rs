@@ -1,27 +1,41 @@
 //! This module defines the [Color]. To set it up from [ArgMatches], a [Yaml] and its [Default]
 //! value, use its [configure_from](Configurable::configure_from) method.
 
-use super::Configurable;
+use super::{classic_overrides, Configurable};
 
 use crate::config_file::Config;
 
 use clap::ArgMatches;
+use std::env;
+use std::ffi::OsStr;
+use std::path::PathBuf;
 use yaml_rust::Yaml;
 
 /// A collection of flags on how to use colors.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Color {
     /// When to use color.
     pub when: ColorOption,
+    /// The theme file overriding the default colors.
+    pub theme: ColorTheme,
+    /// The background the default colors are meant for.
+    pub scheme: ColorScheme,
 }
 
 impl Color {
     /// Get a `Color` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [ColorOption] is configured with their respective [Configurable] implementation.
+    /// The [ColorOption], [ColorTheme] and [ColorScheme] are configured with their respective
+    /// [Configurable] implementation.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let when = ColorOption::configure_from(matches, config);
-        Self { when }
+        let theme = ColorTheme::configure_from(matches, config);
+        let scheme = ColorScheme::configure_from(matches, config);
+        Self {
+            when,
+            theme,
+            scheme,
+        }
     }
 }
 
@@ -49,14 +63,31 @@ impl ColorOption {
     }
 }
 
+impl ColorOption {
+    /// Whether the terminal named by the "TERM" environment variable can display colors and
+    /// icons, which is not the case of a "dumb" or missing terminal. On Windows, "TERM" is usually
+    /// not set, so any terminal can.
+    pub fn terminal_supports_styles() -> bool {
+        cfg!(windows) || Self::term_supports_styles(env::var_os("TERM").as_deref())
+    }
+
+    fn term_supports_styles(term: Option<&OsStr>) -> bool {
+        match term {
+            Some(term) => !term.is_empty() && term != "dumb",
+            None => false,
+        }
+    }
+}
+
 impl Configurable<Self> for ColorOption {
     /// Get a potential `ColorOption` variant from [ArgMatches].
     ///
-    /// If the "classic" argument is passed, then this returns the [ColorOption::Never] variant in
-    /// a [Some]. Otherwise if the argument is passed, this returns the variant corresponding to
-    /// its parameter in a [Some]. Otherwise this returns [None].
+    /// If the "classic" argument is passed and the argument is not passed after it, then this
+    /// returns the [ColorOption::Never] variant in a [Some]. Otherwise if the argument is passed,
+    /// this returns the variant corresponding to its parameter in a [Some]. Otherwise this returns
+    /// [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.is_present("classic") {
+        if classic_overrides(matches, "color") {
             Some(Self::Never)
         } else if matches.occurrences_of("color") > 0 {
             match matches.value_of("color") {
@@ -95,6 +126,30 @@ impl Configurable<Self> for ColorOption {
             None
         }
     }
+
+    /// Get a potential `ColorOption` variant from the environment.
+    ///
+    /// If the "NO_COLOR" environment variable is set to a non-empty value, this returns the
+    /// [ColorOption::Never] variant in a [Some]. Otherwise if the "CLICOLOR_FORCE" environment
+    /// variable is set to a non-empty value, this returns the [ColorOption::Always] variant in a
+    /// [Some], so that the output is colorized even when piped. Otherwise if the "CLICOLOR"
+    /// environment variable is set to "0", this returns the [ColorOption::Never] variant in a
+    /// [Some]. Otherwise this returns [None].
+    ///
+    /// The environment only replaces the default [ColorOption::Auto] mode: the value set in the
+    /// [Config] and then the "color" argument take precedence over it.
+    fn from_environment() -> Option<Self> {
+        let non_empty = |key| env::var_os(key).filter(|value| !value.is_empty());
+        if non_empty("NO_COLOR").is_some() {
+            Some(Self::Never)
+        } else if non_empty("CLICOLOR_FORCE").is_some() {
+            Some(Self::Always)
+        } else if env::var_os("CLICOLOR").as_deref() == Some(OsStr::new("0")) {
+            Some(Self::Never)
+        } else {
+            None
+        }
+    }
 }
 
 /// The default value for `ColorOption` is [ColorOption::Auto].
@@ -104,6 +159,161 @@ impl Default for ColorOption {
     }
 }
 
+/// The flag showing the path of a YAML theme file, whose colors override the default ones.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ColorTheme(pub Option<PathBuf>);
+
+impl Configurable<Self> for ColorTheme {
+    /// Get a `ColorTheme` from [ArgMatches], the environment, a [Config] or the [Default] value.
+    ///
+    /// Unlike the other flags, the environment takes precedence over the [Config], so that the
+    /// theme can be changed for a single session. The Config is read in any case, to print its
+    /// warnings.
+    fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
+        let from_config = Self::from_config(config);
+
+        Self::from_arg_matches(matches)
+            .or_else(Self::from_environment)
+            .or(from_config)
+            .unwrap_or_default()
+    }
+
+    /// Get a potential `ColorTheme` from [ArgMatches].
+    ///
+    /// If the "theme" argument is passed, this returns its path in a [Some]. Otherwise this
+    /// returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .value_of_os("theme")
+            .map(|path| Self(Some(PathBuf::from(path))))
+    }
+
+    /// Get a potential `ColorTheme` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by "color" ->
+    /// "theme", this returns it as a path in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["color"]["theme"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => Some(Self(Some(PathBuf::from(value)))),
+                _ => {
+                    config.print_wrong_type_warning("color->theme", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ColorTheme` from the environment.
+    ///
+    /// If the "LSD_THEME" environment variable is set to a non-empty value, this returns it as a
+    /// path in a [Some]. Otherwise this returns [None].
+    fn from_environment() -> Option<Self> {
+        match env::var_os("LSD_THEME") {
+            Some(value) if !value.is_empty() => Some(Self(Some(PathBuf::from(value)))),
+            _ => None,
+        }
+    }
+}
+
+/// The flag showing whether the default colors are meant for a light or a dark background.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum ColorScheme {
+    Light,
+    Dark,
+    /// The variant guessing the background of the terminal from the "COLORFGBG" environment
+    /// variable.
+    Auto,
+}
+
+impl ColorScheme {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "light" => Some(Self::Light),
+            "dark" => Some(Self::Dark),
+            "auto" => Some(Self::Auto),
+            _ => None,
+        }
+    }
+
+    /// Get the [Light](ColorScheme::Light) or the [Dark](ColorScheme::Dark) variant. The
+    /// [Auto](ColorScheme::Auto) variant is guessed from the "COLORFGBG" environment variable,
+    /// and is dark when it can not be.
+    pub fn resolve(self) -> Self {
+        match self {
+            Self::Auto => env::var("COLORFGBG")
+                .ok()
+                .and_then(|value| Self::from_colorfgbg(&value))
+                .unwrap_or(Self::Dark),
+            scheme => scheme,
+        }
+    }
+
+    /// Get the scheme matching the background of a "COLORFGBG" value, like "15;0" or
+    /// "0;default;15", whose last field is the color of the background. The white and the
+    /// bright colors, but the bright black, are light backgrounds.
+    fn from_colorfgbg(value: &str) -> Option<Self> {
+        match value.rsplit(';').next()?.parse::<u8>().ok()? {
+            0..=6 | 8 => Some(Self::Dark),
+            7 | 9..=15 => Some(Self::Light),
+            _ => None,
+        }
+    }
+}
+
+impl Configurable<Self> for ColorScheme {
+    /// Get a potential `ColorScheme` variant from [ArgMatches].
+    ///
+    /// If the "color-scheme" argument is passed, this returns the variant corresponding to its
+    /// parameter in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("color-scheme") > 0 {
+            match matches.value_of("color-scheme").and_then(Self::from_str) {
+                Some(scheme) => Some(scheme),
+                None => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `ColorScheme` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by "color" ->
+    /// "scheme" and it is one of "light", "dark" or "auto", this returns its corresponding
+    /// variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["color"]["scheme"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match Self::from_str(value) {
+                    Some(scheme) => Some(scheme),
+                    None => {
+                        config.print_invalid_value_warning("color->scheme", value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("color->scheme", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `ColorScheme` is [ColorScheme::Auto].
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
 #[cfg(test)]
 mod test_color_option {
     use super::ColorOption;
@@ -112,6 +322,9 @@ mod test_color_option {
     use crate::config_file::Config;
     use crate::flags::Configurable;
 
+    use std::env;
+    use std::ffi::OsStr;
+    use std::sync::Mutex;
     use yaml_rust::YamlLoader;
 
     #[test]
@@ -161,6 +374,16 @@ mod test_color_option {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_overridden_classic_mode() {
+        let argv = vec!["lsd", "--classic", "--color", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorOption::Always),
+            ColorOption::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, ColorOption::from_config(&Config::with_none()));
@@ -212,4 +435,358 @@ mod test_color_option {
             ColorOption::from_config(&Config::with_yaml(yaml))
         );
     }
+
+    // The environment is shared by all the tests, so the ones changing it are serialized.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    // The color variables are set to the given values, and the others are unset.
+    const COLOR_VARS: [&str; 3] = ["NO_COLOR", "CLICOLOR_FORCE", "CLICOLOR"];
+
+    fn with_env_vars<F: FnOnce()>(vars: &[(&str, &str)], test: F) {
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let previous: Vec<_> = COLOR_VARS.iter().map(env::var_os).collect();
+
+        for key in &COLOR_VARS {
+            match vars.iter().find(|(var, _)| var == key) {
+                Some((_, value)) => env::set_var(key, value),
+                None => env::remove_var(key),
+            }
+        }
+        test();
+        for (key, value) in COLOR_VARS.iter().zip(previous) {
+            match value {
+                Some(value) => env::set_var(key, value),
+                None => env::remove_var(key),
+            }
+        }
+    }
+
+    fn with_env_var<F: FnOnce()>(key: &str, value: Option<&str>, test: F) {
+        match value {
+            Some(value) => with_env_vars(&[(key, value)], test),
+            None => with_env_vars(&[], test),
+        }
+    }
+
+    #[test]
+    fn test_term_supports_styles() {
+        assert!(ColorOption::term_supports_styles(Some(OsStr::new(
+            "xterm-256color"
+        ))));
+        assert!(!ColorOption::term_supports_styles(Some(OsStr::new("dumb"))));
+        assert!(!ColorOption::term_supports_styles(Some(OsStr::new(""))));
+        assert!(!ColorOption::term_supports_styles(None));
+    }
+
+    #[test]
+    fn test_from_environment_no_color() {
+        with_env_var("NO_COLOR", Some("1"), || {
+            assert_eq!(Some(ColorOption::Never), ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_from_environment_no_color_empty() {
+        with_env_var("NO_COLOR", Some(""), || {
+            assert_eq!(None, ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_from_environment_no_color_unset() {
+        with_env_var("NO_COLOR", None, || {
+            assert_eq!(None, ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_configure_from_no_color() {
+        with_env_var("NO_COLOR", Some("1"), || {
+            let argv = vec!["lsd"];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(
+                ColorOption::Never,
+                ColorOption::configure_from(&matches, &Config::with_none())
+            );
+        });
+    }
+
+    #[test]
+    fn test_configure_from_no_color_overridden_by_arg() {
+        with_env_var("NO_COLOR", Some("1"), || {
+            let argv = vec!["lsd", "--color", "always"];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(
+                ColorOption::Always,
+                ColorOption::configure_from(&matches, &Config::with_none())
+            );
+        });
+    }
+
+    #[test]
+    fn test_configure_from_no_color_overridden_by_config() {
+        with_env_var("NO_COLOR", Some("1"), || {
+            let argv = vec!["lsd"];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            let yaml_string = "color:\n  when: always";
+            let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+            assert_eq!(
+                ColorOption::Always,
+                ColorOption::configure_from(&matches, &Config::with_yaml(yaml))
+            );
+        });
+    }
+
+    #[test]
+    fn test_from_environment_clicolor_zero() {
+        with_env_var("CLICOLOR", Some("0"), || {
+            assert_eq!(Some(ColorOption::Never), ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_from_environment_clicolor_one() {
+        with_env_var("CLICOLOR", Some("1"), || {
+            assert_eq!(None, ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_from_environment_clicolor_force() {
+        with_env_var("CLICOLOR_FORCE", Some("1"), || {
+            assert_eq!(Some(ColorOption::Always), ColorOption::from_environment());
+        });
+        with_env_var("CLICOLOR_FORCE", Some(""), || {
+            assert_eq!(None, ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_from_environment_clicolor_force_over_clicolor() {
+        with_env_vars(&[("CLICOLOR_FORCE", "1"), ("CLICOLOR", "0")], || {
+            assert_eq!(Some(ColorOption::Always), ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_from_environment_no_color_over_clicolor_force() {
+        with_env_vars(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], || {
+            assert_eq!(Some(ColorOption::Never), ColorOption::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_configure_from_clicolor_force_overridden_by_arg() {
+        with_env_var("CLICOLOR_FORCE", Some("1"), || {
+            let argv = vec!["lsd", "--color", "auto"];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(
+                ColorOption::Auto,
+                ColorOption::configure_from(&matches, &Config::with_none())
+            );
+        });
+    }
+
+    #[test]
+    fn test_configure_from_clicolor_zero_overridden_by_arg() {
+        with_env_var("CLICOLOR", Some("0"), || {
+            let argv = vec!["lsd", "--color", "always"];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(
+                ColorOption::Always,
+                ColorOption::configure_from(&matches, &Config::with_none())
+            );
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_color_theme {
+    use super::ColorTheme;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use std::env;
+    use std::path::PathBuf;
+    use std::sync::Mutex;
+    use yaml_rust::YamlLoader;
+
+    // The environment is shared by all the tests, so the ones changing it are serialized.
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn with_theme_env<F: FnOnce()>(value: Option<&str>, test: F) {
+        let _lock = ENV_LOCK.lock().unwrap_or_else(|err| err.into_inner());
+        let previous = env::var_os("LSD_THEME");
+
+        match value {
+            Some(value) => env::set_var("LSD_THEME", value),
+            None => env::remove_var("LSD_THEME"),
+        }
+        test();
+        match previous {
+            Some(value) => env::set_var("LSD_THEME", value),
+            None => env::remove_var("LSD_THEME"),
+        }
+    }
+
+    fn theme(path: &str) -> ColorTheme {
+        ColorTheme(Some(PathBuf::from(path)))
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ColorTheme::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_theme() {
+        let argv = vec!["lsd", "--theme", "theme.yaml"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(theme("theme.yaml")),
+            ColorTheme::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ColorTheme::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_theme() {
+        let yaml_string = "color:\n  theme: theme.yaml";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(theme("theme.yaml")),
+            ColorTheme::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_wrong_type() {
+        let yaml_string = "color:\n  theme: 1";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, ColorTheme::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_environment() {
+        with_theme_env(Some("env.yaml"), || {
+            assert_eq!(Some(theme("env.yaml")), ColorTheme::from_environment());
+        });
+        with_theme_env(Some(""), || {
+            assert_eq!(None, ColorTheme::from_environment());
+        });
+    }
+
+    #[test]
+    fn test_configure_from_precedence() {
+        let yaml_string = "color:\n  theme: config.yaml";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let config = Config::with_yaml(yaml);
+
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        let matches_with_flag = app::build()
+            .get_matches_from_safe(vec!["lsd", "--theme", "flag.yaml"])
+            .unwrap();
+
+        with_theme_env(None, || {
+            assert_eq!(
+                ColorTheme::default(),
+                ColorTheme::configure_from(&matches, &Config::with_none())
+            );
+            assert_eq!(
+                theme("config.yaml"),
+                ColorTheme::configure_from(&matches, &config)
+            );
+        });
+        with_theme_env(Some("env.yaml"), || {
+            assert_eq!(
+                theme("env.yaml"),
+                ColorTheme::configure_from(&matches, &config)
+            );
+            assert_eq!(
+                theme("flag.yaml"),
+                ColorTheme::configure_from(&matches_with_flag, &config)
+            );
+        });
+    }
+}
+
+#[cfg(test)]
+mod test_color_scheme {
+    use super::ColorScheme;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, ColorScheme::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_light() {
+        let argv = vec!["lsd", "--color-scheme", "light"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(ColorScheme::Light),
+            ColorScheme::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, ColorScheme::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_light() {
+        let yaml_string = "color:\n  scheme: light";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(ColorScheme::Light),
+            ColorScheme::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "color:\n  scheme: bright";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, ColorScheme::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_colorfgbg() {
+        assert_eq!(Some(ColorScheme::Dark), ColorScheme::from_colorfgbg("15;0"));
+        assert_eq!(Some(ColorScheme::Dark), ColorScheme::from_colorfgbg("7;8"));
+        assert_eq!(
+            Some(ColorScheme::Light),
+            ColorScheme::from_colorfgbg("0;15")
+        );
+        assert_eq!(
+            Some(ColorScheme::Light),
+            ColorScheme::from_colorfgbg("0;default;7")
+        );
+        assert_eq!(None, ColorScheme::from_colorfgbg("0;default"));
+        assert_eq!(None, ColorScheme::from_colorfgbg(""));
+    }
+
+    #[test]
+    fn test_resolve_keeps_the_forced_schemes() {
+        assert_eq!(ColorScheme::Light, ColorScheme::Light.resolve());
+        assert_eq!(ColorScheme::Dark, ColorScheme::Dark.resolve());
+        assert_ne!(ColorScheme::Auto, ColorScheme::Auto.resolve());
+    }
 }
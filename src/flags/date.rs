@@ -1,7 +1,7 @@
 //! This module defines the [DateFlag]. To set it up from [ArgMatches], a [Yaml] and its
 //! [Default] value, use its [configure_from](Configurable::configure_from) method.
 
-use super::Configurable;
+use super::{classic_overrides, last_index, Configurable};
 
 use crate::app;
 use crate::config_file::Config;
@@ -15,9 +15,39 @@ pub enum DateFlag {
     Date,
     Relative,
     Formatted(String),
+    /// The variant formatting the dates of the last six months with the first format, and the
+    /// older ones or the ones in the future with the second format, like `ls`.
+    FormattedByAge(String, String),
 }
 
+/// The format of the "full-iso" style of GNU `ls`.
+const FULL_ISO: &str = "%Y-%m-%d %H:%M:%S.%f %z";
+/// The format of the "long-iso" style of GNU `ls`.
+const LONG_ISO: &str = "%Y-%m-%d %H:%M";
+/// The formats of the recent and old dates in the "iso" style of GNU `ls`.
+const ISO: (&str, &str) = ("%m-%d %H:%M", "%Y-%m-%d ");
+/// The formats of the recent and old dates in the "locale" style of GNU `ls`, in the C locale.
+const LOCALE: (&str, &str) = ("%b %e %H:%M", "%b %e  %Y");
+
 impl DateFlag {
+    /// Get a value from one of "date", "relative", the time style presets or a format starting
+    /// with a "+", which is not validated.
+    fn from_str(value: &str) -> Option<Self> {
+        let by_age = |(recent, old): (&str, &str)| {
+            Some(Self::FormattedByAge(recent.to_string(), old.to_string()))
+        };
+        match value {
+            "date" => Some(Self::Date),
+            "relative" => Some(Self::Relative),
+            "full-iso" => Some(Self::Formatted(FULL_ISO.to_string())),
+            "long-iso" => Some(Self::Formatted(LONG_ISO.to_string())),
+            "iso" => by_age(ISO),
+            "locale" => by_age(LOCALE),
+            _ if value.starts_with('+') => Some(Self::Formatted(value[1..].to_string())),
+            _ => None,
+        }
+    }
+
     /// Get a value from a date format string. The [Config] is used to log warnings about wrong
     /// values in a Yaml.
     fn from_format_string(value: &str, config: &Config) -> Option<Self> {
@@ -34,13 +64,14 @@ impl DateFlag {
     /// in a Yaml.
     fn from_yaml_string(value: &str, config: &Config) -> Option<Self> {
         match value {
-            "date" => Some(Self::Date),
-            "relative" => Some(Self::Relative),
             _ if value.starts_with('+') => Self::from_format_string(&value, &config),
-            _ => {
-                config.print_warning(&format!("Not a valid date value: {}", value));
-                None
-            }
+            _ => match Self::from_str(value) {
+                Some(date) => Some(date),
+                None => {
+                    config.print_warning(&format!("Not a valid date value: {}", value));
+                    None
+                }
+            },
         }
     }
 }
@@ -48,23 +79,29 @@ impl DateFlag {
 impl Configurable<Self> for DateFlag {
     /// Get a potential `DateFlag` variant from [ArgMatches].
     ///
-    /// If the "classic" argument is passed, then this returns the [DateFlag::Date] variant in a
-    /// [Some]. Otherwise if the argument is passed, this returns the variant corresponding to its
-    /// parameter in a [Some]. Otherwise this returns [None].
+    /// If the "classic" argument is passed and none of the "date", "time-style" and "full-time"
+    /// arguments is passed after it, then this returns the [DateFlag::Date] variant in a [Some].
+    /// Otherwise if any of them is passed, this returns the variant corresponding to the last one
+    /// passed in a [Some], which is the "full-iso" style for "full-time". Otherwise this returns
+    /// [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.is_present("classic") {
-            Some(Self::Date)
-        } else if matches.occurrences_of("date") > 0 {
-            match matches.value_of("date") {
-                Some("date") => Some(Self::Date),
-                Some("relative") => Some(Self::Relative),
-                Some(format) if format.starts_with('+') => {
-                    Some(Self::Formatted(format[1..].to_owned()))
-                }
-                _ => panic!("This should not be reachable!"),
-            }
-        } else {
-            None
+        let names = ["date", "time-style", "full-time"];
+        if names.iter().all(|name| classic_overrides(matches, name)) {
+            return Some(Self::Date);
+        }
+
+        let name = match names
+            .iter()
+            .filter_map(|name| last_index(matches, name).map(|index| (index, *name)))
+            .max()
+        {
+            Some((_, "full-time")) => return Some(Self::Formatted(FULL_ISO.to_string())),
+            Some((_, name)) => name,
+            None => return None,
+        };
+        match matches.value_of(name).and_then(Self::from_str) {
+            Some(date) => Some(date),
+            None => panic!("This should not be reachable!"),
         }
     }
 
@@ -73,8 +110,9 @@ impl Configurable<Self> for DateFlag {
     /// If the Config's [Yaml] contains a [Boolean](Yaml::Boolean) value pointed to by "classic"
     /// and its value is `true`, then this returns the [DateFlag::Date] variant in a [Some].
     /// Otherwise if the Yaml contains a [String](Yaml::String) value pointed to by "date" and it
-    /// is one of "date" or "relative", this returns its corresponding variant in a [Some].
-    /// Otherwise this returns [None].
+    /// is one of "date", "relative", "full-iso", "long-iso", "iso" or "locale", or a valid format
+    /// starting with a "+", this returns its corresponding variant in a [Some]. Otherwise this
+    /// returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(yaml) = &config.yaml {
             if let Yaml::Boolean(true) = &yaml["classic"] {
@@ -102,9 +140,81 @@ impl Default for DateFlag {
     }
 }
 
+/// The flag showing which time stamp of the files to display and to sort by.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum TimeKind {
+    Modified,
+    Accessed,
+    /// The variant for the last change of the status of the file, like its permissions.
+    Changed,
+    Created,
+}
+
+impl TimeKind {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "modified" => Some(Self::Modified),
+            "accessed" => Some(Self::Accessed),
+            "changed" => Some(Self::Changed),
+            "created" => Some(Self::Created),
+            _ => None,
+        }
+    }
+}
+
+impl Configurable<Self> for TimeKind {
+    /// Get a potential `TimeKind` variant from [ArgMatches].
+    ///
+    /// If the "time" argument is passed, this returns the variant corresponding to its parameter
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("time") > 0 {
+            match matches.value_of("time").and_then(Self::from_str) {
+                Some(kind) => Some(kind),
+                None => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `TimeKind` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by "time" and it
+    /// is one of "modified", "accessed", "changed" or "created", this returns its corresponding
+    /// variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["time"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match Self::from_str(value) {
+                    Some(kind) => Some(kind),
+                    None => {
+                        config.print_invalid_value_warning("time", value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("time", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `TimeKind` is [TimeKind::Modified].
+impl Default for TimeKind {
+    fn default() -> Self {
+        Self::Modified
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::DateFlag;
+    use super::{DateFlag, TimeKind};
 
     use crate::app;
     use crate::config_file::Config;
@@ -193,6 +303,113 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_time_style_long_iso() {
+        let argv = vec!["lsd", "--time-style", "long-iso"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Formatted("%Y-%m-%d %H:%M".to_string())),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_time_style_iso() {
+        let argv = vec!["lsd", "--time-style", "iso"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::FormattedByAge(
+                "%m-%d %H:%M".to_string(),
+                "%Y-%m-%d ".to_string()
+            )),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_time_style_format() {
+        let argv = vec!["lsd", "--time-style", "+%F"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Formatted("%F".to_string())),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_time_style_invalid() {
+        let argv = vec!["lsd", "--time-style", "relative"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_arg_matches_last_of_date_and_time_style() {
+        let argv = vec!["lsd", "--date", "relative", "--time-style", "full-iso"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Formatted("%Y-%m-%d %H:%M:%S.%f %z".to_string())),
+            DateFlag::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--time-style", "full-iso", "--date", "relative"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Relative),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_full_time() {
+        let argv = vec!["lsd", "--date", "relative", "--full-time"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Formatted("%Y-%m-%d %H:%M:%S.%f %z".to_string())),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_full_time_overridden() {
+        let argv = vec!["lsd", "--full-time", "--date", "relative"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Relative),
+            DateFlag::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--full-time", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(DateFlag::Date), DateFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_time_style_classic_mode() {
+        let argv = vec!["lsd", "--time-style", "iso", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(DateFlag::Date), DateFlag::from_arg_matches(&matches));
+
+        let argv = vec!["lsd", "--classic", "--time-style", "long-iso"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateFlag::Formatted("%Y-%m-%d %H:%M".to_string())),
+            DateFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_time_style_preset() {
+        let yaml_string = "date: locale";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateFlag::FormattedByAge(
+                "%b %e %H:%M".to_string(),
+                "%b %e  %Y".to_string()
+            )),
+            DateFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
     #[test]
     fn test_from_config_format() {
         let yaml_string = "date: +%F";
@@ -203,6 +420,35 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_config_format_with_spaces() {
+        let yaml_string = "date: \"+%Y-%m-%d %H:%M\"";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateFlag::Formatted("%Y-%m-%d %H:%M".to_string())),
+            DateFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_format_without_plus() {
+        let yaml_string = "date: \"%Y-%m-%d\"";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, DateFlag::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_configure_from_arg_overrides_config_format() {
+        let argv = vec!["lsd", "--date", "relative"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml_string = "date: +%F";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            DateFlag::Relative,
+            DateFlag::configure_from(&matches, &Config::with_yaml(yaml))
+        );
+    }
+
     #[test]
     fn test_from_config_format_invalid() {
         let yaml_string = "date: +%J";
@@ -219,4 +465,44 @@ mod test {
             DateFlag::from_config(&Config::with_yaml(yaml))
         );
     }
+
+    #[test]
+    fn test_time_kind_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TimeKind::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_time_kind_from_arg_matches_accessed() {
+        let argv = vec!["lsd", "--time", "accessed"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TimeKind::Accessed),
+            TimeKind::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_time_kind_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--time", "born"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_time_kind_from_config_created() {
+        let yaml_string = "time: created";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(TimeKind::Created),
+            TimeKind::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_time_kind_from_config_invalid() {
+        let yaml_string = "time: born";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, TimeKind::from_config(&Config::with_yaml(yaml)));
+    }
 }
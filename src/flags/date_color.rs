@@ -0,0 +1,221 @@
+//! This module defines the [DateColor] options. To set it up from [ArgMatches], a [Yaml]
+//! and its [Default] value, use the [configure_from](DateColor::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// A collection of flags on how to color the dates.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DateColor {
+    pub gradient: DateGradient,
+    pub thresholds: DateThresholds,
+}
+
+impl DateColor {
+    /// Get a `DateColor` struct from [ArgMatches], a [Config] or the [Default] values.
+    ///
+    /// The [DateGradient] and [DateThresholds] are configured with their respective
+    /// [Configurable] implementation.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
+        let gradient = DateGradient::configure_from(matches, config);
+        let thresholds = DateThresholds::configure_from(matches, config);
+        Self {
+            gradient,
+            thresholds,
+        }
+    }
+}
+
+/// The flag showing whether to color the dates on a gradient, from the recent ones to the old
+/// ones which are dimmed, instead of only telling apart the dates of the last hour and day.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DateGradient(pub bool);
+
+impl Configurable<Self> for DateGradient {
+    /// Get a potential `DateGradient` value from [ArgMatches].
+    ///
+    /// If the "date-color" argument is passed, this returns a `DateGradient` with value `true` in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("date-color") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DateGradient` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains the [Boolean](Yaml::Boolean) value pointed to by
+    /// "date-color" -> "gradient", this returns its value as the value of the `DateGradient`, in
+    /// a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["date-color"]["gradient"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("date-color->gradient", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The ages in seconds up to which the dates of the gradient are considered recent, a day old
+/// and a week old. The older dates are dimmed.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct DateThresholds {
+    pub recent: i64,
+    pub day: i64,
+    pub week: i64,
+}
+
+impl DateThresholds {
+    /// Get the threshold pointed to by "date-color" -> "thresholds" -> `key` in a [Yaml]. The
+    /// [Config] is used to log warnings about wrong values.
+    fn threshold_from_yaml(yaml: &Yaml, key: &str, config: &Config) -> Option<i64> {
+        match &yaml[key] {
+            Yaml::BadValue => None,
+            Yaml::Integer(value) if *value > 0 => Some(*value),
+            Yaml::Integer(value) => {
+                config.print_invalid_value_warning(
+                    &format!("date-color->thresholds->{}", key),
+                    &value.to_string(),
+                );
+                None
+            }
+            _ => {
+                config.print_wrong_type_warning(
+                    &format!("date-color->thresholds->{}", key),
+                    "integer",
+                );
+                None
+            }
+        }
+    }
+}
+
+impl Configurable<Self> for DateThresholds {
+    /// The `DateThresholds` can not be configured by [ArgMatches].
+    ///
+    /// Return `None`
+    fn from_arg_matches(_: &ArgMatches) -> Option<Self> {
+        None
+    }
+
+    /// Get a potential `DateThresholds` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Hash](Yaml::Hash) pointed to by "date-color" ->
+    /// "thresholds", each of its positive [Integer](Yaml::Integer) values pointed to by "recent",
+    /// "day" and "week" replaces the default one, and the result is returned in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["date-color"]["thresholds"] {
+                Yaml::BadValue => None,
+                Yaml::Hash(_) => {
+                    let yaml = &yaml["date-color"]["thresholds"];
+                    let default = Self::default();
+                    Some(Self {
+                        recent: Self::threshold_from_yaml(yaml, "recent", config)
+                            .unwrap_or(default.recent),
+                        day: Self::threshold_from_yaml(yaml, "day", config).unwrap_or(default.day),
+                        week: Self::threshold_from_yaml(yaml, "week", config)
+                            .unwrap_or(default.week),
+                    })
+                }
+                _ => {
+                    config.print_wrong_type_warning("date-color->thresholds", "object");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default `DateThresholds` are an hour, a day and a week.
+impl Default for DateThresholds {
+    fn default() -> Self {
+        Self {
+            recent: 60 * 60,
+            day: 24 * 60 * 60,
+            week: 7 * 24 * 60 * 60,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{DateColor, DateGradient, DateThresholds};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        let date_color = DateColor::default();
+        assert_eq!(DateGradient(false), date_color.gradient);
+        assert_eq!(3600, date_color.thresholds.recent);
+        assert_eq!(86400, date_color.thresholds.day);
+        assert_eq!(604800, date_color.thresholds.week);
+    }
+
+    #[test]
+    fn test_gradient_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DateGradient::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_gradient_from_arg_matches_true() {
+        let argv = vec!["lsd", "--date-color"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DateGradient(true)),
+            DateGradient::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_gradient_from_config_true() {
+        let yaml_string = "date-color:\n  gradient: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateGradient(true)),
+            DateGradient::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_thresholds_from_config_none() {
+        assert_eq!(None, DateThresholds::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_thresholds_from_config_partial() {
+        let yaml_string = "date-color:\n  thresholds:\n    recent: 60\n    week: -1";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DateThresholds {
+                recent: 60,
+                day: 86400,
+                week: 604800,
+            }),
+            DateThresholds::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
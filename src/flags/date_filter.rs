@@ -0,0 +1,201 @@
+//! This module defines the [DateFilter] options. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](DateFilter::configure_from) method.
+
+use crate::app;
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use time::Timespec;
+use yaml_rust::Yaml;
+
+/// The range of dates of the files to list, compared to the time stamp of the kind displayed by
+/// the date block. A file is listed when it is more recent than `newer_than` and older than
+/// `older_than`. When neither is set, files of any date are listed.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DateFilter {
+    pub newer_than: Option<Timespec>,
+    pub older_than: Option<Timespec>,
+}
+
+impl DateFilter {
+    /// Get a `DateFilter` struct from [ArgMatches], a [Config] or the [Default] values.
+    ///
+    /// Each bound is taken from the "newer-than" and "older-than" arguments, or else from the
+    /// values of the same name in the [Config]. The durations are counted back from now.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
+        Self::configure_at(matches, config, time::get_time())
+    }
+
+    /// Get a `DateFilter` like [configure_from](DateFilter::configure_from), with the durations
+    /// counted back from `now`.
+    fn configure_at(matches: &ArgMatches, config: &Config, now: Timespec) -> Self {
+        let bound = |name| {
+            Self::bound_from_arg_matches(matches, name, now)
+                .or_else(|| Self::bound_from_config(config, name, now))
+        };
+
+        Self {
+            newer_than: bound("newer-than"),
+            older_than: bound("older-than"),
+        }
+    }
+
+    /// Get a potential bound from the argument called `name`.
+    fn bound_from_arg_matches(matches: &ArgMatches, name: &str, now: Timespec) -> Option<Timespec> {
+        matches
+            .value_of(name)
+            .map(|value| match app::parse_date_bound(value, now) {
+                Some(date) => date,
+                None => panic!("This should not be reachable!"),
+            })
+    }
+
+    /// Get a potential bound from the valid [String](Yaml::String) value pointed to by `name` in
+    /// the Config's [Yaml].
+    fn bound_from_config(config: &Config, name: &str, now: Timespec) -> Option<Timespec> {
+        let yaml = config.yaml.as_ref()?;
+        match &yaml[name] {
+            Yaml::BadValue => None,
+            Yaml::String(value) => match app::parse_date_bound(value, now) {
+                Some(date) => Some(date),
+                None => {
+                    config.print_invalid_value_warning(name, value);
+                    None
+                }
+            },
+            _ => {
+                config.print_wrong_type_warning(name, "string");
+                None
+            }
+        }
+    }
+
+    /// Whether no bound is set, so that no file is filtered out by its date.
+    pub fn is_empty(&self) -> bool {
+        self.newer_than.is_none() && self.older_than.is_none()
+    }
+
+    /// Whether a file with the given time stamp should be listed. The files without the time
+    /// stamp are only listed when no bound is set.
+    pub fn matches(&self, time: Option<Timespec>) -> bool {
+        match time {
+            Some(time) => {
+                !matches!(self.newer_than, Some(min) if time <= min)
+                    && !matches!(self.older_than, Some(max) if time >= max)
+            }
+            None => self.is_empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DateFilter;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use time::{Duration, Timespec};
+    use yaml_rust::YamlLoader;
+
+    const NOW: Timespec = Timespec {
+        sec: 1_600_000_000,
+        nsec: 0,
+    };
+
+    #[test]
+    fn test_parse_date_bound_durations() {
+        let ago = |seconds| Some(NOW - Duration::seconds(seconds));
+        assert_eq!(ago(30), app::parse_date_bound("30s", NOW));
+        assert_eq!(ago(15 * 60), app::parse_date_bound("15m", NOW));
+        assert_eq!(ago(2 * 60 * 60), app::parse_date_bound("2h", NOW));
+        assert_eq!(ago(7 * 24 * 60 * 60), app::parse_date_bound("7d", NOW));
+        assert_eq!(ago(14 * 24 * 60 * 60), app::parse_date_bound("2w", NOW));
+    }
+
+    #[test]
+    fn test_parse_date_bound_dates() {
+        let day = app::parse_date_bound("2020-01-31", NOW).unwrap();
+        let noon = app::parse_date_bound("2020-01-31T12:00:00", NOW).unwrap();
+        assert_eq!(Duration::hours(12), noon - day);
+        assert_eq!(
+            Some(noon),
+            app::parse_date_bound("2020-01-31 12:00:00", NOW)
+        );
+    }
+
+    #[test]
+    fn test_parse_date_bound_invalid() {
+        assert_eq!(None, app::parse_date_bound("", NOW));
+        assert_eq!(None, app::parse_date_bound("d", NOW));
+        assert_eq!(None, app::parse_date_bound("7y", NOW));
+        assert_eq!(None, app::parse_date_bound("2020-13-01", NOW));
+        assert_eq!(None, app::parse_date_bound("yesterday", NOW));
+    }
+
+    #[test]
+    fn test_from_arg_matches_range() {
+        let argv = vec!["lsd", "--newer-than", "7d", "--older-than", "1d"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            DateFilter {
+                newer_than: Some(NOW - Duration::days(7)),
+                older_than: Some(NOW - Duration::days(1)),
+            },
+            DateFilter::configure_at(&matches, &Config::with_none(), NOW)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--newer-than", "soon"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_arg_matches_take_precedence_over_config() {
+        let yaml_string = "newer-than: 2w\nolder-than: 2h";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let argv = vec!["lsd", "--newer-than", "1d"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            DateFilter {
+                newer_than: Some(NOW - Duration::days(1)),
+                older_than: Some(NOW - Duration::hours(2)),
+            },
+            DateFilter::configure_at(&matches, &Config::with_yaml(yaml), NOW)
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "newer-than: 7\nolder-than: later";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        assert!(DateFilter::configure_at(&matches, &Config::with_yaml(yaml), NOW).is_empty());
+    }
+
+    #[test]
+    fn test_matches_across_a_day() {
+        let filter = DateFilter {
+            newer_than: Some(NOW - Duration::days(1)),
+            older_than: None,
+        };
+        assert!(filter.matches(Some(NOW - Duration::hours(23))));
+        assert!(!filter.matches(Some(NOW - Duration::hours(25))));
+        assert!(!filter.matches(None));
+
+        let filter = DateFilter {
+            newer_than: None,
+            older_than: Some(NOW - Duration::days(1)),
+        };
+        assert!(!filter.matches(Some(NOW - Duration::hours(23))));
+        assert!(filter.matches(Some(NOW - Duration::hours(25))));
+    }
+
+    #[test]
+    fn test_default_matches_everything() {
+        assert!(DateFilter::default().matches(None));
+        assert!(DateFilter::default().matches(Some(NOW)));
+    }
+}
@@ -1,5 +1,6 @@
-//! This module defines the [Dereference] flag. To set it up from [ArgMatches], a [Yaml] and its
-//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+//! This module defines the [Dereference] and the [DereferenceColumns] flags. To set them up from
+//! [ArgMatches], a [Yaml] and their [Default] value, use the
+//! [configure_from](Configurable::configure_from) method.
 
 use super::Configurable;
 
@@ -46,9 +47,101 @@ impl Configurable<Self> for Dereference {
     }
 }
 
+/// The columns describing the file a symbolic link points to rather than the link itself, while
+/// the name and the arrow still show the link. When it is empty, the links are described by their
+/// own metadata, unless all of it is dereferenced with [Dereference].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DereferenceColumns(pub Vec<DereferenceColumn>);
+
+impl DereferenceColumns {
+    /// Whether the given column describes the targets of the links.
+    pub fn contains(&self, column: DereferenceColumn) -> bool {
+        self.0.contains(&column)
+    }
+}
+
+impl Configurable<Self> for DereferenceColumns {
+    /// Get a potential `DereferenceColumns` from [ArgMatches].
+    ///
+    /// If the "dereference-columns" argument is passed, this returns a `DereferenceColumns`
+    /// containing all of its values in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("dereference-columns") > 0 {
+            matches.values_of("dereference-columns").map(|values| {
+                Self(
+                    values
+                        .map(|value| match DereferenceColumn::from_str(value) {
+                            Some(column) => column,
+                            None => panic!("This should not be reachable!"),
+                        })
+                        .collect(),
+                )
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DereferenceColumns` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Array](Yaml::Array) value pointed to by
+    /// "dereference-columns", each of its valid [String](Yaml::String) values is returned in a
+    /// `DereferenceColumns` in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["dereference-columns"] {
+                Yaml::BadValue => None,
+                Yaml::Array(values) => {
+                    let mut columns = vec![];
+                    for value in values {
+                        match value {
+                            Yaml::String(value) => match DereferenceColumn::from_str(value) {
+                                Some(column) => columns.push(column),
+                                None => {
+                                    config.print_invalid_value_warning("dereference-columns", value)
+                                }
+                            },
+                            _ => config.print_warning(
+                                "The dereference-columns config values have to be strings.",
+                            ),
+                        }
+                    }
+                    Some(Self(columns))
+                }
+                _ => {
+                    config.print_wrong_type_warning("dereference-columns", "array");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// A column which can describe the targets of the symbolic links.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum DereferenceColumn {
+    Size,
+    /// The file type, shown by the permission and the filetype blocks.
+    FileType,
+    Date,
+}
+
+impl DereferenceColumn {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "size" => Some(Self::Size),
+            "type" => Some(Self::FileType),
+            "date" => Some(Self::Date),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Dereference;
+    use super::{Dereference, DereferenceColumn, DereferenceColumns};
 
     use crate::app;
     use crate::config_file::Config;
@@ -104,4 +197,77 @@ mod test {
             Dereference::from_config(&Config::with_yaml(yaml))
         );
     }
+
+    #[test]
+    fn test_columns_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DereferenceColumns::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_columns_from_arg_matches() {
+        let argv = vec!["lsd", "--dereference-columns=size,type"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DereferenceColumns(vec![
+                DereferenceColumn::Size,
+                DereferenceColumn::FileType
+            ])),
+            DereferenceColumns::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_columns_from_arg_matches_repeated() {
+        let argv = vec![
+            "lsd",
+            "--dereference-columns",
+            "date",
+            "--dereference-columns",
+            "size",
+        ];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DereferenceColumns(vec![
+                DereferenceColumn::Date,
+                DereferenceColumn::Size
+            ])),
+            DereferenceColumns::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_columns_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--dereference-columns=name"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_columns_from_config_none() {
+        assert_eq!(None, DereferenceColumns::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_columns_from_config() {
+        let yaml_string = "dereference-columns: [size, bogus, date]";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DereferenceColumns(vec![
+                DereferenceColumn::Size,
+                DereferenceColumn::Date
+            ])),
+            DereferenceColumns::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_columns_from_config_wrong_type() {
+        let yaml_string = "dereference-columns: size";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            None,
+            DereferenceColumns::from_config(&Config::with_yaml(yaml))
+        );
+    }
 }
@@ -0,0 +1,114 @@
+//! This module defines the [DereferenceCommandLine] flag. To set it up from [ArgMatches], a
+//! [Yaml] and its [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing whether to dereference the symbolic links given on the command line, but not
+/// the ones found while recursing into directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct DereferenceCommandLine(pub bool);
+
+impl Configurable<Self> for DereferenceCommandLine {
+    /// Get a potential `DereferenceCommandLine` value from [ArgMatches].
+    ///
+    /// If the "dereference-command-line" argument is passed, this returns a
+    /// `DereferenceCommandLine` with value `true` in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("dereference-command-line") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DereferenceCommandLine` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains the [Boolean](Yaml::Boolean) value pointed to by
+    /// "dereference-command-line", this returns its value as the value of the
+    /// `DereferenceCommandLine`, in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["dereference-command-line"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("dereference-command-line", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DereferenceCommandLine;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DereferenceCommandLine::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--dereference-command-line"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DereferenceCommandLine(true)),
+            DereferenceCommandLine::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(
+            None,
+            DereferenceCommandLine::from_config(&Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_config_empty() {
+        let yaml_string = "---";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            None,
+            DereferenceCommandLine::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let yaml_string = "dereference-command-line: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DereferenceCommandLine(true)),
+            DereferenceCommandLine::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_false() {
+        let yaml_string = "dereference-command-line: false";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DereferenceCommandLine(false)),
+            DereferenceCommandLine::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
@@ -0,0 +1,122 @@
+//! This module defines the [DirSize]. To set it up from [ArgMatches], a [Yaml] and its [Default]
+//! value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing what to display in the size column for the directories.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum DirSize {
+    /// The variant to show the size of the directories themselves.
+    Size,
+    /// The variant to show the number of entries of the directories, without recursing into them.
+    Count,
+}
+
+impl Configurable<Self> for DirSize {
+    /// Get a potential `DirSize` variant from [ArgMatches].
+    ///
+    /// If the "dirsize" argument is passed, the variant corresponding to its parameter is returned
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("dirsize") > 0 {
+            match matches.value_of("dirsize") {
+                Some("size") => Some(Self::Size),
+                Some("count") => Some(Self::Count),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `DirSize` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by "dirsize"
+    /// and it is either "size" or "count", this returns the corresponding `DirSize` variant in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["dirsize"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "size" => Some(Self::Size),
+                    "count" => Some(Self::Count),
+                    _ => {
+                        config.print_invalid_value_warning("dirsize", value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("dirsize", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `DirSize` is [DirSize::Size].
+impl Default for DirSize {
+    fn default() -> Self {
+        Self::Size
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DirSize;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(DirSize::Size, DirSize::default());
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, DirSize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_count() {
+        let argv = vec!["lsd", "--dirsize", "count"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(DirSize::Count), DirSize::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, DirSize::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_count() {
+        let yaml_string = "dirsize: count";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(DirSize::Count),
+            DirSize::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "dirsize: total";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, DirSize::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,76 @@
+//! This module defines the [Dired] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+#[cfg(doc)]
+use yaml_rust::Yaml;
+
+/// The flag showing whether to print the byte offsets of the names after the listing, for the
+/// dired mode of Emacs.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Dired(pub bool);
+
+impl Configurable<Self> for Dired {
+    /// Get a potential `Dired` value from [ArgMatches].
+    ///
+    /// If the "dired" argument is passed, this returns a `Dired` with value `true` in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("dired") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Dired` can not be configured by a [Config], as it is only meant for Emacs.
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Dired;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Dired::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--dired"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Dired(true)), Dired::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_short() {
+        let argv = vec!["lsd", "-D"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Dired(true)), Dired::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_ignored() {
+        let yaml_string = "dired: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Dired::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -37,7 +37,8 @@ impl Configurable<Self> for Display {
     /// Get a potential `Display` variant from [ArgMatches].
     ///
     /// If any of the "all", "almost-all" or "directory-only" arguments is passed, this returns the
-    /// corresponding `Display` variant in a [Some]. If neither of them is passed, this returns
+    /// corresponding `Display` variant in a [Some]. The "all" and "almost-all" arguments override
+    /// each other, so the last one passed wins. If neither of them is passed, this returns
     /// [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
         if matches.is_present("all") {
@@ -113,6 +114,38 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_last_of_all_and_almost_all_wins() {
+        for (argv, expected) in &[
+            (vec!["lsd", "-a", "-A"], Display::AlmostAll),
+            (vec!["lsd", "-A", "-a"], Display::All),
+            (vec!["lsd", "-aA"], Display::AlmostAll),
+            (vec!["lsd", "-Aa"], Display::All),
+            (vec!["lsd", "--all", "--almost-all", "--all"], Display::All),
+            (vec!["lsd", "-A", "-a", "-A"], Display::AlmostAll),
+        ] {
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(
+                Some(*expected),
+                Display::from_arg_matches(&matches),
+                "{:?}",
+                argv
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_arg_matches_directory() {
+        for flag in &["-d", "--directory"] {
+            let argv = vec!["lsd", flag];
+            let matches = app::build().get_matches_from_safe(argv).unwrap();
+            assert_eq!(
+                Some(Display::DirectoryItself),
+                Display::from_arg_matches(&matches)
+            );
+        }
+    }
+
     #[test]
     fn test_from_arg_matches_directory_only() {
         let argv = vec!["lsd", "--directory-only"];
@@ -0,0 +1,69 @@
+//! This module defines the [Extended] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+#[cfg(doc)]
+use yaml_rust::Yaml;
+
+/// The flag showing whether to list the names of the extended attributes beneath each entry.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Extended(pub bool);
+
+impl Configurable<Self> for Extended {
+    /// Get a potential `Extended` value from [ArgMatches].
+    ///
+    /// If the "extended" argument is passed, this returns an `Extended` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("extended") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Extended` can not be configured by a [Config], like the "inode" argument it goes with a
+    /// block which is only added on the command line.
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Extended;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Extended::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "-@"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Extended(true)), Extended::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_ignored() {
+        let yaml_string = "extended: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Extended::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,188 @@
+//! This module defines the [FileTypes] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+use crate::meta::FileType;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The types of the files to list. When it is empty, files of any type are listed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FileTypes(pub Vec<FileTypeFlag>);
+
+impl FileTypes {
+    /// Whether a file of the given type should be listed.
+    pub fn matches(&self, file_type: FileType) -> bool {
+        self.0.is_empty() || self.0.iter().any(|flag| flag.matches(file_type))
+    }
+}
+
+impl Configurable<Self> for FileTypes {
+    /// Get a potential `FileTypes` from [ArgMatches].
+    ///
+    /// If the "type" argument is passed, this returns a `FileTypes` containing all of its
+    /// values in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("type") > 0 {
+            matches.values_of("type").map(|values| {
+                Self(
+                    values
+                        .map(|value| match FileTypeFlag::from_str(value) {
+                            Some(flag) => flag,
+                            None => panic!("This should not be reachable!"),
+                        })
+                        .collect(),
+                )
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `FileTypes` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Array](Yaml::Array) value pointed to by "type", each of
+    /// its valid [String](Yaml::String) values is returned in a `FileTypes` in a [Some].
+    /// Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["type"] {
+                Yaml::BadValue => None,
+                Yaml::Array(values) => {
+                    let mut flags = vec![];
+                    for value in values {
+                        match value {
+                            Yaml::String(value) => match FileTypeFlag::from_str(value) {
+                                Some(flag) => flags.push(flag),
+                                None => config.print_invalid_value_warning("type", value),
+                            },
+                            _ => config.print_warning("The type config values have to be strings."),
+                        }
+                    }
+                    if flags.is_empty() {
+                        None
+                    } else {
+                        Some(Self(flags))
+                    }
+                }
+                _ => {
+                    config.print_wrong_type_warning("type", "array");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// A type of file to list.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum FileTypeFlag {
+    File,
+    Directory,
+    SymLink,
+    Executable,
+    Pipe,
+    Socket,
+}
+
+impl FileTypeFlag {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "file" => Some(Self::File),
+            "dir" => Some(Self::Directory),
+            "symlink" => Some(Self::SymLink),
+            "executable" => Some(Self::Executable),
+            "pipe" => Some(Self::Pipe),
+            "socket" => Some(Self::Socket),
+            _ => None,
+        }
+    }
+
+    fn matches(self, file_type: FileType) -> bool {
+        match self {
+            Self::File => matches!(file_type, FileType::File { .. }),
+            Self::Directory => matches!(file_type, FileType::Directory { .. }),
+            Self::SymLink => matches!(file_type, FileType::SymLink { .. }),
+            Self::Executable => matches!(file_type, FileType::File { exec: true, .. }),
+            Self::Pipe => file_type == FileType::Pipe,
+            Self::Socket => file_type == FileType::Socket,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FileTypeFlag, FileTypes};
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+    use crate::meta::FileType;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, FileTypes::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_multiple() {
+        let argv = vec!["lsd", "--type", "dir", "--type", "symlink"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(FileTypes(vec![
+                FileTypeFlag::Directory,
+                FileTypeFlag::SymLink
+            ])),
+            FileTypes::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--type", "device"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, FileTypes::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_array() {
+        let yaml_string = "type:\n  - file\n  - pipe\n  - device";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(FileTypes(vec![FileTypeFlag::File, FileTypeFlag::Pipe])),
+            FileTypes::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_matches() {
+        let file = FileType::File {
+            uid: false,
+            exec: false,
+        };
+        let executable = FileType::File {
+            uid: false,
+            exec: true,
+        };
+        let dir = FileType::Directory { uid: false };
+
+        assert!(FileTypes::default().matches(file));
+        assert!(FileTypes(vec![FileTypeFlag::File]).matches(executable));
+        assert!(!FileTypes(vec![FileTypeFlag::Executable]).matches(file));
+        assert!(FileTypes(vec![FileTypeFlag::Executable, FileTypeFlag::Directory]).matches(dir));
+        assert!(!FileTypes(vec![FileTypeFlag::SymLink]).matches(dir));
+    }
+}
@@ -0,0 +1,71 @@
+//! This module defines the [Hide] globs. To set it up from [ArgMatches], use the
+//! [configure_from](Hide::configure_from) method.
+
+use super::IgnoreGlobs;
+
+use clap::{ArgMatches, Error};
+use globset::{GlobSet, GlobSetBuilder};
+
+/// The globs of the names of the entries to hide, like the dot files, unless all the entries are
+/// displayed. Unlike the [IgnoreGlobs], they are only given on the command line, as they are
+/// meant to be overridden by the "all" and "almost-all" arguments of a single call.
+#[derive(Clone, Debug)]
+pub struct Hide(pub GlobSet);
+
+impl Hide {
+    /// Get the `Hide` globs from the "hide" arguments, or the [Default] value when there are none.
+    ///
+    /// # Errors
+    ///
+    /// If one of the globs passed as arguments is not a valid pattern.
+    pub fn configure_from(matches: &ArgMatches) -> Result<Self, Error> {
+        let mut glob_set_builder = GlobSetBuilder::new();
+
+        if let Some(values) = matches.values_of("hide") {
+            for value in values {
+                glob_set_builder.add(IgnoreGlobs::create_glob(value)?);
+            }
+        }
+
+        Ok(Self(IgnoreGlobs::create_glob_set(&glob_set_builder)?))
+    }
+}
+
+/// The default value of `Hide` is the empty [GlobSet], returned by [GlobSet::empty()].
+impl Default for Hide {
+    fn default() -> Self {
+        Self(GlobSet::empty())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Hide;
+
+    use crate::app;
+
+    #[test]
+    fn test_configure_from_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let hide = Hide::configure_from(&matches).unwrap();
+        assert!(hide.0.is_empty());
+    }
+
+    #[test]
+    fn test_configure_from_several() {
+        let argv = vec!["lsd", "--hide", "*~", "--hide=*.o"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let hide = Hide::configure_from(&matches).unwrap();
+        assert!(hide.0.is_match("notes.txt~"));
+        assert!(hide.0.is_match("main.o"));
+        assert!(!hide.0.is_match("main.c"));
+    }
+
+    #[test]
+    fn test_configure_from_invalid() {
+        let argv = vec!["lsd", "--hide", "a{b"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(Hide::configure_from(&matches).is_err());
+    }
+}
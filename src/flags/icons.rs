@@ -1,31 +1,123 @@
 //! This module defines the [IconOption]. To set it up from [ArgMatches], a [Yaml] and its
 //! [Default] value, use its [configure_from](Configurable::configure_from) method.
 
-use super::Configurable;
+use super::{classic_overrides, Configurable};
 
 use crate::config_file::Config;
 
 use clap::ArgMatches;
+use std::collections::HashMap;
 use yaml_rust::Yaml;
 
 /// A collection of flags on how to use icons.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Icons {
     /// When to use icons.
     pub when: IconOption,
     /// Which icon theme to use.
     pub theme: IconTheme,
+    /// The icons set in the configuration file, used instead of the built-in ones.
+    pub overrides: IconOverrides,
+    /// How many spaces separate the icons from the names.
+    pub spacing: IconSpacing,
 }
 
 impl Icons {
     /// Get an `Icons` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [IconOption] and [IconTheme] are configured with their respective [Configurable]
-    /// implementation.
+    /// The [IconOption], [IconTheme] and [IconSpacing] are configured with their respective
+    /// [Configurable] implementation. The [IconOverrides] can only be set in a [Config].
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let when = IconOption::configure_from(matches, config);
         let theme = IconTheme::configure_from(matches, config);
-        Self { when, theme }
+        let overrides = IconOverrides::from_config(config);
+        let spacing = IconSpacing::configure_from(matches, config);
+        Self {
+            when,
+            theme,
+            overrides,
+            spacing,
+        }
+    }
+}
+
+/// The file type keywords an icon can be set for, as named by
+/// [FileType::name](crate::meta::FileType::name).
+const FILE_TYPE_KEYWORDS: [&str; 8] = [
+    "block-device",
+    "char-device",
+    "directory",
+    "file",
+    "symlink",
+    "pipe",
+    "socket",
+    "special",
+];
+
+/// The icons set by the user, mapping lower-case file names, extensions and file type keywords to
+/// glyphs.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct IconOverrides {
+    pub by_name: HashMap<String, String>,
+    pub by_extension: HashMap<String, String>,
+    pub by_type: HashMap<String, String>,
+}
+
+impl IconOverrides {
+    /// Get the `IconOverrides` from a [Config].
+    ///
+    /// The Config's [Yaml] can contain [Hash](Yaml::Hash) values pointed to by "icons" ->
+    /// "by-name", "icons" -> "by-extension" and "icons" -> "by-type", mapping their keys to a
+    /// [String](Yaml::String) glyph. A malformed entry prints a warning and is skipped.
+    fn from_config(config: &Config) -> Self {
+        match &config.yaml {
+            Some(yaml) => Self {
+                by_name: Self::from_yaml_hash(&yaml["icons"]["by-name"], "by-name", config),
+                by_extension: Self::from_yaml_hash(
+                    &yaml["icons"]["by-extension"],
+                    "by-extension",
+                    config,
+                )
+                .into_iter()
+                .map(|(extension, icon)| (extension.trim_start_matches('.').to_string(), icon))
+                .collect(),
+                by_type: Self::from_yaml_hash(&yaml["icons"]["by-type"], "by-type", config)
+                    .into_iter()
+                    .filter(|(keyword, _)| {
+                        let known = FILE_TYPE_KEYWORDS.contains(&keyword.as_str());
+                        if !known {
+                            config.print_invalid_value_warning("icons->by-type", keyword);
+                        }
+                        known
+                    })
+                    .collect(),
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Get the lower-cased mapping from a [Yaml] hash. The [Config] is used to log warnings about
+    /// wrong values in a Yaml.
+    fn from_yaml_hash(yaml: &Yaml, name: &str, config: &Config) -> HashMap<String, String> {
+        let mut icons = HashMap::new();
+        match yaml {
+            Yaml::BadValue => {}
+            Yaml::Hash(hash) => {
+                for (key, value) in hash {
+                    match (key, value) {
+                        (Yaml::String(key), Yaml::String(value)) => {
+                            icons.insert(key.to_lowercase(), value.clone());
+                        }
+                        _ => config.print_warning(&format!(
+                            "The icons->{} config values have to be strings.",
+                            name
+                        )),
+                    }
+                }
+            }
+            _ => config.print_wrong_type_warning(&format!("icons->{}", name), "hash"),
+        }
+        icons
     }
 }
 
@@ -56,11 +148,12 @@ impl IconOption {
 impl Configurable<Self> for IconOption {
     /// Get a potential `IconOption` variant from [ArgMatches].
     ///
-    /// If the "classic" argument is passed, then this returns the [IconOption::Never] variant in
-    /// a [Some]. Otherwise if the argument is passed, this returns the variant corresponding to
-    /// its parameter in a [Some]. Otherwise this returns [None].
+    /// If the "classic" argument is passed and the argument is not passed after it, then this
+    /// returns the [IconOption::Never] variant in a [Some]. Otherwise if the argument is passed,
+    /// this returns the variant corresponding to its parameter in a [Some]. Otherwise this returns
+    /// [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.is_present("classic") {
+        if classic_overrides(matches, "icon") {
             Some(Self::Never)
         } else if matches.occurrences_of("icon") > 0 {
             match matches.value_of("icon") {
@@ -175,6 +268,58 @@ impl Default for IconTheme {
     }
 }
 
+/// The flag showing how many spaces separate the icons from the names, either 1 or 2.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct IconSpacing(pub usize);
+
+impl Configurable<Self> for IconSpacing {
+    /// Get a potential `IconSpacing` value from [ArgMatches].
+    ///
+    /// If the "icon-spacing" argument is passed, this returns its parsed value in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("icon-spacing") > 0 {
+            match matches.value_of("icon-spacing").map(str::parse) {
+                Some(Ok(spacing)) => Some(Self(spacing)),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `IconSpacing` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Integer](Yaml::Integer) value pointed to by "icons" ->
+    /// "spacing" and it is either 1 or 2, this returns it in a [Some]. Otherwise this returns
+    /// [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["icons"]["spacing"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) if *value == 1 || *value == 2 => Some(Self(*value as usize)),
+                Yaml::Integer(value) => {
+                    config.print_invalid_value_warning("icons->spacing", &value.to_string());
+                    None
+                }
+                _ => {
+                    config.print_wrong_type_warning("icons->spacing", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `IconSpacing` is a single space.
+impl Default for IconSpacing {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
 #[cfg(test)]
 mod test_icon_option {
     use super::IconOption;
@@ -232,6 +377,16 @@ mod test_icon_option {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_overridden_classic_mode() {
+        let argv = vec!["lsd", "--classic", "--icon", "always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(IconOption::Always),
+            IconOption::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, IconOption::from_config(&Config::with_none()));
@@ -354,3 +509,130 @@ mod test_icon_theme {
         );
     }
 }
+
+#[cfg(test)]
+mod test_icon_overrides {
+    use super::IconOverrides;
+
+    use crate::config_file::Config;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(
+            IconOverrides::default(),
+            IconOverrides::from_config(&Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        let yaml_string = "icons:
+  by-name:
+    Makefile: m
+  by-extension:
+    rs: r
+    .TOML: t
+  by-type:
+    directory: d";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let overrides = IconOverrides::from_config(&Config::with_yaml(yaml));
+
+        assert_eq!(
+            Some("m"),
+            overrides.by_name.get("makefile").map(String::as_str)
+        );
+        assert_eq!(
+            Some("r"),
+            overrides.by_extension.get("rs").map(String::as_str)
+        );
+        assert_eq!(
+            Some("t"),
+            overrides.by_extension.get("toml").map(String::as_str)
+        );
+        assert_eq!(
+            Some("d"),
+            overrides.by_type.get("directory").map(String::as_str)
+        );
+    }
+
+    #[test]
+    fn test_from_config_skips_malformed_entries() {
+        let yaml_string = "icons:
+  by-extension:
+    rs: r
+    md:
+      - a
+      - b
+  by-type:
+    folder: f
+  by-name: n";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let overrides = IconOverrides::from_config(&Config::with_yaml(yaml));
+
+        assert_eq!(1, overrides.by_extension.len());
+        assert_eq!(
+            Some("r"),
+            overrides.by_extension.get("rs").map(String::as_str)
+        );
+        assert!(overrides.by_type.is_empty());
+        assert!(overrides.by_name.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod test_icon_spacing {
+    use super::IconSpacing;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(IconSpacing(1), IconSpacing::default());
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, IconSpacing::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_two() {
+        let argv = vec!["lsd", "--icon-spacing", "2"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(IconSpacing(2)),
+            IconSpacing::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--icon-spacing", "3"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_two() {
+        let yaml_string = "icons:\n  spacing: 2";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(IconSpacing(2)),
+            IconSpacing::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "icons:\n  spacing: 0";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, IconSpacing::from_config(&Config::with_yaml(yaml)));
+    }
+}
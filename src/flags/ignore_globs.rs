@@ -12,11 +12,10 @@ use yaml_rust::Yaml;
 pub struct IgnoreGlobs(pub GlobSet);
 
 impl IgnoreGlobs {
-    /// Returns a value from either [ArgMatches], a [Config] or a [Default] value. The first value
-    /// that is not [None] is used. The order of precedence for the value used is:
-    /// - [from_arg_matches](IgnoreGlobs::from_arg_matches)
+    /// Returns the union of the globs from [ArgMatches] and a [Config], or the [Default] value
+    /// when there are none. The globs are collected by:
     /// - [from_config](IgnoreGlobs::from_config)
-    /// - [Default::default]
+    /// - [from_arg_matches](IgnoreGlobs::from_arg_matches)
     ///
     /// # Note
     ///
@@ -25,82 +24,68 @@ impl IgnoreGlobs {
     ///
     /// # Errors
     ///
-    /// If either of the [Glob::new] or [GlobSetBuilder.build] methods return an [Err].
+    /// If either of the [Glob::new] or [GlobSetBuilder.build] methods return an [Err] for the
+    /// globs passed as arguments.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Result<Self, Error> {
-        let mut result: Result<Self, Error> = Ok(Default::default());
+        let mut glob_set_builder = GlobSetBuilder::new();
 
         if config.has_yaml() {
-            if let Some(value) = Self::from_config(config) {
-                match value {
-                    Ok(glob_set) => result = Ok(Self(glob_set)),
-                    Err(err) => result = Err(err),
+            if let Some(globs) = Self::from_config(config) {
+                for glob in globs {
+                    glob_set_builder.add(glob);
                 }
             }
         }
 
-        if let Some(value) = Self::from_arg_matches(matches) {
-            match value {
-                Ok(glob_set) => result = Ok(Self(glob_set)),
-                Err(err) => result = Err(err),
+        if let Some(globs) = Self::from_arg_matches(matches) {
+            for glob in globs? {
+                glob_set_builder.add(glob);
             }
         }
 
-        result
+        Ok(Self(Self::create_glob_set(&glob_set_builder)?))
     }
 
-    /// Get a potential [GlobSet] from [ArgMatches].
+    /// Get the potential [Glob]s from [ArgMatches].
     ///
     /// If the "ignore-glob" argument has been passed, this returns a [Result] in a [Some] with
-    /// either the built [GlobSet] or an [Error], if any error was encountered while creating the
-    /// [GlobSet]. If the argument has not been passed, this returns [None].
-    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<GlobSet, Error>> {
+    /// either the created [Glob]s or an [Error], if any error was encountered while creating one
+    /// of them. If the argument has not been passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Result<Vec<Glob>, Error>> {
         if matches.occurrences_of("ignore-glob") > 0 {
-            if let Some(values) = matches.values_of("ignore-glob") {
-                let mut glob_set_builder = GlobSetBuilder::new();
-                for value in values {
-                    match Self::create_glob(value) {
-                        Ok(glob) => {
-                            glob_set_builder.add(glob);
-                        }
-                        Err(err) => return Some(Err(err)),
-                    }
-                }
-                Some(Self::create_glob_set(&glob_set_builder))
-            } else {
-                None
-            }
+            matches
+                .values_of("ignore-glob")
+                .map(|values| values.map(Self::create_glob).collect())
         } else {
             None
         }
     }
 
-    /// Get a potential [GlobSet] from a [Config].
+    /// Get the potential [Glob]s from a [Config].
     ///
     /// If the Config's [Yaml] contains an [Array](Yaml::Array) value pointed to by "ignore-globs",
-    /// each of its [String](Yaml::String) values is used to build the [GlobSet]. If the building
-    /// succeeds, the [GlobSet] is returned in the [Result] in a [Some]. If any error is
-    /// encountered while building, an [Error] is returned in the Result instead. If the Yaml does
-    /// not contain such a key, this returns [None].
-    fn from_config(config: &Config) -> Option<Result<GlobSet, Error>> {
+    /// a [Glob] is created from each of its [String](Yaml::String) values and they are returned
+    /// in a [Some]. The values which are not strings or not valid patterns are skipped with a
+    /// warning. If the Yaml does not contain such a key, this returns [None].
+    fn from_config(config: &Config) -> Option<Vec<Glob>> {
         if let Some(yaml) = &config.yaml {
             match &yaml["ignore-globs"] {
                 Yaml::BadValue => None,
                 Yaml::Array(values) => {
-                    let mut glob_set_builder = GlobSetBuilder::new();
+                    let mut globs = vec![];
                     for yaml_str in values.iter() {
-                        if let Yaml::String(value) = yaml_str {
-                            match Self::create_glob(value) {
-                                Ok(glob) => {
-                                    glob_set_builder.add(glob);
-                                }
-                                Err(err) => return Some(Err(err)),
-                            }
+                        match yaml_str {
+                            Yaml::String(value) => match Self::create_glob(value) {
+                                Ok(glob) => globs.push(glob),
+                                Err(_) => config.print_invalid_value_warning("ignore-globs", value),
+                            },
+                            _ => config.print_wrong_type_warning("ignore-globs", "string list"),
                         }
                     }
-                    Some(Self::create_glob_set(&glob_set_builder))
+                    Some(globs)
                 }
                 _ => {
-                    config.print_wrong_type_warning("ignore-globs", "string");
+                    config.print_wrong_type_warning("ignore-globs", "string list");
                     None
                 }
             }
@@ -112,7 +97,7 @@ impl IgnoreGlobs {
     /// Create a [Glob] from a provided pattern.
     ///
     /// This method is mainly a helper to wrap the handling of potential errors.
-    fn create_glob(pattern: &str) -> Result<Glob, Error> {
+    pub fn create_glob(pattern: &str) -> Result<Glob, Error> {
         match Glob::new(pattern) {
             Ok(glob) => Ok(glob),
             Err(err) => Err(Error::with_description(
@@ -125,7 +110,7 @@ impl IgnoreGlobs {
     /// Create a [GlobSet] from a provided [GlobSetBuilder].
     ///
     /// This method is mainly a helper to wrap the handling of potential errors.
-    fn create_glob_set(builder: &GlobSetBuilder) -> Result<GlobSet, Error> {
+    pub fn create_glob_set(builder: &GlobSetBuilder) -> Result<GlobSet, Error> {
         match builder.build() {
             Ok(glob_set) => Ok(glob_set),
             Err(err) => Err(Error::with_description(
@@ -176,6 +161,36 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--ignore-glob", "a{b"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(IgnoreGlobs::configure_from(&matches, &Config::with_none()).is_err());
+    }
+
+    #[test]
+    fn test_from_config_patterns() {
+        let yaml_string = "ignore-globs:\n  - '*.log'\n  - a{b\n  - 42\n  - target";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let globs = IgnoreGlobs::from_config(&Config::with_yaml(yaml)).unwrap();
+        assert_eq!(2, globs.len());
+    }
+
+    #[test]
+    fn test_configure_from_union() {
+        let argv = vec!["lsd", "--ignore-glob", "*.tmp"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml_string = "ignore-globs:\n  - '*.log'\n  - a{b\n  - target";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let glob_set = IgnoreGlobs::configure_from(&matches, &Config::with_yaml(yaml))
+            .unwrap()
+            .0;
+        assert!(glob_set.is_match("debug.log"));
+        assert!(glob_set.is_match("target"));
+        assert!(glob_set.is_match("file.tmp"));
+        assert!(!glob_set.is_match("file.rs"));
+    }
+
     #[test]
     fn test_from_config_empty() {
         let yaml_string = "---";
@@ -1,11 +1,12 @@
-//! This module defines the [Indicators] flag. To set it up from [ArgMatches], a [Yaml] and its
-//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+//! This module defines the [Indicators] flag and the [IndicatorChars]. To set them up from
+//! [ArgMatches], a [Yaml] and their [Default] value, use their `configure_from` methods.
 
 use super::Configurable;
 
 use crate::config_file::Config;
 
 use clap::ArgMatches;
+use std::collections::HashMap;
 use yaml_rust::Yaml;
 
 /// The flag showing whether to print file type indicators.
@@ -15,10 +16,10 @@ pub struct Indicators(pub bool);
 impl Configurable<Self> for Indicators {
     /// Get a potential `Indicators` value from [ArgMatches].
     ///
-    /// If the "indicators" argument is passed, this returns an `Indicators` with value `true` in a
-    /// [Some]. Otherwise this returns [None].
+    /// If the "indicators" or "indicator-types" argument is passed, this returns an `Indicators`
+    /// with value `true` in a [Some]. Otherwise this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.is_present("indicators") {
+        if matches.is_present("indicators") || matches.is_present("indicator-types") {
             Some(Self(true))
         } else {
             None
@@ -46,9 +47,140 @@ impl Configurable<Self> for Indicators {
     }
 }
 
+/// The kinds of files which have an indicator.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub enum IndicatorKind {
+    Dir,
+    Exec,
+    SymLink,
+    Pipe,
+    Socket,
+}
+
+impl IndicatorKind {
+    /// All the kinds, with their names in the arguments and the configuration file.
+    const ALL: [(&'static str, Self); 5] = [
+        ("dir", Self::Dir),
+        ("exec", Self::Exec),
+        ("symlink", Self::SymLink),
+        ("pipe", Self::Pipe),
+        ("socket", Self::Socket),
+    ];
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .find(|(kind_name, _)| *kind_name == name)
+            .map(|(_, kind)| *kind)
+    }
+
+    /// The indicator appended by `ls -F`.
+    fn default_char(self) -> &'static str {
+        match self {
+            Self::Dir => "/",
+            Self::Exec => "*",
+            Self::SymLink => "@",
+            Self::Pipe => "|",
+            Self::Socket => "=",
+        }
+    }
+}
+
+/// The indicator characters of each kind of file. The kinds without one have no indicator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IndicatorChars(pub HashMap<IndicatorKind, String>);
+
+impl IndicatorChars {
+    /// Get the `IndicatorChars` from a [Config] or their [Default] value, keeping only the kinds
+    /// passed with the "indicator-types" argument if it is passed.
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
+        let mut result = Self::from_config(config).unwrap_or_default();
+
+        if let Some(kinds) = Self::kinds_from_arg_matches(matches) {
+            result.0.retain(|kind, _| kinds.contains(kind));
+        }
+
+        result
+    }
+
+    /// Get the kinds passed with the "indicator-types" argument in a [Some], or [None] when it
+    /// is not passed.
+    fn kinds_from_arg_matches(matches: &ArgMatches) -> Option<Vec<IndicatorKind>> {
+        matches.values_of("indicator-types").map(|values| {
+            values
+                .map(|value| match IndicatorKind::from_name(value) {
+                    Some(kind) => kind,
+                    None => panic!("This should not be reachable!"),
+                })
+                .collect()
+        })
+    }
+
+    /// Get potential `IndicatorChars` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Hash](Yaml::Hash) value pointed to by
+    /// "indicator-chars", each of its [String](Yaml::String) values pointed to by "dir", "exec",
+    /// "symlink", "pipe" or "socket" replaces the default indicator of the kind, and the result
+    /// is returned in a [Some]. An empty string removes the indicator of the kind. Otherwise this
+    /// returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        let yaml = config.yaml.as_ref()?;
+        match &yaml["indicator-chars"] {
+            Yaml::BadValue => None,
+            Yaml::Hash(hash) => {
+                let mut result = Self::default();
+                for (key, value) in hash {
+                    let kind = match key.as_str().and_then(IndicatorKind::from_name) {
+                        Some(kind) => kind,
+                        None => {
+                            config.print_invalid_value_warning(
+                                "indicator-chars",
+                                key.as_str().unwrap_or_default(),
+                            );
+                            continue;
+                        }
+                    };
+                    match value {
+                        Yaml::String(value) if value.is_empty() => {
+                            result.0.remove(&kind);
+                        }
+                        Yaml::String(value) => {
+                            result.0.insert(kind, value.clone());
+                        }
+                        _ => config
+                            .print_warning("The indicator-chars config values have to be strings."),
+                    }
+                }
+                Some(result)
+            }
+            _ => {
+                config.print_wrong_type_warning("indicator-chars", "hash");
+                None
+            }
+        }
+    }
+
+    /// The indicator of the kind, which is empty when the kind has none.
+    pub fn get(&self, kind: IndicatorKind) -> &str {
+        self.0.get(&kind).map_or("", String::as_str)
+    }
+}
+
+/// The default `IndicatorChars` are the ones of `ls -F`.
+impl Default for IndicatorChars {
+    fn default() -> Self {
+        Self(
+            IndicatorKind::ALL
+                .iter()
+                .map(|(_, kind)| (*kind, kind.default_char().to_string()))
+                .collect(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::Indicators;
+    use super::{IndicatorChars, IndicatorKind, Indicators};
 
     use crate::app;
     use crate::config_file::Config;
@@ -104,4 +236,57 @@ mod test {
             Indicators::from_config(&Config::with_yaml(yaml))
         );
     }
+
+    #[test]
+    fn test_from_arg_matches_indicator_types() {
+        let argv = vec!["lsd", "--indicator-types", "exec"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(Indicators(true)),
+            Indicators::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_chars_default() {
+        let chars = IndicatorChars::default();
+        assert_eq!("/", chars.get(IndicatorKind::Dir));
+        assert_eq!("*", chars.get(IndicatorKind::Exec));
+        assert_eq!("@", chars.get(IndicatorKind::SymLink));
+        assert_eq!("|", chars.get(IndicatorKind::Pipe));
+        assert_eq!("=", chars.get(IndicatorKind::Socket));
+    }
+
+    #[test]
+    fn test_chars_configure_from_indicator_types() {
+        let argv = vec!["lsd", "--indicator-types", "exec"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let chars = IndicatorChars::configure_from(&matches, &Config::with_none());
+        assert_eq!("*", chars.get(IndicatorKind::Exec));
+        assert_eq!("", chars.get(IndicatorKind::Dir));
+        assert_eq!("", chars.get(IndicatorKind::SymLink));
+        assert_eq!(1, chars.0.len());
+    }
+
+    #[test]
+    fn test_chars_from_config() {
+        let yaml_string = "indicator-chars:\n  dir: ''\n  exec: '!'\n  door: '>'";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let chars = IndicatorChars::from_config(&Config::with_yaml(yaml)).unwrap();
+        assert_eq!("", chars.get(IndicatorKind::Dir));
+        assert_eq!("!", chars.get(IndicatorKind::Exec));
+        assert_eq!("@", chars.get(IndicatorKind::SymLink));
+    }
+
+    #[test]
+    fn test_chars_configure_from_config_and_indicator_types() {
+        let argv = vec!["lsd", "--indicator-types", "exec,dir"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let yaml_string = "indicator-chars:\n  exec: '!'";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let chars = IndicatorChars::configure_from(&matches, &Config::with_yaml(yaml));
+        assert_eq!("!", chars.get(IndicatorKind::Exec));
+        assert_eq!("/", chars.get(IndicatorKind::Dir));
+        assert_eq!("", chars.get(IndicatorKind::Pipe));
+    }
 }
@@ -19,7 +19,7 @@ pub enum Layout {
 impl Configurable<Layout> for Layout {
     /// Get a potential `Layout` variant from [ArgMatches].
     ///
-    /// If any of the "tree", "long" or "oneline" arguments is passed, this returns the
+    /// If any of the "tree", "long", "full-time" or "oneline" arguments is passed, this returns the
     /// corresponding `Layout` variant in a [Some]. Otherwise if the number of passed "blocks"
     /// arguments is greater than 1, this also returns the [OneLine](Layout::OneLine) variant.
     /// Finally if neither of them is passed, this returns [None].
@@ -27,8 +27,13 @@ impl Configurable<Layout> for Layout {
         if matches.is_present("tree") {
             Some(Self::Tree)
         } else if matches.is_present("long")
+            || matches.is_present("full-time")
             || matches.is_present("oneline")
             || matches.is_present("inode")
+            || matches.is_present("git")
+            || matches.is_present("extended")
+            || matches.is_present("context")
+            || matches.is_present("dired")
             || matches!(matches.values_of("blocks"), Some(values) if values.len() > 1)
         // TODO: handle this differently
         {
@@ -112,6 +117,13 @@ mod test {
         assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
     }
 
+    #[test]
+    fn test_from_arg_matches_oneline_through_full_time() {
+        let argv = vec!["lsd", "--full-time"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
+    }
+
     #[test]
     fn test_from_arg_matches_oneline_through_blocks() {
         let argv = vec!["lsd", "--blocks", "permission,name"];
@@ -119,6 +131,34 @@ mod test {
         assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
     }
 
+    #[test]
+    fn test_from_arg_matches_oneline_through_context() {
+        let argv = vec!["lsd", "-Z"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_oneline_through_git() {
+        let argv = vec!["lsd", "--git"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_oneline_through_extended() {
+        let argv = vec!["lsd", "-@"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_oneline_through_dired() {
+        let argv = vec!["lsd", "--dired"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Layout::OneLine), Layout::from_arg_matches(&matches));
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, Layout::from_config(&Config::with_none()));
@@ -0,0 +1,134 @@
+//! This module defines the [Padding] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app;
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing the number of spaces between the columns of the output. When it is not set,
+/// the columns of the grid are separated by 2 spaces and the blocks of a line by 1 space.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Padding(pub Option<usize>);
+
+impl Padding {
+    /// The number of spaces between the columns of the grid layout.
+    pub fn grid(self) -> usize {
+        self.0.unwrap_or(2)
+    }
+
+    /// The number of spaces between the blocks of a line, in the other layouts.
+    pub fn blocks(self) -> usize {
+        self.0.unwrap_or(1)
+    }
+}
+
+impl Configurable<Self> for Padding {
+    /// Get a potential `Padding` from [ArgMatches].
+    ///
+    /// If the "padding" argument is passed, its parsed value is returned in a [Some]. Otherwise
+    /// this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if let Some(value) = matches.value_of("padding") {
+            match app::parse_padding(value) {
+                Some(padding) => Some(Self(Some(padding))),
+                None => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Padding` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Integer](Yaml::Integer) value pointed to by
+    /// "padding", which is between 0 and [MAX_PADDING](app::MAX_PADDING), this returns it in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["padding"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) => match app::parse_padding(&value.to_string()) {
+                    Some(padding) => Some(Self(Some(padding))),
+                    None => {
+                        config.print_invalid_value_warning("padding", &value.to_string());
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("padding", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Padding;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(2, Padding::default().grid());
+        assert_eq!(1, Padding::default().blocks());
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Padding::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_padding() {
+        let argv = vec!["lsd", "--padding", "4"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let padding = Padding::from_arg_matches(&matches).unwrap();
+        assert_eq!(4, padding.grid());
+        assert_eq!(4, padding.blocks());
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--padding", "-1"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+
+        let argv = vec!["lsd", "--padding", "1000"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Padding::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_padding() {
+        let yaml_string = "padding: 0";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(Padding(Some(0))),
+            Padding::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "padding: 100";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Padding::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,172 @@
+//! This module defines the [PermissionFlag]. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::{classic_overrides, Configurable};
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing how to display the permissions.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum PermissionFlag {
+    /// The variant to show the permissions in a symbolic `rwxr-xr-x` style.
+    Rwx,
+    /// The variant to show the permissions as an octal number, like `0755`.
+    Octal,
+}
+
+impl Configurable<Self> for PermissionFlag {
+    /// Get a potential `PermissionFlag` variant from [ArgMatches].
+    ///
+    /// If the "classic" argument is passed and the "permission" argument is not passed after it,
+    /// then this returns the [PermissionFlag::Rwx] variant in a [Some]. Otherwise if any of the
+    /// "rwx" or "octal" arguments is passed, the corresponding `PermissionFlag` variant is
+    /// returned in a [Some]. If neither of them is passed, this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if classic_overrides(matches, "permission") {
+            Some(Self::Rwx)
+        } else if matches.occurrences_of("permission") > 0 {
+            match matches.value_of("permission") {
+                Some("rwx") => Some(Self::Rwx),
+                Some("octal") => Some(Self::Octal),
+                _ => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `PermissionFlag` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Boolean](Yaml::Boolean) value pointed to by "classic"
+    /// and its value is `true`, then this returns the [PermissionFlag::Rwx] variant in a [Some].
+    /// Otherwise if the Yaml contains a [String](Yaml::String) value, pointed to by "permission"
+    /// and it is either "rwx" or "octal", this returns the corresponding `PermissionFlag` variant
+    /// in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            if let Yaml::Boolean(true) = &yaml["classic"] {
+                return Some(Self::Rwx);
+            }
+
+            match &yaml["permission"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match value.as_ref() {
+                    "rwx" => Some(Self::Rwx),
+                    "octal" => Some(Self::Octal),
+                    _ => {
+                        config.print_invalid_value_warning("permission", value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("permission", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `PermissionFlag` is [PermissionFlag::Rwx].
+impl Default for PermissionFlag {
+    fn default() -> Self {
+        Self::Rwx
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PermissionFlag;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(PermissionFlag::Rwx, PermissionFlag::default());
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, PermissionFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_rwx() {
+        let argv = vec!["lsd", "--permission", "rwx"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Rwx),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_octal() {
+        let argv = vec!["lsd", "--permission", "octal"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Octal),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, PermissionFlag::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_octal() {
+        let yaml_string = "permission: octal";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(PermissionFlag::Octal),
+            PermissionFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "permission: symbolic";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, PermissionFlag::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_arg_matches_classic_mode() {
+        let argv = vec!["lsd", "--permission", "octal", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Rwx),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--classic", "--permission", "octal"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(PermissionFlag::Octal),
+            PermissionFlag::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let yaml_string = "classic: true\npermission: octal";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(PermissionFlag::Rwx),
+            PermissionFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+}
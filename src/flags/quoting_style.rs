@@ -0,0 +1,159 @@
+//! This module defines the [QuotingStyle] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing how to quote file names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum QuotingStyle {
+    /// The variant to print names as they are, only escaping control characters.
+    Literal,
+    /// The variant to single-quote names containing spaces or shell special characters.
+    Shell,
+    /// The variant to single-quote every name.
+    ShellAlways,
+    /// The variant to double-quote names like a C string, escaping control characters.
+    C,
+}
+
+impl QuotingStyle {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "literal" => Some(Self::Literal),
+            "shell" => Some(Self::Shell),
+            "shell-always" => Some(Self::ShellAlways),
+            "c" => Some(Self::C),
+            _ => None,
+        }
+    }
+}
+
+impl Configurable<Self> for QuotingStyle {
+    /// Get a potential `QuotingStyle` variant from [ArgMatches].
+    ///
+    /// If the "quoting-style" argument is passed, the corresponding `QuotingStyle` variant is
+    /// returned in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("quoting-style") > 0 {
+            match matches.value_of("quoting-style").and_then(Self::from_str) {
+                Some(style) => Some(style),
+                None => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `QuotingStyle` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "quoting-style" and it is one of "literal", "shell", "shell-always" or "c", this returns
+    /// the corresponding `QuotingStyle` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["quoting-style"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match Self::from_str(value) {
+                    Some(style) => Some(style),
+                    None => {
+                        config.print_invalid_value_warning("quoting-style", value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("quoting-style", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `QuotingStyle` is [QuotingStyle::Literal].
+impl Default for QuotingStyle {
+    fn default() -> Self {
+        Self::Literal
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::QuotingStyle;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(QuotingStyle::Literal, QuotingStyle::default());
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, QuotingStyle::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_shell() {
+        let argv = vec!["lsd", "--quoting-style", "shell"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(QuotingStyle::Shell),
+            QuotingStyle::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_shell_always() {
+        let argv = vec!["lsd", "--quoting-style", "shell-always"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(QuotingStyle::ShellAlways),
+            QuotingStyle::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_c() {
+        let argv = vec!["lsd", "--quoting-style", "c"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(QuotingStyle::C),
+            QuotingStyle::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, QuotingStyle::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_shell() {
+        let yaml_string = "quoting-style: shell";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(QuotingStyle::Shell),
+            QuotingStyle::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "quoting-style: escape";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, QuotingStyle::from_config(&Config::with_yaml(yaml)));
+    }
+}
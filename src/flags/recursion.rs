@@ -121,18 +121,21 @@ impl Recursion {
     /// Get a potential "depth" value from [ArgMatches].
     ///
     /// If the "depth" argument is passed, its parameter is evaluated. If it can be parsed into a
-    /// [usize], the [Result] is returned in the [Some]. If it can not be parsed an [Error] is
+    /// [usize] greater than zero, the [Result] is returned in the [Some]. Otherwise an [Error] is
     /// returned in the [Some]. If the argument has not been passed, a [None] is returned.
     ///
+    /// The depth caps both the recursive and the tree listings, a depth of 1 only lists the
+    /// content of the given directories.
+    ///
     /// # Errors
     ///
-    /// If the parameter to the "depth" argument can not be parsed, this returns an [Error] in a
-    /// [Some].
+    /// If the parameter to the "depth" argument can not be parsed or is zero, this returns an
+    /// [Error] in a [Some].
     fn depth_from_arg_matches(matches: &ArgMatches) -> Option<Result<usize, Error>> {
         if let Some(str) = matches.value_of("depth") {
             match str.parse::<usize>() {
-                Ok(value) => return Some(Ok(value)),
-                Err(_) => {
+                Ok(value) if value > 0 => return Some(Ok(value)),
+                _ => {
                     return Some(Err(Error::with_description(
                         "The argument '--depth' requires a valid positive number.",
                         ErrorKind::ValueValidation,
@@ -299,6 +302,21 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_depth_from_arg_matches_zero() {
+        let argv = vec!["lsd", "--depth", "0"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert!(match Recursion::depth_from_arg_matches(&matches) {
+            None => false,
+            Some(result) => {
+                match result {
+                    Ok(_) => false,
+                    Err(error) => error.kind == ErrorKind::ValueValidation,
+                }
+            }
+        });
+    }
+
     #[test]
     fn test_depth_from_config_none() {
         assert_eq!(None, Recursion::depth_from_config(&Config::with_none()));
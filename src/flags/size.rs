@@ -1,7 +1,7 @@
 //! This module defines the [SizeFlag]. To set it up from [ArgMatches], a [Yaml] and its
 //! [Default] value, use its [configure_from](Configurable::configure_from) method.
 
-use super::Configurable;
+use super::{classic_overrides, Configurable};
 
 use crate::config_file::Config;
 
@@ -17,20 +17,26 @@ pub enum SizeFlag {
     Short,
     /// The variant to show file size in bytes.
     Bytes,
+    /// The variant to show file size with IEC (binary) unit prefixes and a B for bytes.
+    Binary,
 }
 
 impl Configurable<Self> for SizeFlag {
     /// Get a potential `SizeFlag` variant from [ArgMatches].
     ///
-    /// If any of the "default", "short" or "bytes" arguments is passed, the corresponding
-    /// `SizeFlag` variant is returned in a [Some]. If neither of them is passed, this returns
-    /// [None].
+    /// If the "classic" argument is passed and the "size" argument is not passed after it, then
+    /// this returns the [SizeFlag::Default] variant in a [Some]. Otherwise if any of the
+    /// "default", "short", "bytes" or "binary" arguments is passed, the corresponding `SizeFlag`
+    /// variant is returned in a [Some]. If neither of them is passed, this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.occurrences_of("size") > 0 {
+        if classic_overrides(matches, "size") {
+            Some(Self::Default)
+        } else if matches.occurrences_of("size") > 0 {
             match matches.value_of("size") {
                 Some("default") => Some(Self::Default),
                 Some("short") => Some(Self::Short),
                 Some("bytes") => Some(Self::Bytes),
+                Some("binary") => Some(Self::Binary),
                 _ => panic!("This should not be reachable!"),
             }
         } else {
@@ -40,17 +46,24 @@ impl Configurable<Self> for SizeFlag {
 
     /// Get a potential `SizeFlag` variant from a [Config].
     ///
-    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by "size" and it
-    /// is either "default", "short" or "bytes", this returns the corresponding `SizeFlag` variant
-    /// in a [Some]. Otherwise this returns [None].
+    /// If the Config's [Yaml] contains a [Boolean](Yaml::Boolean) value pointed to by "classic"
+    /// and its value is `true`, then this returns the [SizeFlag::Default] variant in a [Some].
+    /// Otherwise if the Yaml contains a [String](Yaml::String) value, pointed to by "size" and it
+    /// is either "default", "short", "bytes" or "binary", this returns the corresponding
+    /// `SizeFlag` variant in a [Some]. Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(yaml) = &config.yaml {
+            if let Yaml::Boolean(true) = &yaml["classic"] {
+                return Some(Self::Default);
+            }
+
             match &yaml["size"] {
                 Yaml::BadValue => None,
                 Yaml::String(value) => match value.as_ref() {
                     "default" => Some(Self::Default),
                     "short" => Some(Self::Short),
                     "bytes" => Some(Self::Bytes),
+                    "binary" => Some(Self::Binary),
                     _ => {
                         config.print_invalid_value_warning("size", &value);
                         None
@@ -115,6 +128,13 @@ mod test {
         assert_eq!(Some(SizeFlag::Bytes), SizeFlag::from_arg_matches(&matches));
     }
 
+    #[test]
+    fn test_from_arg_matches_binary() {
+        let args = vec!["lsd", "--size", "binary"];
+        let matches = app::build().get_matches_from_safe(args).unwrap();
+        assert_eq!(Some(SizeFlag::Binary), SizeFlag::from_arg_matches(&matches));
+    }
+
     #[test]
     fn test_from_config_none() {
         assert_eq!(None, SizeFlag::from_config(&Config::with_none()));
@@ -156,4 +176,45 @@ mod test {
             SizeFlag::from_config(&Config::with_yaml(yaml))
         );
     }
+
+    #[test]
+    fn test_from_config_binary() {
+        let yaml_string = "size: binary";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SizeFlag::Binary),
+            SizeFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "size: foo";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SizeFlag::from_config(&Config::with_yaml(yaml)));
+    }
+
+    #[test]
+    fn test_from_arg_matches_classic_mode() {
+        let argv = vec!["lsd", "--size", "bytes", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SizeFlag::Default),
+            SizeFlag::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--classic", "--size", "bytes"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(SizeFlag::Bytes), SizeFlag::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_classic_mode() {
+        let yaml_string = "classic: true\nsize: short";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SizeFlag::Default),
+            SizeFlag::from_config(&Config::with_yaml(yaml))
+        );
+    }
 }
@@ -0,0 +1,153 @@
+//! This module defines the [SizeFilter] options. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](SizeFilter::configure_from) method.
+
+use crate::app;
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The range of sizes, in bytes, of the files to list. A file is listed when it has at least the
+/// `larger_than` size and less than the `smaller_than` size. When neither is set, files of any
+/// size are listed.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct SizeFilter {
+    pub larger_than: Option<u64>,
+    pub smaller_than: Option<u64>,
+}
+
+impl SizeFilter {
+    /// Get a `SizeFilter` struct from [ArgMatches], a [Config] or the [Default] values.
+    ///
+    /// Each bound is taken from the "larger-than" and "smaller-than" arguments, or else from the
+    /// values of the same name in the [Config].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
+        let bound = |name| {
+            Self::bound_from_arg_matches(matches, name)
+                .or_else(|| Self::bound_from_config(config, name))
+        };
+
+        Self {
+            larger_than: bound("larger-than"),
+            smaller_than: bound("smaller-than"),
+        }
+    }
+
+    /// Get a potential bound from the argument called `name`, parsed like a block size.
+    fn bound_from_arg_matches(matches: &ArgMatches, name: &str) -> Option<u64> {
+        matches
+            .value_of(name)
+            .map(|value| match app::parse_block_size(value) {
+                Some(size) => size,
+                None => panic!("This should not be reachable!"),
+            })
+    }
+
+    /// Get a potential bound from the valid [String](Yaml::String) or positive
+    /// [Integer](Yaml::Integer) value pointed to by `name` in the Config's [Yaml].
+    fn bound_from_config(config: &Config, name: &str) -> Option<u64> {
+        let yaml = config.yaml.as_ref()?;
+        match &yaml[name] {
+            Yaml::BadValue => None,
+            Yaml::String(value) => match app::parse_block_size(value) {
+                Some(size) => Some(size),
+                None => {
+                    config.print_invalid_value_warning(name, value);
+                    None
+                }
+            },
+            Yaml::Integer(value) if *value > 0 => Some(*value as u64),
+            Yaml::Integer(value) => {
+                config.print_invalid_value_warning(name, &value.to_string());
+                None
+            }
+            _ => {
+                config.print_wrong_type_warning(name, "string");
+                None
+            }
+        }
+    }
+
+    /// Whether no bound is set, so that no file is filtered out by its size.
+    pub fn is_empty(&self) -> bool {
+        self.larger_than.is_none() && self.smaller_than.is_none()
+    }
+
+    /// Whether a file of `bytes` bytes should be listed.
+    pub fn matches(&self, bytes: u64) -> bool {
+        !matches!(self.larger_than, Some(min) if bytes < min)
+            && !matches!(self.smaller_than, Some(max) if bytes >= max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizeFilter;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        let filter = SizeFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.matches(0));
+        assert!(filter.matches(u64::MAX));
+    }
+
+    #[test]
+    fn test_from_arg_matches_range() {
+        let argv = vec!["lsd", "--larger-than", "10K", "--smaller-than", "1M"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            SizeFilter {
+                larger_than: Some(10 * 1024),
+                smaller_than: Some(1024 * 1024),
+            },
+            SizeFilter::configure_from(&matches, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--larger-than", "10X"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_arg_matches_take_precedence_over_config() {
+        let yaml_string = "larger-than: 1K\nsmaller-than: 2048";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let argv = vec!["lsd", "--larger-than", "512"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            SizeFilter {
+                larger_than: Some(512),
+                smaller_than: Some(2048),
+            },
+            SizeFilter::configure_from(&matches, &Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "larger-than: -1\nsmaller-than: big";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        assert!(SizeFilter::configure_from(&matches, &Config::with_yaml(yaml)).is_empty());
+    }
+
+    #[test]
+    fn test_matches_range() {
+        let filter = SizeFilter {
+            larger_than: Some(10),
+            smaller_than: Some(100),
+        };
+        assert!(!filter.matches(9));
+        assert!(filter.matches(10));
+        assert!(filter.matches(99));
+        assert!(!filter.matches(100));
+    }
+}
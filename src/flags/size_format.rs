@@ -0,0 +1,169 @@
+//! This module defines the [SizeFormat] options. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](SizeFormat::configure_from) method.
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The largest number of decimal places of the human readable sizes.
+const MAX_PRECISION: usize = 3;
+
+/// How to format the human readable sizes. When `precision` is not set, the values below 10 have
+/// one decimal place and the others have none. When `fixed_width` is set, the values are padded to
+/// the width of the widest possible value, so that the column does not depend on the listed files.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct SizeFormat {
+    pub precision: Option<usize>,
+    pub fixed_width: bool,
+}
+
+impl SizeFormat {
+    /// Get a `SizeFormat` struct from [ArgMatches], a [Config] or the [Default] values.
+    ///
+    /// The precision is taken from the "size-precision" argument, or else from the value of the
+    /// same name in the [Config]. The fixed width is set by the "size-fixed-width" argument, or
+    /// else by the value of the same name in the [Config].
+    pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
+        Self {
+            precision: Self::precision_from_arg_matches(matches)
+                .or_else(|| Self::precision_from_config(config)),
+            fixed_width: matches.is_present("size-fixed-width")
+                || Self::fixed_width_from_config(config).unwrap_or_default(),
+        }
+    }
+
+    /// Get a potential precision from the "size-precision" argument.
+    fn precision_from_arg_matches(matches: &ArgMatches) -> Option<usize> {
+        matches
+            .value_of("size-precision")
+            .map(|value| match value.parse::<usize>() {
+                Ok(precision) => precision,
+                Err(_) => panic!("This should not be reachable!"),
+            })
+    }
+
+    /// Get a potential precision from the [Integer](Yaml::Integer) value between 0 and 3 pointed
+    /// to by "size-precision" in the Config's [Yaml].
+    fn precision_from_config(config: &Config) -> Option<usize> {
+        let yaml = config.yaml.as_ref()?;
+        match &yaml["size-precision"] {
+            Yaml::BadValue => None,
+            Yaml::Integer(value) if (0..=MAX_PRECISION as i64).contains(value) => {
+                Some(*value as usize)
+            }
+            Yaml::Integer(value) => {
+                config.print_invalid_value_warning("size-precision", &value.to_string());
+                None
+            }
+            _ => {
+                config.print_wrong_type_warning("size-precision", "integer");
+                None
+            }
+        }
+    }
+
+    /// Get a potential fixed width from the [Boolean](Yaml::Boolean) value pointed to by
+    /// "size-fixed-width" in the Config's [Yaml].
+    fn fixed_width_from_config(config: &Config) -> Option<bool> {
+        let yaml = config.yaml.as_ref()?;
+        match &yaml["size-fixed-width"] {
+            Yaml::BadValue => None,
+            Yaml::Boolean(value) => Some(*value),
+            _ => {
+                config.print_wrong_type_warning("size-fixed-width", "boolean");
+                None
+            }
+        }
+    }
+
+    /// The width the human readable size values are padded to. A value has at most four digits
+    /// before the decimal point, as the next unit is used from 1024 on. This is 0 when the width
+    /// is not fixed.
+    pub fn value_width(&self) -> usize {
+        match (self.fixed_width, self.precision) {
+            (false, _) => 0,
+            (true, None) | (true, Some(0)) => 4,
+            (true, Some(precision)) => 5 + precision,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SizeFormat;
+
+    use crate::app;
+    use crate::config_file::Config;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        assert_eq!(
+            SizeFormat::default(),
+            SizeFormat::configure_from(&matches, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches() {
+        let argv = vec!["lsd", "--size-precision", "2", "--size-fixed-width"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            SizeFormat {
+                precision: Some(2),
+                fixed_width: true,
+            },
+            SizeFormat::configure_from(&matches, &Config::with_none())
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--size-precision", "4"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_arg_matches_take_precedence_over_config() {
+        let yaml_string = "size-precision: 3\nsize-fixed-width: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let argv = vec!["lsd", "--size-precision", "0"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            SizeFormat {
+                precision: Some(0),
+                fixed_width: true,
+            },
+            SizeFormat::configure_from(&matches, &Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "size-precision: 4\nsize-fixed-width: 1";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        let matches = app::build().get_matches_from_safe(vec!["lsd"]).unwrap();
+        assert_eq!(
+            SizeFormat::default(),
+            SizeFormat::configure_from(&matches, &Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_value_width() {
+        let width = |precision, fixed_width| {
+            SizeFormat {
+                precision,
+                fixed_width,
+            }
+            .value_width()
+        };
+        assert_eq!(0, width(Some(2), false));
+        assert_eq!(4, width(None, true));
+        assert_eq!(4, width(Some(0), true));
+        assert_eq!(7, width(Some(2), true));
+    }
+}
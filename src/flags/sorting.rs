@@ -1,34 +1,51 @@
 //! This module defines the [Sorting] options. To set it up from [ArgMatches], a [Yaml]
 //! and its [Default] value, use the [configure_from](Sorting::configure_from) method.
 
-use super::Configurable;
+use super::{classic_overrides, last_index, Configurable};
 
 use crate::config_file::Config;
 
 use clap::ArgMatches;
 use yaml_rust::Yaml;
 
-/// A collection of flags on how to sort the output.
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+/// A collection of flags on how to sort the output. The entries which the `column` does not tell
+/// apart are sorted by the `then_by` columns, in order.
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Sorting {
     pub column: SortColumn,
+    pub then_by: Vec<SortColumn>,
     pub order: SortOrder,
     pub dir_grouping: DirGrouping,
+    pub locale: SortLocale,
 }
 
 impl Sorting {
     /// Get a `Sorting` struct from [ArgMatches], a [Config] or the [Default] values.
     ///
-    /// The [SortColumn], [SortOrder] and [DirGrouping] are configured with their respective
-    /// [Configurable] implementation.
+    /// The [SortColumn], [SortOrder], [DirGrouping] and [SortLocale] are configured with their
+    /// respective [Configurable] implementation. The columns breaking the ties are the values of the "sort"
+    /// argument following the first one.
     pub fn configure_from(matches: &ArgMatches, config: &Config) -> Self {
         let column = SortColumn::configure_from(matches, config);
         let order = SortOrder::configure_from(matches, config);
         let dir_grouping = DirGrouping::configure_from(matches, config);
+        let locale = SortLocale::configure_from(matches, config);
         Self {
             column,
+            then_by: Self::then_by_from_arg_matches(matches),
             order,
             dir_grouping,
+            locale,
+        }
+    }
+
+    /// Get the columns from the values of the "sort" argument following the first one. The
+    /// shortcuts like "timesort" override all of the values, so none are left when they are
+    /// passed last.
+    fn then_by_from_arg_matches(matches: &ArgMatches) -> Vec<SortColumn> {
+        match matches.values_of("sort") {
+            Some(values) => values.skip(1).filter_map(SortColumn::from_word).collect(),
+            None => Vec::new(),
         }
     }
 }
@@ -41,13 +58,32 @@ pub enum SortColumn {
     Time,
     Size,
     Version,
+    /// The variant to keep the entries in the order they are read from the directories.
+    None,
+}
+
+impl SortColumn {
+    /// The column named by a value of the "sort" argument.
+    fn from_word(word: &str) -> Option<Self> {
+        match word {
+            "extension" => Some(Self::Extension),
+            "name" => Some(Self::Name),
+            "time" => Some(Self::Time),
+            "size" => Some(Self::Size),
+            "version" => Some(Self::Version),
+            "none" => Some(Self::None),
+            _ => None,
+        }
+    }
 }
 
 impl Configurable<Self> for SortColumn {
     /// Get a potential `SortColumn` variant from [ArgMatches].
     ///
-    /// If either the "timesort" or "sizesort" arguments are passed, this returns the corresponding
-    /// `SortColumn` variant in a [Some]. Otherwise this returns [None].
+    /// If either the "timesort", "sizesort", "extensionsort", "versionsort" or "nosort" arguments,
+    /// or the "sort" argument are passed, this returns the corresponding `SortColumn` variant in
+    /// a [Some]. The first value of the "sort" argument is used, the others break the ties.
+    /// Otherwise this returns [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
         let sort = matches.value_of("sort");
         if matches.is_present("timesort") || sort == Some("time") {
@@ -58,6 +94,10 @@ impl Configurable<Self> for SortColumn {
             Some(Self::Extension)
         } else if matches.is_present("versionsort") || sort == Some("version") {
             Some(Self::Version)
+        } else if matches.is_present("nosort") || sort == Some("none") {
+            Some(Self::None)
+        } else if sort == Some("name") {
+            Some(Self::Name)
         } else {
             None
         }
@@ -66,8 +106,8 @@ impl Configurable<Self> for SortColumn {
     /// Get a potential `SortColumn` variant from a [Config].
     ///
     /// If the Config's [Yaml] contains a [String](Yaml::String) value pointed to by "sorting" ->
-    /// "column" and it is one of "time", "size" or "name", this returns the corresponding variant
-    /// in a [Some]. Otherwise this returns [None].
+    /// "column" and it is one of "extension", "name", "time", "size", "version" or "none", this
+    /// returns the corresponding variant in a [Some]. Otherwise this returns [None].
     fn from_config(config: &Config) -> Option<Self> {
         if let Some(yaml) = &config.yaml {
             match &yaml["sorting"]["column"] {
@@ -78,6 +118,7 @@ impl Configurable<Self> for SortColumn {
                     "time" => Some(Self::Time),
                     "size" => Some(Self::Size),
                     "version" => Some(Self::Version),
+                    "none" => Some(Self::None),
                     _ => {
                         config.print_invalid_value_warning("sorting->column", &value);
                         None
@@ -166,11 +207,22 @@ pub enum DirGrouping {
 impl Configurable<Self> for DirGrouping {
     /// Get a potential `DirGrouping` variant from [ArgMatches].
     ///
-    /// If the "classic" argument is passed, then this returns the [DirGrouping::None] variant in a
-    /// [Some]. Otherwise if the argument is passed, this returns the variant corresponding to its
-    /// parameter in a [Some]. Otherwise this returns [None].
+    /// If the "group-directories-first" argument is passed after the "group-dirs" and "classic"
+    /// arguments, this returns the [DirGrouping::First] variant in a [Some]. Otherwise if the
+    /// "classic" argument is passed and the argument is not passed after it, then this returns
+    /// the [DirGrouping::None] variant in a [Some]. Otherwise if the argument is passed, this
+    /// returns the variant corresponding to its parameter in a [Some]. Otherwise this returns
+    /// [None].
     fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
-        if matches.is_present("classic") {
+        if let Some(index) = last_index(matches, "group-directories-first") {
+            let passed_after =
+                |name| matches!(last_index(matches, name), Some(other) if other > index);
+            if !passed_after("group-dirs") && !passed_after("classic") {
+                return Some(Self::First);
+            }
+        }
+
+        if classic_overrides(matches, "group-dirs") {
             Some(Self::None)
         } else if matches.occurrences_of("group-dirs") > 0 {
             match matches.value_of("group-dirs") {
@@ -226,9 +278,48 @@ impl Default for DirGrouping {
     }
 }
 
+/// The flag showing whether to compare the names with the collation of the locale, instead of
+/// ignoring their case only.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct SortLocale(pub bool);
+
+impl Configurable<Self> for SortLocale {
+    /// Get a potential `SortLocale` value from [ArgMatches].
+    ///
+    /// If the "sort-locale" argument is passed, this returns a `SortLocale` with value `true` in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("sort-locale") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SortLocale` value from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [Boolean](Yaml::Boolean) value pointed to by "sorting" ->
+    /// "locale", this returns its value as the value of the `SortLocale`, in a [Some]. Otherwise
+    /// this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["sorting"]["locale"] {
+                Yaml::BadValue => None,
+                Yaml::Boolean(value) => Some(Self(*value)),
+                _ => {
+                    config.print_wrong_type_warning("sorting->locale", "boolean");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_sort_column {
-    use super::SortColumn;
+    use super::{SortColumn, Sorting};
 
     use crate::app;
     use crate::config_file::Config;
@@ -283,6 +374,33 @@ mod test_sort_column {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_no_sort() {
+        let argv = vec!["lsd", "-U"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::None),
+            SortColumn::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--sort", "none"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::None),
+            SortColumn::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_no_sort_overridden() {
+        let argv = vec!["lsd", "-U", "-t"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::Time),
+            SortColumn::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     fn test_from_arg_matches_sort() {
         let argv = vec!["lsd", "--sort", "time"];
@@ -314,6 +432,31 @@ mod test_sort_column {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_sort_name() {
+        let argv = vec!["lsd", "--sort", "name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortColumn::Name),
+            SortColumn::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_then_by_from_arg_matches() {
+        let argv = vec!["lsd", "--sort", "extension", "--sort", "size,name"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let sorting = Sorting::configure_from(&matches, &Config::with_none());
+        assert_eq!(SortColumn::Extension, sorting.column);
+        assert_eq!(vec![SortColumn::Size, SortColumn::Name], sorting.then_by);
+
+        let argv = vec!["lsd", "--sort", "extension", "--sort", "size", "-t"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let sorting = Sorting::configure_from(&matches, &Config::with_none());
+        assert_eq!(SortColumn::Time, sorting.column);
+        assert_eq!(Vec::<SortColumn>::new(), sorting.then_by);
+    }
+
     #[test]
     fn test_multi_sort_use_last() {
         let argv = vec!["lsd", "--sort", "size", "-t", "-S", "-X", "--sort", "time"];
@@ -392,6 +535,16 @@ mod test_sort_column {
             SortColumn::from_config(&Config::with_yaml(yaml))
         );
     }
+
+    #[test]
+    fn test_from_config_no_sort() {
+        let yaml_string = "sorting:\n  column: none";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SortColumn::None),
+            SortColumn::from_config(&Config::with_yaml(yaml))
+        );
+    }
 }
 
 #[cfg(test)]
@@ -481,6 +634,50 @@ mod test_dir_grouping {
         );
     }
 
+    #[test]
+    fn test_from_arg_matches_group_directories_first() {
+        let argv = vec!["lsd", "--group-directories-first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::First),
+            DirGrouping::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_group_directories_first_and_group_dirs() {
+        let argv = vec!["lsd", "--group-dirs", "last", "--group-directories-first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::First),
+            DirGrouping::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--group-directories-first", "--group-dirs", "last"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::Last),
+            DirGrouping::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_group_directories_first_and_classic() {
+        let argv = vec!["lsd", "--classic", "--group-directories-first"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::First),
+            DirGrouping::from_arg_matches(&matches)
+        );
+
+        let argv = vec!["lsd", "--group-directories-first", "--classic"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(DirGrouping::None),
+            DirGrouping::from_arg_matches(&matches)
+        );
+    }
+
     #[test]
     fn test_from_arg_matches_last() {
         let argv = vec!["lsd", "--group-dirs", "last"];
@@ -563,3 +760,53 @@ mod test_dir_grouping {
         );
     }
 }
+
+#[cfg(test)]
+mod test_sort_locale {
+    use super::SortLocale;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SortLocale::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--sort-locale"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SortLocale(true)),
+            SortLocale::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SortLocale::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_true() {
+        let yaml_string = "sorting:\n  locale: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SortLocale(true)),
+            SortLocale::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_wrong_type() {
+        let yaml_string = "sorting:\n  locale: yes please";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SortLocale::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,125 @@
+//! This module defines the [Stdin] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+#[cfg(doc)]
+use yaml_rust::Yaml;
+
+/// The flag showing whether the paths to list are read from the standard input instead of the
+/// command line, and how they are delimited.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum Stdin {
+    Disabled,
+    Newline,
+    Null,
+}
+
+impl Stdin {
+    /// The byte ending each path read from the standard input, or [None] when the paths are
+    /// taken from the command line.
+    pub fn delimiter(self) -> Option<u8> {
+        match self {
+            Self::Disabled => None,
+            Self::Newline => Some(b'\n'),
+            Self::Null => Some(b'\0'),
+        }
+    }
+}
+
+impl Configurable<Self> for Stdin {
+    /// Get a potential `Stdin` variant from [ArgMatches].
+    ///
+    /// If the "stdin" argument is passed, or if the only input is "-", this returns
+    /// [Stdin::Null] when the "null" argument is passed too and [Stdin::Newline] otherwise, in a
+    /// [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        let dash = matches.occurrences_of("FILE") == 1 && matches.value_of("FILE") == Some("-");
+        if !matches.is_present("stdin") && !dash {
+            None
+        } else if matches.is_present("null") {
+            Some(Self::Null)
+        } else {
+            Some(Self::Newline)
+        }
+    }
+
+    /// `Stdin` can not be configured by a [Config], as it depends on how lsd is invoked.
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+/// The default value for `Stdin` is [Stdin::Disabled].
+impl Default for Stdin {
+    fn default() -> Self {
+        Self::Disabled
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Stdin;
+
+    use crate::app;
+    use crate::flags::Configurable;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Stdin::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_stdin() {
+        let argv = vec!["lsd", "--stdin"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Stdin::Newline), Stdin::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_dash() {
+        let argv = vec!["lsd", "-"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Stdin::Newline), Stdin::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_dash_among_other_inputs() {
+        let argv = vec!["lsd", "-", "src"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Stdin::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_null() {
+        let argv = vec!["lsd", "--stdin", "-0"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Stdin::Null), Stdin::from_arg_matches(&matches));
+
+        let argv = vec!["lsd", "--null", "-"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Stdin::Null), Stdin::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_null_alone() {
+        let argv = vec!["lsd", "--null"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Stdin::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_delimiter() {
+        assert_eq!(None, Stdin::Disabled.delimiter());
+        assert_eq!(Some(b'\n'), Stdin::Newline.delimiter());
+        assert_eq!(Some(b'\0'), Stdin::Null.delimiter());
+    }
+}
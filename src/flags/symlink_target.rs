@@ -0,0 +1,153 @@
+//! This module defines the [SymlinkTarget] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing how to display the paths the symbolic links point to.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub enum SymlinkTarget {
+    /// The variant to print the targets exactly as they are stored in the links.
+    Raw,
+    /// The variant to print the targets as absolute paths, resolving the relative targets
+    /// against the directory of the link.
+    Absolute,
+    /// The variant to print the targets relative to the directory of the link.
+    Relative,
+}
+
+impl SymlinkTarget {
+    fn from_str(value: &str) -> Option<Self> {
+        match value {
+            "raw" => Some(Self::Raw),
+            "absolute" => Some(Self::Absolute),
+            "relative" => Some(Self::Relative),
+            _ => None,
+        }
+    }
+}
+
+impl Configurable<Self> for SymlinkTarget {
+    /// Get a potential `SymlinkTarget` variant from [ArgMatches].
+    ///
+    /// If the "symlink-target" argument is passed, the corresponding `SymlinkTarget` variant is
+    /// returned in a [Some]. Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.occurrences_of("symlink-target") > 0 {
+            match matches.value_of("symlink-target").and_then(Self::from_str) {
+                Some(target) => Some(target),
+                None => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `SymlinkTarget` variant from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a [String](Yaml::String) value, pointed to by
+    /// "symlink-target" and it is one of "raw", "absolute" or "relative", this returns the
+    /// corresponding `SymlinkTarget` variant in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["symlink-target"] {
+                Yaml::BadValue => None,
+                Yaml::String(value) => match Self::from_str(value) {
+                    Some(target) => Some(target),
+                    None => {
+                        config.print_invalid_value_warning("symlink-target", value);
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("symlink-target", "string");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `SymlinkTarget` is [SymlinkTarget::Raw].
+impl Default for SymlinkTarget {
+    fn default() -> Self {
+        Self::Raw
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::SymlinkTarget;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_default() {
+        assert_eq!(SymlinkTarget::Raw, SymlinkTarget::default());
+    }
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, SymlinkTarget::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_absolute() {
+        let argv = vec!["lsd", "--symlink-target", "absolute"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkTarget::Absolute),
+            SymlinkTarget::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_relative() {
+        let argv = vec!["lsd", "--symlink-target", "relative"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(SymlinkTarget::Relative),
+            SymlinkTarget::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--symlink-target", "canonical"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, SymlinkTarget::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_absolute() {
+        let yaml_string = "symlink-target: absolute";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(SymlinkTarget::Absolute),
+            SymlinkTarget::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "symlink-target: canonical";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, SymlinkTarget::from_config(&Config::with_yaml(yaml)));
+    }
+}
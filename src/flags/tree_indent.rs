@@ -0,0 +1,113 @@
+//! This module defines the [TreeIndent] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::app;
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing the number of columns each depth of the tree layout is indented by, which is
+/// the width of the branches drawn before the names.
+#[derive(Clone, Debug, Copy, PartialEq, Eq)]
+pub struct TreeIndent(pub usize);
+
+impl Configurable<Self> for TreeIndent {
+    /// Get a potential `TreeIndent` from [ArgMatches].
+    ///
+    /// If the "tree-indent" argument is passed, its parsed value is returned in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        matches
+            .value_of("tree-indent")
+            .map(|value| match app::parse_tree_indent(value) {
+                Some(indent) => Self(indent),
+                None => panic!("This should not be reachable!"),
+            })
+    }
+
+    /// Get a potential `TreeIndent` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains an [Integer](Yaml::Integer) value pointed to by
+    /// "tree-indent", which is between 1 and [MAX_TREE_INDENT](app::MAX_TREE_INDENT), this
+    /// returns it in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["tree-indent"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) => match app::parse_tree_indent(&value.to_string()) {
+                    Some(indent) => Some(Self(indent)),
+                    None => {
+                        config.print_invalid_value_warning("tree-indent", &value.to_string());
+                        None
+                    }
+                },
+                _ => {
+                    config.print_wrong_type_warning("tree-indent", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+/// The default value for `TreeIndent` is 3 columns, like "├──".
+impl Default for TreeIndent {
+    fn default() -> Self {
+        Self(3)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TreeIndent;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TreeIndent::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_tree_indent() {
+        let argv = vec!["lsd", "--tree-indent", "2"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(TreeIndent(2)), TreeIndent::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        for value in &["0", "9", "wide"] {
+            let argv = vec!["lsd", "--tree-indent", value];
+            assert!(app::build().get_matches_from_safe(argv).is_err());
+        }
+    }
+
+    #[test]
+    fn test_from_config_tree_indent() {
+        let yaml_string = "tree-indent: 4";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(TreeIndent(4)),
+            TreeIndent::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "tree-indent: 0";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, TreeIndent::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -0,0 +1,113 @@
+//! This module defines the [TruncateNames] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing the number of columns the displayed names are truncated to, with an ellipsis
+/// as their last column. When it is not set, the names are not truncated.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct TruncateNames(pub Option<usize>);
+
+impl Configurable<Self> for TruncateNames {
+    /// Get a potential `TruncateNames` from [ArgMatches].
+    ///
+    /// If the "truncate-names" argument is passed, its parsed value is returned in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if let Some(value) = matches.value_of("truncate-names") {
+            match value.parse::<usize>() {
+                Ok(width) => Some(Self(Some(width))),
+                Err(_) => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `TruncateNames` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a positive [Integer](Yaml::Integer) value pointed to by
+    /// "truncate-names", this returns it in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["truncate-names"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) if *value > 0 => Some(Self(Some(*value as usize))),
+                Yaml::Integer(value) => {
+                    config.print_invalid_value_warning("truncate-names", &value.to_string());
+                    None
+                }
+                _ => {
+                    config.print_wrong_type_warning("truncate-names", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::TruncateNames;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, TruncateNames::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_truncate_names() {
+        let argv = vec!["lsd", "--truncate-names", "20"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(
+            Some(TruncateNames(Some(20))),
+            TruncateNames::from_arg_matches(&matches)
+        );
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--truncate-names", "0"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+
+        let argv = vec!["lsd", "--truncate-names", "short"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, TruncateNames::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_truncate_names() {
+        let yaml_string = "truncate-names: 30";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(TruncateNames(Some(30))),
+            TruncateNames::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "truncate-names: 0";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, TruncateNames::from_config(&Config::with_yaml(yaml)));
+    }
+}
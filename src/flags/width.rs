@@ -0,0 +1,118 @@
+//! This module defines the [Width] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use its [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+use yaml_rust::Yaml;
+
+/// The flag showing the number of columns the grid layout has to fit into. When it is not set,
+/// the width of the terminal is used, or else the `COLUMNS` environment variable. A width of 0
+/// displays one entry per line.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Width(pub Option<usize>);
+
+impl Configurable<Self> for Width {
+    /// Get a potential `Width` from [ArgMatches].
+    ///
+    /// If the "width" argument is passed, its parsed value is returned in a [Some]. Otherwise
+    /// this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if let Some(value) = matches.value_of("width") {
+            match value.parse::<usize>() {
+                Ok(width) => Some(Self(Some(width))),
+                Err(_) => panic!("This should not be reachable!"),
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Get a potential `Width` from a [Config].
+    ///
+    /// If the Config's [Yaml] contains a non-negative [Integer](Yaml::Integer) value pointed to
+    /// by "width", this returns it in a [Some]. Otherwise this returns [None].
+    fn from_config(config: &Config) -> Option<Self> {
+        if let Some(yaml) = &config.yaml {
+            match &yaml["width"] {
+                Yaml::BadValue => None,
+                Yaml::Integer(value) if *value >= 0 => Some(Self(Some(*value as usize))),
+                Yaml::Integer(value) => {
+                    config.print_invalid_value_warning("width", &value.to_string());
+                    None
+                }
+                _ => {
+                    config.print_wrong_type_warning("width", "integer");
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Width;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Width::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_width() {
+        let argv = vec!["lsd", "--width", "40"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Width(Some(40))), Width::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_zero() {
+        let argv = vec!["lsd", "--width", "0"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Width(Some(0))), Width::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_invalid() {
+        let argv = vec!["lsd", "--width", "-1"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+
+        let argv = vec!["lsd", "--width", "wide"];
+        assert!(app::build().get_matches_from_safe(argv).is_err());
+    }
+
+    #[test]
+    fn test_from_config_none() {
+        assert_eq!(None, Width::from_config(&Config::with_none()));
+    }
+
+    #[test]
+    fn test_from_config_width() {
+        let yaml_string = "width: 120";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(
+            Some(Width(Some(120))),
+            Width::from_config(&Config::with_yaml(yaml))
+        );
+    }
+
+    #[test]
+    fn test_from_config_invalid() {
+        let yaml_string = "width: -3";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Width::from_config(&Config::with_yaml(yaml)));
+    }
+}
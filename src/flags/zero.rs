@@ -0,0 +1,68 @@
+//! This module defines the [Zero] flag. To set it up from [ArgMatches], a [Yaml] and its
+//! [Default] value, use the [configure_from](Configurable::configure_from) method.
+
+use super::Configurable;
+
+use crate::config_file::Config;
+
+use clap::ArgMatches;
+
+#[cfg(doc)]
+use yaml_rust::Yaml;
+
+/// The flag showing whether to print only the paths of the listing, each ended by a NUL character.
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
+pub struct Zero(pub bool);
+
+impl Configurable<Self> for Zero {
+    /// Get a potential `Zero` value from [ArgMatches].
+    ///
+    /// If the "zero" argument is passed, this returns a `Zero` with value `true` in a [Some].
+    /// Otherwise this returns [None].
+    fn from_arg_matches(matches: &ArgMatches) -> Option<Self> {
+        if matches.is_present("zero") {
+            Some(Self(true))
+        } else {
+            None
+        }
+    }
+
+    /// `Zero` can not be configured by a [Config], as it is meant for scripting only.
+    ///
+    /// Return `None`
+    fn from_config(_: &Config) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Zero;
+
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::Configurable;
+
+    use yaml_rust::YamlLoader;
+
+    #[test]
+    fn test_from_arg_matches_none() {
+        let argv = vec!["lsd"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(None, Zero::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_arg_matches_true() {
+        let argv = vec!["lsd", "--zero"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        assert_eq!(Some(Zero(true)), Zero::from_arg_matches(&matches));
+    }
+
+    #[test]
+    fn test_from_config_ignored() {
+        let yaml_string = "zero: true";
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        assert_eq!(None, Zero::from_config(&Config::with_yaml(yaml)));
+    }
+}
@@ -1,12 +1,14 @@
+use crate::flags::icons::IconOverrides;
 use crate::meta::{FileType, Name};
 use std::collections::HashMap;
 
 pub struct Icons {
-    display_icons: bool,
-    icons_by_name: HashMap<&'static str, &'static str>,
-    icons_by_extension: HashMap<&'static str, &'static str>,
-    default_folder_icon: &'static str,
-    default_file_icon: &'static str,
+    overrides: IconOverrides,
+    /// The spaces between the icons and the names.
+    spacing: String,
+    /// The built-in icons of the theme, which are shared by all the `Icons`, or [None] when no
+    /// icon is displayed.
+    builtin: Option<&'static BuiltinIcons>,
 }
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -18,81 +20,129 @@ pub enum Theme {
 
 const ICON_SPACE: &str = " ";
 
+/// The built-in icons of a theme.
+struct BuiltinIcons {
+    /// The icons of the known file names.
+    by_name: HashMap<&'static str, &'static str>,
+    /// The icons of the known extensions of the regular files.
+    by_extension: HashMap<&'static str, &'static str>,
+    /// The icons of the file types other than the regular files, by their
+    /// [name](FileType::name), with "symlink-directory" for the symbolic links to directories.
+    by_type: HashMap<&'static str, &'static str>,
+    /// The icon of the regular files without a known name or extension.
+    file: &'static str,
+}
+
+impl BuiltinIcons {
+    /// Get the icon of the file type, or [None] for the regular files.
+    fn get_by_type(&self, file_type: FileType) -> Option<&'static str> {
+        let key = match file_type {
+            FileType::File { .. } => return None,
+            FileType::SymLink { is_dir: true } => "symlink-directory",
+            file_type => file_type.name(),
+        };
+        self.by_type.get(key).copied()
+    }
+}
+
+lazy_static! {
+    /// The built-in icons are only built once, the first time they are used, and are only read
+    /// afterwards, so that they can be shared between threads. The fancy icons need a patched
+    /// font such as the Nerd Fonts, while the unicode ones only use widely available symbols.
+    static ref FANCY_ICONS: BuiltinIcons = BuiltinIcons {
+        by_name: Icons::get_default_icons_by_name(),
+        by_extension: Icons::get_default_icons_by_extension(),
+        by_type: Icons::get_default_icons_by_type(),
+        file: "\u{f016}", // 
+    };
+    static ref UNICODE_ICONS: BuiltinIcons = BuiltinIcons {
+        by_name: Icons::get_unicode_icons_by_name(),
+        by_extension: Icons::get_unicode_icons_by_extension(),
+        by_type: Icons::get_unicode_icons_by_type(),
+        file: "\u{1f5cb}", // 🗋
+    };
+}
+
 // In order to add a new icon, write the unicode value like "\ue5fb" then
 // run the command below in vim:
 //
 // s#\\u[0-9a-f]*#\=eval('"'.submatch(0).'"')#
 impl Icons {
     pub fn new(theme: Theme) -> Self {
-        let display_icons = theme == Theme::Fancy || theme == Theme::Unicode;
-        let (icons_by_name, icons_by_extension, default_file_icon, default_folder_icon) =
-            if theme == Theme::Fancy {
-                (
-                    Self::get_default_icons_by_name(),
-                    Self::get_default_icons_by_extension(),
-                    "\u{f016}", // 
-                    "\u{f115}", // 
-                )
-            } else {
-                (
-                    HashMap::new(),
-                    HashMap::new(),
-                    "\u{1f5cb}", // 🗋
-                    "\u{1f5c1}", // 🗁
-                )
-            };
+        let builtin = match theme {
+            Theme::NoIcon => None,
+            Theme::Fancy => Some(&*FANCY_ICONS),
+            Theme::Unicode => Some(&*UNICODE_ICONS),
+        };
 
         Self {
-            display_icons,
-            icons_by_name,
-            icons_by_extension,
-            default_file_icon,
-            default_folder_icon,
+            overrides: IconOverrides::default(),
+            spacing: String::from(ICON_SPACE),
+            builtin,
         }
     }
 
+    /// Use the icons set by the user instead of the built-in ones.
+    pub fn with_overrides(mut self, overrides: IconOverrides) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Separate the icons from the names with the given number of spaces.
+    pub fn with_spacing(mut self, spacing: usize) -> Self {
+        self.spacing = ICON_SPACE.repeat(spacing);
+        self
+    }
+
     pub fn get(&self, name: &Name) -> String {
-        if !self.display_icons {
-            return String::new();
-        }
+        let builtin = match self.builtin {
+            Some(builtin) => builtin,
+            None => return String::new(),
+        };
 
         // Check file types
         let file_type: FileType = name.file_type();
 
-        let icon = if let FileType::Directory { .. } = file_type {
-            self.default_folder_icon
-        } else if let FileType::SymLink { is_dir: true } = file_type {
-            "\u{f482}" // ""
-        } else if let FileType::SymLink { is_dir: false } = file_type {
-            "\u{f481}" // ""
-        } else if let FileType::Socket = file_type {
-            "\u{f6a7}" // ""
-        } else if let FileType::Pipe = file_type {
-            "\u{f731}" // ""
-        } else if let FileType::CharDevice = file_type {
-            "\u{e601}" // ""
-        } else if let FileType::BlockDevice = file_type {
-            "\u{fc29}" // "ﰩ"
-        } else if let FileType::Special = file_type {
-            "\u{f2dc}" // ""
-        } else if let Some(icon) = self
-            .icons_by_name
+        let icon = if let Some(icon) = self.get_user_icon(name) {
+            icon
+        } else if let Some(icon) = builtin.get_by_type(file_type) {
+            icon
+        } else if let Some(icon) = builtin
+            .by_name
             .get(name.file_name().to_lowercase().as_str())
         {
             // Use the known names.
             icon
-        } else if let Some(icon) = name.extension().and_then(|extension| {
-            self.icons_by_extension
-                .get(extension.to_lowercase().as_str())
-        }) {
+        } else if let Some(icon) = name
+            .extension()
+            .and_then(|extension| builtin.by_extension.get(extension.to_lowercase().as_str()))
+        {
             // Use the known extensions.
             icon
+        } else if let Some(icon) = self.overrides.by_type.get("file") {
+            icon
         } else {
             // Use the default icons.
-            self.default_file_icon
+            builtin.file
         };
 
-        format!("{}{}", icon, ICON_SPACE)
+        format!("{}{}", icon, self.spacing)
+    }
+
+    /// Get the icon set by the user for the file name, or else for the extension of a file or the
+    /// type of anything else. The icon set for regular files is only used as their default icon.
+    fn get_user_icon(&self, name: &Name) -> Option<&str> {
+        if let Some(icon) = self.overrides.by_name.get(&name.file_name().to_lowercase()) {
+            return Some(icon);
+        }
+
+        match name.file_type() {
+            FileType::File { .. } => name
+                .extension()
+                .and_then(|extension| self.overrides.by_extension.get(&extension.to_lowercase())),
+            file_type => self.overrides.by_type.get(file_type.name()),
+        }
+        .map(String::as_str)
     }
 
     fn get_default_icons_by_name() -> HashMap<&'static str, &'static str> {
@@ -341,14 +391,106 @@ impl Icons {
 
         m
     }
+
+    fn get_default_icons_by_type() -> HashMap<&'static str, &'static str> {
+        let mut m = HashMap::new();
+
+        m.insert("directory", "\u{f115}"); // 
+        m.insert("symlink-directory", "\u{f482}"); // 
+        m.insert("symlink", "\u{f481}"); // 
+        m.insert("socket", "\u{f6a7}"); // 
+        m.insert("pipe", "\u{f731}"); // 
+        m.insert("char-device", "\u{e601}"); // 
+        m.insert("block-device", "\u{fc29}"); // ﰩ
+        m.insert("special", "\u{f2dc}"); // 
+
+        m
+    }
+
+    fn get_unicode_icons_by_type() -> HashMap<&'static str, &'static str> {
+        let mut m = HashMap::new();
+
+        m.insert("directory", "\u{1f5c1}"); // 🗁
+        m.insert("symlink-directory", "\u{1f5c2}"); // 🗂
+        m.insert("symlink", "\u{1f517}"); // 🔗
+        m.insert("socket", "\u{1f50c}"); // 🔌
+        m.insert("pipe", "\u{2225}"); // ∥
+        m.insert("char-device", "\u{2328}"); // ⌨
+        m.insert("block-device", "\u{1f5b4}"); // 🖴
+        m.insert("special", "\u{2699}"); // ⚙
+
+        m
+    }
+
+    fn get_unicode_icons_by_name() -> HashMap<&'static str, &'static str> {
+        let mut m = HashMap::new();
+
+        // Note: filenames must be lower-case
+
+        m.insert(".git", "\u{1f33f}"); // 🌿
+        m.insert("dockerfile", "\u{1f433}"); // 🐳
+        m.insert("license", "\u{2696}"); // ⚖
+        m.insert("makefile", "\u{1f6e0}"); // 🛠
+
+        m
+    }
+
+    fn get_unicode_icons_by_extension() -> HashMap<&'static str, &'static str> {
+        let mut m = HashMap::new();
+
+        // Note: extensions must be lower-case
+
+        m.insert("7z", "\u{1f4e6}"); // 📦
+        m.insert("avi", "\u{1f39e}"); // 🎞
+        m.insert("bat", "\u{1f5b3}"); // 🖳
+        m.insert("bmp", "\u{1f5bc}"); // 🖼
+        m.insert("bz2", "\u{1f4e6}"); // 📦
+        m.insert("doc", "\u{1f5b9}"); // 🖹
+        m.insert("docx", "\u{1f5b9}"); // 🖹
+        m.insert("fish", "\u{1f5b3}"); // 🖳
+        m.insert("flac", "\u{1f3b5}"); // 🎵
+        m.insert("gif", "\u{1f5bc}"); // 🖼
+        m.insert("gz", "\u{1f4e6}"); // 📦
+        m.insert("ico", "\u{1f5bc}"); // 🖼
+        m.insert("jpeg", "\u{1f5bc}"); // 🖼
+        m.insert("jpg", "\u{1f5bc}"); // 🖼
+        m.insert("m4a", "\u{1f3b5}"); // 🎵
+        m.insert("md", "\u{1f5b9}"); // 🖹
+        m.insert("mkv", "\u{1f39e}"); // 🎞
+        m.insert("mov", "\u{1f39e}"); // 🎞
+        m.insert("mp3", "\u{1f3b5}"); // 🎵
+        m.insert("mp4", "\u{1f39e}"); // 🎞
+        m.insert("ogg", "\u{1f3b5}"); // 🎵
+        m.insert("opus", "\u{1f3b5}"); // 🎵
+        m.insert("pdf", "\u{1f5b9}"); // 🖹
+        m.insert("png", "\u{1f5bc}"); // 🖼
+        m.insert("rar", "\u{1f4e6}"); // 📦
+        m.insert("sh", "\u{1f5b3}"); // 🖳
+        m.insert("svg", "\u{1f5bc}"); // 🖼
+        m.insert("tar", "\u{1f4e6}"); // 📦
+        m.insert("tgz", "\u{1f4e6}"); // 📦
+        m.insert("txt", "\u{1f5b9}"); // 🖹
+        m.insert("wav", "\u{1f3b5}"); // 🎵
+        m.insert("webm", "\u{1f39e}"); // 🎞
+        m.insert("webp", "\u{1f5bc}"); // 🖼
+        m.insert("xz", "\u{1f4e6}"); // 📦
+        m.insert("zip", "\u{1f4e6}"); // 📦
+        m.insert("zsh", "\u{1f5b3}"); // 🖳
+
+        m
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::{Icons, Theme, ICON_SPACE};
+    use crate::app;
+    use crate::config_file::Config;
+    use crate::flags::{self, icons::IconOverrides};
     use crate::meta::Meta;
     use std::fs::File;
     use tempfile::tempdir;
+    use yaml_rust::YamlLoader;
 
     #[test]
     fn get_no_icon() {
@@ -413,6 +555,30 @@ mod test {
         assert_eq!(icon, format!("{}{}", "\u{1f5c1}", ICON_SPACE));
     }
 
+    #[test]
+    fn get_directory_icon_differs_between_themes() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+
+        let fancy = Icons::new(Theme::Fancy).get(&meta.name);
+        let unicode = Icons::new(Theme::Unicode).get(&meta.name);
+
+        assert_ne!(fancy, unicode);
+    }
+
+    #[test]
+    fn get_symlink_icon_unicode() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let link_path = tmp_dir.path().join("link");
+        std::os::unix::fs::symlink(tmp_dir.path().join("missing"), &link_path)
+            .expect("failed to create the symlink");
+        let meta = Meta::from_path(&link_path, false).unwrap();
+
+        let icon = Icons::new(Theme::Unicode).get(&meta.name);
+
+        assert_eq!(icon, format!("{}{}", "\u{1f517}", ICON_SPACE));
+    }
+
     #[test]
     fn get_directory_icon_with_ext() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -456,4 +622,131 @@ mod test {
             assert_eq!(icon, format!("{}{}", file_icon, ICON_SPACE));
         }
     }
+
+    fn overrides(yaml_string: &str) -> IconOverrides {
+        let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
+        flags::Icons::configure_from(
+            &app::build().get_matches_from_safe(vec!["lsd"]).unwrap(),
+            &Config::with_yaml(yaml),
+        )
+        .overrides
+    }
+
+    #[test]
+    fn get_icon_by_name_and_extension_unicode() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let names = Icons::get_unicode_icons_by_name().into_iter();
+        let extensions = Icons::get_unicode_icons_by_extension()
+            .into_iter()
+            .map(|(ext, icon)| (format!("file.{}", ext), icon));
+
+        for (file_name, file_icon) in names
+            .map(|(name, icon)| (name.to_string(), icon))
+            .chain(extensions)
+        {
+            let file_path = tmp_dir.path().join(&file_name);
+            File::create(&file_path).expect("failed to create file");
+            let meta = Meta::from_path(&file_path, false).unwrap();
+
+            let icon = Icons::new(Theme::Unicode).get(&meta.name);
+
+            assert_eq!(icon, format!("{}{}", file_icon, ICON_SPACE));
+        }
+    }
+
+    #[test]
+    fn get_user_icon_by_extension() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("main.rs");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let icon = Icons::new(Theme::Fancy)
+            .with_overrides(overrides("icons:\n  by-extension:\n    rs: \"R\""));
+
+        assert_eq!(icon.get(&meta.name), format!("{}{}", "R", ICON_SPACE));
+        assert_eq!(
+            Icons::new(Theme::Fancy).get(&meta.name),
+            format!("{}{}", "\u{e7a8}", ICON_SPACE)
+        );
+    }
+
+    #[test]
+    fn get_user_icon_by_name_and_type() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("Makefile");
+        File::create(&file_path).expect("failed to create file");
+        let other_path = tmp_dir.path().join("other");
+        File::create(&other_path).expect("failed to create file");
+        let dir_meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+
+        let icon = Icons::new(Theme::Unicode).with_overrides(overrides(
+            "icons:\n  by-name:\n    makefile: M\n  by-type:\n    directory: D\n    file: F",
+        ));
+
+        let get = |path| icon.get(&Meta::from_path(path, false).unwrap().name);
+        assert_eq!(get(&file_path), format!("{}{}", "M", ICON_SPACE));
+        assert_eq!(get(&other_path), format!("{}{}", "F", ICON_SPACE));
+        assert_eq!(icon.get(&dir_meta.name), format!("{}{}", "D", ICON_SPACE));
+    }
+
+    #[test]
+    fn get_builtin_icon_before_user_default_file_icon() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("main.rs");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let icon =
+            Icons::new(Theme::Fancy).with_overrides(overrides("icons:\n  by-type:\n    file: F"));
+
+        assert_eq!(
+            icon.get(&meta.name),
+            format!("{}{}", "\u{e7a8}", ICON_SPACE)
+        );
+    }
+
+    #[test]
+    fn get_icons_from_several_threads() {
+        use std::sync::Arc;
+        use std::thread;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let names: Vec<_> = ["file.rs", "Makefile", "file.unknown"]
+            .iter()
+            .map(|file_name| {
+                let file_path = tmp_dir.path().join(file_name);
+                File::create(&file_path).expect("failed to create file");
+                Meta::from_path(&file_path, false).unwrap().name
+            })
+            .collect();
+
+        let icons = Arc::new(Icons::new(Theme::Fancy));
+        // The built-in icons are shared instead of being built for each `Icons`.
+        assert!(std::ptr::eq(
+            icons.builtin.unwrap(),
+            Icons::new(Theme::Fancy).builtin.unwrap()
+        ));
+        let expected: Vec<String> = names.iter().map(|name| icons.get(name)).collect();
+        let names = Arc::new(names);
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let icons = Arc::clone(&icons);
+                let names = Arc::clone(&names);
+                thread::spawn(move || {
+                    (0..100)
+                        .flat_map(|_| names.iter().map(|name| icons.get(name)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            let icons = thread.join().expect("failed to query the icons");
+            for chunk in icons.chunks(expected.len()) {
+                assert_eq!(expected, chunk);
+            }
+        }
+    }
 }
@@ -10,8 +10,12 @@ extern crate clap;
 extern crate ansi_term;
 extern crate chrono_humanize;
 extern crate dirs;
+#[macro_use]
+extern crate lazy_static;
 extern crate libc;
 extern crate lscolors;
+extern crate serde;
+extern crate serde_json;
 #[cfg(test)]
 extern crate tempfile;
 extern crate term_grid;
@@ -36,6 +40,7 @@ mod display;
 mod flags;
 mod icon;
 mod meta;
+mod progress;
 mod sort;
 
 use crate::config_file::Config;
@@ -52,7 +57,8 @@ macro_rules! print_error {
 
         let stderr = std::io::stderr();
 
-        {
+        // The line of the spinner is cleared first, so that the error is not printed over it.
+        $crate::progress::suspend(|| {
             let mut handle = stderr.lock();
             // We can write on stderr, so we simply ignore the error and don't print
             // and stop with success.
@@ -60,7 +66,7 @@ macro_rules! print_error {
             if res.is_err() {
                 std::process::exit(0);
             }
-        }
+        });
     };
 }
 
@@ -87,13 +93,15 @@ macro_rules! print_output {
 }
 
 fn main() {
+    sort::set_up_collation();
+
     let matches = app::build().get_matches_from(wild::args_os());
 
     // input translate glob FILE without single quote into real names
     // for example:
     // * to all files matched
     // '*' remain as '*'
-    let inputs = matches
+    let inputs: Vec<PathBuf> = matches
         .values_of("FILE")
         .expect("failed to retrieve cli value")
         .map(PathBuf::from)
@@ -103,9 +111,11 @@ fn main() {
         Config::with_none()
     } else {
         Config::read_config()
+            .with_directory_overrides(&inputs, matches.is_present("directory-config"))
     };
     let flags = Flags::configure_from(&matches, &config).unwrap_or_else(|err| err.exit());
     let core = Core::new(flags);
 
-    core.run(inputs);
+    let exit_code = core.run(inputs);
+    std::process::exit(exit_code as i32);
 }
@@ -0,0 +1,136 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::path::Path;
+
+/// The SELinux security context of a file, like `ls -Z` shows.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AccessControl {
+    context: Option<String>,
+}
+
+impl From<&Path> for AccessControl {
+    fn from(path: &Path) -> Self {
+        Self::from_xattr(read_context(path).as_deref())
+    }
+}
+
+impl AccessControl {
+    /// The context held by the value of the `security.selinux` extended attribute, which the
+    /// kernel ends by a NUL character.
+    fn from_xattr(value: Option<&[u8]>) -> Self {
+        let context = value
+            .map(|value| match value.split_last() {
+                Some((0, context)) => context,
+                _ => value,
+            })
+            .filter(|value| !value.is_empty())
+            .map(|value| String::from_utf8_lossy(value).to_string());
+
+        Self { context }
+    }
+
+    /// The security context, or `?` for the files without one.
+    pub fn value_string(&self) -> String {
+        match &self.context {
+            Some(context) => context.clone(),
+            None => String::from("?"),
+        }
+    }
+
+    /// Render the security context, left-aligned to `val_alignment` characters.
+    pub fn render(&self, colors: &Colors, val_alignment: usize) -> ColoredString<'_> {
+        let value = format!("{:<1$}", self.value_string(), val_alignment);
+
+        colors.colorize(value, &Elem::Context)
+    }
+}
+
+/// Read the value of the `security.selinux` extended attribute of the file at `path`, without
+/// following symlinks.
+#[cfg(target_os = "linux")]
+fn read_context(path: &Path) -> Option<Vec<u8>> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    let name = CString::new("security.selinux").ok()?;
+
+    // The first call only gets the size of the buffer needed to hold the value.
+    let size = unsafe { libc::lgetxattr(path.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0) };
+    if size <= 0 {
+        return None;
+    }
+
+    let mut buffer = vec![0u8; size as usize];
+    let size = unsafe {
+        libc::lgetxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            buffer.as_mut_ptr() as *mut libc::c_void,
+            buffer.len(),
+        )
+    };
+    if size <= 0 {
+        return None;
+    }
+    buffer.truncate(size as usize);
+
+    Some(buffer)
+}
+
+/// The security contexts are only read on Linux, where SELinux runs.
+#[cfg(not(target_os = "linux"))]
+fn read_context(_: &Path) -> Option<Vec<u8>> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AccessControl;
+    use crate::color::{Colors, Theme};
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_context_from_xattr() {
+        let access_control =
+            AccessControl::from_xattr(Some(b"unconfined_u:object_r:user_home_t:s0\0"));
+        assert_eq!(
+            "unconfined_u:object_r:user_home_t:s0",
+            access_control.value_string()
+        );
+    }
+
+    #[test]
+    fn test_context_without_xattr() {
+        assert_eq!("?", AccessControl::from_xattr(None).value_string());
+        assert_eq!("?", AccessControl::from_xattr(Some(b"\0")).value_string());
+    }
+
+    #[test]
+    fn test_render_aligned() {
+        let colors = Colors::new(Theme::NoColor);
+
+        let access_control = AccessControl::from_xattr(Some(b"system_u:object_r:etc_t:s0"));
+        assert_eq!(
+            "system_u:object_r:etc_t:s0  ",
+            access_control.render(&colors, 28).to_string()
+        );
+        assert_eq!(
+            "?    ",
+            AccessControl::default().render(&colors, 5).to_string()
+        );
+    }
+
+    #[test]
+    fn test_file_without_context() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        // The file systems of the systems without SELinux do not label the files.
+        let access_control = AccessControl::from(file_path.as_path());
+        if !std::path::Path::new("/sys/fs/selinux/enforce").exists() {
+            assert_eq!("?", access_control.value_string());
+        }
+    }
+}
@@ -0,0 +1,80 @@
+use crate::color::{ColoredString, Colors, Elem};
+use ansi_term::ANSIStrings;
+use std::fs::Metadata;
+use std::os::windows::fs::MetadataExt;
+use winapi::um::winnt::{
+    FILE_ATTRIBUTE_ARCHIVE, FILE_ATTRIBUTE_HIDDEN, FILE_ATTRIBUTE_READONLY, FILE_ATTRIBUTE_SYSTEM,
+};
+
+/// The Windows attributes of a file, which matter more there than the permissions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Attributes(u32);
+
+impl<'a> From<&'a Metadata> for Attributes {
+    fn from(meta: &Metadata) -> Self {
+        Self(meta.file_attributes())
+    }
+}
+
+impl Attributes {
+    /// The attributes displayed, in order, with their letters.
+    const DISPLAYED: [(u32, &'static str); 4] = [
+        (FILE_ATTRIBUTE_HIDDEN, "H"),
+        (FILE_ATTRIBUTE_SYSTEM, "S"),
+        (FILE_ATTRIBUTE_ARCHIVE, "A"),
+        (FILE_ATTRIBUTE_READONLY, "R"),
+    ];
+
+    pub fn is_hidden(self) -> bool {
+        self.0 & FILE_ATTRIBUTE_HIDDEN != 0
+    }
+
+    /// Render the hidden, system, archive and read-only attributes like `HSA-`, with a dash for
+    /// each attribute which is not set.
+    pub fn render(self, colors: &Colors) -> ColoredString<'_> {
+        let strings: Vec<ColoredString> = Self::DISPLAYED
+            .iter()
+            .map(|(attribute, letter)| {
+                if self.0 & attribute != 0 {
+                    colors.colorize(letter.to_string(), &Elem::Read)
+                } else {
+                    colors.colorize(String::from("-"), &Elem::NoAccess)
+                }
+            })
+            .collect();
+
+        ColoredString::from(ANSIStrings(&strings).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Attributes;
+    use crate::color::{Colors, Theme};
+    use std::fs::{self, File};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_read_only_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        let mut permissions = file_path.metadata().unwrap().permissions();
+        permissions.set_readonly(true);
+        fs::set_permissions(&file_path, permissions).expect("failed to set read-only");
+
+        let attributes = Attributes::from(&file_path.metadata().unwrap());
+        let rendered = attributes.render(&Colors::new(Theme::NoColor)).to_string();
+
+        // The archive attribute depends on the file system, the others are not set on new files.
+        assert_eq!(4, rendered.len());
+        assert!(rendered.starts_with("--"));
+        assert!(rendered.ends_with('R'));
+        assert!(!attributes.is_hidden());
+
+        let mut permissions = file_path.metadata().unwrap().permissions();
+        permissions.set_readonly(false);
+        fs::set_permissions(&file_path, permissions).expect("failed to unset read-only");
+    }
+}
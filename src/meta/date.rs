@@ -1,65 +1,170 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::{DateFlag, Flags};
+use crate::flags::{DateColor, DateFlag, Flags, TimeKind};
 use chrono_humanize::HumanTime;
 use std::fs::Metadata;
-use std::time::UNIX_EPOCH;
+use std::io;
+use std::time::{SystemTime, UNIX_EPOCH};
 use time::{Duration, Timespec};
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Date(time::Tm);
+/// The time stamps of a file. The ones which are not supported by the platform or the file
+/// system are [None].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Date {
+    modified: Option<time::Tm>,
+    accessed: Option<time::Tm>,
+    changed: Option<time::Tm>,
+    created: Option<time::Tm>,
+}
 
 impl<'a> From<&'a Metadata> for Date {
     fn from(meta: &'a Metadata) -> Self {
-        let modified_time = meta.modified().expect("failed to retrieve modified date");
+        Self {
+            modified: to_tm(meta.modified()),
+            accessed: to_tm(meta.accessed()),
+            changed: changed_time(meta),
+            created: to_tm(meta.created()),
+        }
+    }
+}
 
-        let modified_time_since_epoch =
-            modified_time.duration_since(UNIX_EPOCH).unwrap_or_default();
+fn to_tm(system_time: io::Result<SystemTime>) -> Option<time::Tm> {
+    let time_since_epoch = system_time
+        .ok()?
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default();
 
-        let time = time::at(Timespec::new(
-            modified_time_since_epoch.as_secs() as i64,
-            modified_time_since_epoch.subsec_nanos() as i32,
-        ));
+    Some(time::at(Timespec::new(
+        time_since_epoch.as_secs() as i64,
+        time_since_epoch.subsec_nanos() as i32,
+    )))
+}
 
-        Date(time)
-    }
+#[cfg(unix)]
+fn changed_time(meta: &Metadata) -> Option<time::Tm> {
+    use std::os::unix::fs::MetadataExt;
+
+    Some(time::at(Timespec::new(
+        meta.ctime(),
+        meta.ctime_nsec() as i32,
+    )))
+}
+
+/// The change time of the status of a file is only a unix concept.
+#[cfg(not(unix))]
+fn changed_time(_: &Metadata) -> Option<time::Tm> {
+    None
 }
 
 impl Date {
+    /// The time stamp of the given kind, if it is available.
+    pub fn get(&self, kind: TimeKind) -> Option<&time::Tm> {
+        match kind {
+            TimeKind::Modified => self.modified.as_ref(),
+            TimeKind::Accessed => self.accessed.as_ref(),
+            TimeKind::Changed => self.changed.as_ref(),
+            TimeKind::Created => self.created.as_ref(),
+        }
+    }
+
+    /// The time stamp of the given kind to sort by. A missing time stamp is the unix epoch, so
+    /// that the files without it are sorted consistently as the oldest ones.
+    pub fn sort_key(&self, kind: TimeKind) -> Timespec {
+        self.get(kind)
+            .map_or(Timespec::new(0, 0), |time| time.to_timespec())
+    }
+
+    /// The number of seconds since the unix epoch of the modification time.
+    pub fn timestamp(&self) -> i64 {
+        self.modified.map_or(0, |time| time.to_timespec().sec)
+    }
+
     pub fn render(&self, colors: &Colors, flags: &Flags) -> ColoredString {
-        let now = time::now();
+        let time = match self.get(flags.time_kind) {
+            Some(time) => time,
+            None => return colors.colorize(String::from("-"), &Elem::Older),
+        };
 
-        let elem;
-        if self.0 > now - Duration::hours(1) {
-            elem = &Elem::HourOld;
-        } else if self.0 > now - Duration::days(1) {
-            elem = &Elem::DayOld;
+        let elem = age_elem(time, time::now(), &flags.date_color);
+
+        colors.colorize(date_string(time, flags), &elem)
+    }
+}
+
+/// The element coloring a date, depending on how long before `now` it is.
+fn age_elem(time: &time::Tm, now: time::Tm, date_color: &DateColor) -> Elem {
+    if !date_color.gradient.0 {
+        return if *time > now - Duration::hours(1) {
+            Elem::HourOld
+        } else if *time > now - Duration::days(1) {
+            Elem::DayOld
         } else {
-            elem = &Elem::Older;
-        }
+            Elem::Older
+        };
+    }
+
+    let thresholds = &date_color.thresholds;
+    if *time > now - Duration::seconds(thresholds.recent) {
+        Elem::HourOld
+    } else if *time > now - Duration::seconds(thresholds.day) {
+        Elem::DayOld
+    } else if *time > now - Duration::seconds(thresholds.week) {
+        Elem::WeekOld
+    } else {
+        Elem::Ancient
+    }
+}
 
-        colors.colorize(self.date_string(&flags), elem)
+fn date_string(time: &time::Tm, flags: &Flags) -> String {
+    match &flags.date {
+        DateFlag::Date => time.ctime().to_string(),
+        DateFlag::Relative => relative_string(time, time::now()),
+        date => formatted_string(&time.to_local(), date, time::now()),
     }
+}
 
-    pub fn date_string(&self, flags: &Flags) -> String {
-        match &flags.date {
-            DateFlag::Date => self.0.ctime().to_string(),
-            DateFlag::Relative => format!("{}", HumanTime::from(self.0 - time::now())),
-            DateFlag::Formatted(format) => self.0.to_local().strftime(&format).unwrap().to_string(),
+/// The date formatted by the format of `date`. When the format depends on the age of the date,
+/// the dates of the six months before `now` are the recent ones, like in `ls`.
+fn formatted_string(time: &time::Tm, date: &DateFlag, now: time::Tm) -> String {
+    let format = match date {
+        DateFlag::Formatted(format) => format,
+        DateFlag::FormattedByAge(recent, _)
+            if *time <= now && *time > now - Duration::days(365 / 2) =>
+        {
+            recent
         }
+        DateFlag::FormattedByAge(_, old) => old,
+        _ => return time.ctime().to_string(),
+    };
+    time.strftime(format).unwrap().to_string()
+}
+
+/// The date relative to `now`, like "3 days ago" or "in 2 hours". Anything closer than a minute
+/// to `now` is "just now".
+fn relative_string(time: &time::Tm, now: time::Tm) -> String {
+    let duration = *time - now;
+
+    if duration.num_seconds().abs() < 60 {
+        String::from("just now")
+    } else {
+        HumanTime::from(duration).to_string()
     }
 }
 
 #[cfg(test)]
 mod test {
-    use super::Date;
-    use crate::color::{Colors, Theme};
-    use crate::flags::{DateFlag, Flags};
+    use super::{age_elem, formatted_string, relative_string, Date};
+    use crate::app;
+    use crate::color::{Colors, Elem, Theme};
+    use crate::config_file::Config;
+    use crate::flags::date_color::{DateGradient, DateThresholds};
+    use crate::flags::{DateColor, DateFlag, Flags, TimeKind};
     use ansi_term::Colour;
     use std::io;
     use std::path::Path;
     use std::process::{Command, ExitStatus};
     use std::{env, fs};
     use time;
+    use time::Timespec;
 
     #[cfg(unix)]
     fn cross_platform_touch(path: &Path, date: &time::Tm) -> io::Result<ExitStatus> {
@@ -210,8 +315,215 @@ mod test {
         let mut flags = Flags::default();
         flags.date = DateFlag::Relative;
 
-        assert_eq!(Colour::Fixed(40).paint("now"), date.render(&colors, &flags));
+        assert_eq!(
+            Colour::Fixed(40).paint("just now"),
+            date.render(&colors, &flags)
+        );
 
         fs::remove_file(file_path).unwrap();
     }
+
+    #[test]
+    fn test_relative_string() {
+        let now = time::at(Timespec::new(1_600_000_000, 0));
+        let relative = |offset| relative_string(&(now + time::Duration::seconds(offset)), now);
+
+        assert_eq!("just now", relative(0));
+        assert_eq!("just now", relative(-59));
+        assert_eq!("just now", relative(59));
+        assert_eq!("2 minutes ago", relative(-120));
+        assert_eq!("2 hours ago", relative(-2 * 3600));
+        assert_eq!("3 days ago", relative(-3 * 86400));
+        assert_eq!("in 2 hours", relative(2 * 3600));
+        assert_eq!("in 3 days", relative(3 * 86400));
+    }
+
+    #[test]
+    fn test_render_selected_time_kind() {
+        let modified = time::at(Timespec::new(1_500_000_000, 0));
+        let accessed = time::at(Timespec::new(1_600_000_000, 0));
+        let date = Date {
+            modified: Some(modified),
+            accessed: Some(accessed),
+            changed: None,
+            created: None,
+        };
+        let colors = Colors::new(Theme::NoColor);
+        let mut flags = Flags::default();
+
+        assert_eq!(
+            modified.ctime().to_string(),
+            date.render(&colors, &flags).to_string()
+        );
+
+        flags.time_kind = TimeKind::Accessed;
+        assert_eq!(
+            accessed.ctime().to_string(),
+            date.render(&colors, &flags).to_string()
+        );
+
+        flags.time_kind = TimeKind::Created;
+        assert_eq!("-", date.render(&colors, &flags).to_string());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_accessed_time_from_metadata() {
+        let mut file_path = env::temp_dir();
+        file_path.push("test_accessed_time_from_metadata.tmp");
+
+        let modified_date = (time::now() - time::Duration::days(2)).to_local();
+        let accessed_date = (time::now() - time::Duration::days(5)).to_local();
+
+        let success = cross_platform_touch(&file_path, &modified_date)
+            .unwrap()
+            .success();
+        assert!(success, "failed to exec touch");
+        let success = Command::new("touch")
+            .arg("-a")
+            .arg("-t")
+            .arg(accessed_date.strftime("%Y%m%d%H%M.%S").unwrap().to_string())
+            .arg(&file_path)
+            .status()
+            .unwrap()
+            .success();
+        assert!(success, "failed to exec touch");
+
+        let date = Date::from(&file_path.metadata().unwrap());
+
+        assert_eq!(
+            accessed_date.to_timespec().sec,
+            date.get(TimeKind::Accessed).unwrap().to_timespec().sec
+        );
+        assert_eq!(
+            modified_date.to_timespec().sec,
+            date.get(TimeKind::Modified).unwrap().to_timespec().sec
+        );
+
+        fs::remove_file(file_path).unwrap();
+    }
+
+    #[test]
+    fn test_sort_key_of_missing_time_is_epoch() {
+        let modified = time::at(Timespec::new(1_500_000_000, 0));
+        let date = Date {
+            modified: Some(modified),
+            accessed: None,
+            changed: None,
+            created: None,
+        };
+
+        assert_eq!(
+            Timespec::new(1_500_000_000, 0),
+            date.sort_key(TimeKind::Modified)
+        );
+        assert_eq!(Timespec::new(0, 0), date.sort_key(TimeKind::Created));
+    }
+
+    #[test]
+    fn test_age_elem_without_gradient() {
+        let now = time::at(Timespec::new(1_600_000_000, 0));
+        let elem = |offset| {
+            age_elem(
+                &(now - time::Duration::seconds(offset)),
+                now,
+                &DateColor::default(),
+            )
+        };
+
+        assert_eq!(Elem::HourOld, elem(60));
+        assert_eq!(Elem::DayOld, elem(2 * 3600));
+        assert_eq!(Elem::Older, elem(3 * 86400));
+        assert_eq!(Elem::Older, elem(30 * 86400));
+    }
+
+    #[test]
+    fn test_age_elem_with_gradient() {
+        let now = time::at(Timespec::new(1_600_000_000, 0));
+        let date_color = DateColor {
+            gradient: DateGradient(true),
+            thresholds: DateThresholds::default(),
+        };
+        let elem = |offset| age_elem(&(now - time::Duration::seconds(offset)), now, &date_color);
+
+        assert_eq!(Elem::HourOld, elem(60));
+        assert_eq!(Elem::DayOld, elem(2 * 3600));
+        assert_eq!(Elem::WeekOld, elem(3 * 86400));
+        assert_eq!(Elem::Ancient, elem(30 * 86400));
+    }
+
+    #[test]
+    fn test_age_elem_with_custom_thresholds() {
+        let now = time::at(Timespec::new(1_600_000_000, 0));
+        let date_color = DateColor {
+            gradient: DateGradient(true),
+            thresholds: DateThresholds {
+                recent: 10,
+                day: 100,
+                week: 1000,
+            },
+        };
+        let elem = |offset| age_elem(&(now - time::Duration::seconds(offset)), now, &date_color);
+
+        assert_eq!(Elem::HourOld, elem(5));
+        assert_eq!(Elem::DayOld, elem(50));
+        assert_eq!(Elem::WeekOld, elem(500));
+        assert_eq!(Elem::Ancient, elem(5000));
+    }
+
+    /// 2023-01-02 15:04:05.000000006, one hour ahead of UTC.
+    fn fixed_time() -> time::Tm {
+        time::Tm {
+            tm_year: 123,
+            tm_mon: 0,
+            tm_mday: 2,
+            tm_hour: 15,
+            tm_min: 4,
+            tm_sec: 5,
+            tm_nsec: 6,
+            tm_utcoff: 3600,
+            ..time::empty_tm()
+        }
+    }
+
+    #[test]
+    fn test_long_iso_time_style() {
+        let argv = vec!["lsd", "--time-style", "long-iso"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        assert_eq!(
+            "2023-01-02 15:04",
+            formatted_string(&fixed_time(), &flags.date, time::now())
+        );
+    }
+
+    #[test]
+    fn test_full_iso_time_style() {
+        let argv = vec!["lsd", "--time-style", "full-iso"];
+        let matches = app::build().get_matches_from_safe(argv).unwrap();
+        let flags = Flags::configure_from(&matches, &Config::with_none()).unwrap();
+        assert_eq!(
+            "2023-01-02 15:04:05.000000006 +0100",
+            formatted_string(&fixed_time(), &flags.date, time::now())
+        );
+    }
+
+    #[test]
+    fn test_iso_time_style_depends_on_the_age() {
+        let date = DateFlag::FormattedByAge("%m-%d %H:%M".to_string(), "%Y-%m-%d ".to_string());
+        let time = fixed_time();
+        assert_eq!(
+            "01-02 15:04",
+            formatted_string(&time, &date, time + time::Duration::days(30))
+        );
+        assert_eq!(
+            "2023-01-02 ",
+            formatted_string(&time, &date, time + time::Duration::days(200))
+        );
+        // The dates in the future are formatted like the old ones.
+        assert_eq!(
+            "2023-01-02 ",
+            formatted_string(&time, &date, time - time::Duration::days(1))
+        );
+    }
 }
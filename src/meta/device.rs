@@ -0,0 +1,137 @@
+use crate::color::{ColoredString, Colors, Elem};
+use crate::meta::FileType;
+use std::fs::Metadata;
+
+/// The major and minor numbers of a character or block special file, which `ls -l` shows in
+/// place of the size of the device nodes.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Default)]
+pub struct Device {
+    numbers: Option<(u64, u64)>,
+}
+
+impl Device {
+    #[cfg(unix)]
+    pub fn new(meta: &Metadata, file_type: FileType) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        match file_type {
+            FileType::CharDevice | FileType::BlockDevice => Self::from_rdev(meta.rdev()),
+            _ => Self::default(),
+        }
+    }
+
+    #[cfg(windows)]
+    pub fn new(_: &Metadata, _: FileType) -> Self {
+        Self::default()
+    }
+
+    /// Split the device ID of `st_rdev` into its major and minor numbers, with the encoding of
+    /// glibc: the minor number takes the lowest 8 bits and bits 20 to 43, and the major number
+    /// takes bits 8 to 19 and 44 to 63.
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn from_rdev(rdev: u64) -> Self {
+        let major = ((rdev >> 8) & 0xfff) | ((rdev >> 32) & 0xffff_f000);
+        let minor = (rdev & 0xff) | ((rdev >> 12) & 0xffff_ff00);
+
+        Self {
+            numbers: Some((major, minor)),
+        }
+    }
+
+    /// Split the device ID of `st_rdev` into its major and minor numbers, with the encoding of
+    /// the BSDs and macOS: the highest 8 bits of the 32 are the major number, and the others are
+    /// the minor number.
+    #[cfg(all(unix, not(any(target_os = "linux", target_os = "android"))))]
+    fn from_rdev(rdev: u64) -> Self {
+        Self {
+            numbers: Some(((rdev >> 24) & 0xff, rdev & 0xff_ffff)),
+        }
+    }
+
+    /// Whether the file is a device node, whose numbers are rendered instead of its size.
+    pub fn is_device(&self) -> bool {
+        self.numbers.is_some()
+    }
+
+    /// The `major, minor` numbers of a device node, or an empty string for the other files.
+    pub fn value_string(&self) -> String {
+        match self.numbers {
+            Some((major, minor)) => format!("{}, {}", major, minor),
+            None => String::new(),
+        }
+    }
+
+    /// Render the numbers like `ls -l` does, as `major, minor` right-aligned to `val_alignment`
+    /// characters. This is empty for the files which are not devices.
+    pub fn render(&self, colors: &Colors, val_alignment: usize) -> ColoredString<'_> {
+        match self.numbers {
+            Some(_) => colors.colorize(
+                format!("{:>1$}", self.value_string(), val_alignment),
+                &Elem::NonFile,
+            ),
+            None => ColoredString::from(""),
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::Device;
+    use crate::color::{Colors, Theme};
+    use crate::meta::FileType;
+    use crate::meta::Permissions;
+    use std::fs::File;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn device_of(path: &Path) -> Device {
+        let metadata = path.symlink_metadata().unwrap();
+        let file_type = FileType::new(&metadata, None, &Permissions::from(&metadata));
+        Device::new(&metadata, file_type)
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_dev_null_numbers() {
+        let device = device_of(Path::new("/dev/null"));
+        assert!(device.is_device());
+        assert_eq!(
+            "1, 3",
+            device.render(&Colors::new(Theme::NoColor), 0).to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    fn test_numbers_from_rdev() {
+        // The device ID of /dev/null, and one with large numbers using all of the encoding.
+        assert_eq!(Some((1, 3)), Device::from_rdev(0x103).numbers);
+        assert_eq!(
+            Some((0x1234_5cde, 0x6789_ab12)),
+            Device::from_rdev(0x1234_5678_9abc_de12).numbers
+        );
+    }
+
+    #[test]
+    fn test_render_aligned() {
+        let device = Device {
+            numbers: Some((8, 1)),
+        };
+        assert_eq!(
+            "  8, 1",
+            device.render(&Colors::new(Theme::NoColor), 6).to_string()
+        );
+    }
+
+    #[test]
+    fn test_regular_file_is_not_a_device() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        let device = device_of(&file_path);
+        assert!(!device.is_device());
+        assert_eq!("", device.value_string());
+    }
+}
@@ -0,0 +1,148 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::fs::Metadata;
+
+/// The symbolic names of the file flags, like `ls -lO` prints them, with their bit in `st_flags`.
+/// The flags set by the users come first, then the ones only the super-user can set.
+const FLAG_NAMES: [(u32, &str); 12] = [
+    (0x0000_0001, "nodump"),
+    (0x0000_0002, "uchg"),
+    (0x0000_0004, "uappnd"),
+    (0x0000_0008, "opaque"),
+    (0x0000_0010, "uunlnk"),
+    (0x0000_0020, "compressed"),
+    (0x0000_8000, "hidden"),
+    (0x0001_0000, "arch"),
+    (0x0002_0000, "schg"),
+    (0x0004_0000, "sappnd"),
+    (0x0008_0000, "restricted"),
+    (0x0010_0000, "sunlnk"),
+];
+
+/// The flags of a file set by `chflags`, which only exist on the BSDs and macOS.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct FileFlags {
+    flags: Option<u32>,
+}
+
+impl From<&Metadata> for FileFlags {
+    #[cfg(target_os = "macos")]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::macos::fs::MetadataExt;
+
+        Self::from_bits(meta.st_flags())
+    }
+
+    #[cfg(target_os = "freebsd")]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::freebsd::fs::MetadataExt;
+
+        Self::from_bits(meta.st_flags())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "freebsd")))]
+    fn from(_: &Metadata) -> Self {
+        Self { flags: None }
+    }
+}
+
+impl FileFlags {
+    #[cfg_attr(
+        not(any(target_os = "macos", target_os = "freebsd", test)),
+        allow(dead_code)
+    )]
+    fn from_bits(flags: u32) -> Self {
+        Self { flags: Some(flags) }
+    }
+
+    /// The names of the flags separated by commas, or `-` when none is set. This is empty on the
+    /// systems without file flags. The flags without a known name are ignored.
+    pub fn value_string(&self) -> String {
+        let flags = match self.flags {
+            Some(flags) => flags,
+            None => return String::new(),
+        };
+
+        let names: Vec<&str> = FLAG_NAMES
+            .iter()
+            .filter(|(bit, _)| flags & bit != 0)
+            .map(|(_, name)| *name)
+            .collect();
+        if names.is_empty() {
+            String::from("-")
+        } else {
+            names.join(",")
+        }
+    }
+
+    /// Render the names of the flags, left-aligned to `val_alignment` characters.
+    pub fn render(&self, colors: &Colors, val_alignment: usize) -> ColoredString<'_> {
+        let value = format!("{:<1$}", self.value_string(), val_alignment);
+
+        colors.colorize(value, &Elem::FileFlags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileFlags;
+    use crate::color::{Colors, Theme};
+
+    #[test]
+    fn test_locked_file() {
+        assert_eq!("uchg", FileFlags::from_bits(0x2).value_string());
+    }
+
+    #[test]
+    fn test_several_flags() {
+        assert_eq!(
+            "uchg,hidden,restricted",
+            FileFlags::from_bits(0x8_0000 | 0x8000 | 0x2).value_string()
+        );
+    }
+
+    #[test]
+    fn test_without_flags() {
+        assert_eq!("-", FileFlags::from_bits(0).value_string());
+        assert_eq!("", FileFlags { flags: None }.value_string());
+    }
+
+    #[test]
+    fn test_render_aligned() {
+        let colors = Colors::new(Theme::NoColor);
+        assert_eq!(
+            "uchg  ",
+            FileFlags::from_bits(0x2).render(&colors, 6).to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn test_locked_file_on_disk() {
+        use std::fs::File;
+        use std::process::Command;
+        use tempfile::tempdir;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        let success = Command::new("chflags")
+            .arg("uchg")
+            .arg(&file_path)
+            .status()
+            .expect("failed to run chflags")
+            .success();
+        assert!(success, "failed to lock the file");
+
+        let flags = FileFlags::from(&file_path.metadata().unwrap());
+
+        // The file must be unlocked for the temporary directory to be removed.
+        Command::new("chflags")
+            .arg("nouchg")
+            .arg(&file_path)
+            .status()
+            .expect("failed to run chflags");
+
+        assert_eq!("uchg", flags.value_string());
+    }
+}
@@ -83,23 +83,66 @@ impl FileType {
     pub fn is_dirlike(self) -> bool {
         matches!(self, FileType::Directory { .. } | FileType::SymLink { is_dir: true })
     }
+
+    /// A stable, lowercase name of the file type, used by the JSON output.
+    pub fn name(self) -> &'static str {
+        match self {
+            FileType::BlockDevice => "block-device",
+            FileType::CharDevice => "char-device",
+            FileType::Directory { .. } => "directory",
+            FileType::File { .. } => "file",
+            FileType::SymLink { .. } => "symlink",
+            FileType::Pipe => "pipe",
+            FileType::Socket => "socket",
+            FileType::Special => "special",
+        }
+    }
 }
 
 impl FileType {
     pub fn render(self, colors: &Colors) -> ColoredString {
+        let symbol = match self {
+            FileType::File { .. } => ".",
+            FileType::Directory { .. } => "d",
+            FileType::Pipe => "|",
+            FileType::SymLink { .. } => "l",
+            FileType::BlockDevice => "b",
+            FileType::CharDevice => "c",
+            FileType::Socket => "s",
+            FileType::Special => "?",
+        };
+
+        colors.colorize(String::from(symbol), &self.elem())
+    }
+
+    /// Render the type like the first character of `ls -l`, e.g. `-` for a file or `p` for a
+    /// pipe, used by the file type block.
+    pub fn render_ls_char(self, colors: &Colors) -> ColoredString<'_> {
+        let symbol = match self {
+            FileType::File { .. } => "-",
+            FileType::Directory { .. } => "d",
+            FileType::Pipe => "p",
+            FileType::SymLink { .. } => "l",
+            FileType::BlockDevice => "b",
+            FileType::CharDevice => "c",
+            FileType::Socket => "s",
+            FileType::Special => "?",
+        };
+
+        colors.colorize(String::from(symbol), &self.elem())
+    }
+
+    /// The element coloring the character of the type.
+    fn elem(self) -> Elem {
         match self {
-            FileType::File { exec, .. } => {
-                colors.colorize(String::from("."), &Elem::File { exec, uid: false })
-            }
-            FileType::Directory { .. } => {
-                colors.colorize(String::from("d"), &Elem::Dir { uid: false })
-            }
-            FileType::Pipe => colors.colorize(String::from("|"), &Elem::Pipe),
-            FileType::SymLink { .. } => colors.colorize(String::from("l"), &Elem::SymLink),
-            FileType::BlockDevice => colors.colorize(String::from("b"), &Elem::BlockDevice),
-            FileType::CharDevice => colors.colorize(String::from("c"), &Elem::CharDevice),
-            FileType::Socket => colors.colorize(String::from("s"), &Elem::Socket),
-            FileType::Special => colors.colorize(String::from("?"), &Elem::Special),
+            FileType::File { exec, .. } => Elem::File { exec, uid: false },
+            FileType::Directory { .. } => Elem::Dir { uid: false },
+            FileType::Pipe => Elem::Pipe,
+            FileType::SymLink { .. } => Elem::SymLink,
+            FileType::BlockDevice => Elem::BlockDevice,
+            FileType::CharDevice => Elem::CharDevice,
+            FileType::Socket => Elem::Socket,
+            FileType::Special => Elem::Special,
         }
     }
 }
@@ -256,4 +299,53 @@ mod test {
 
         assert_eq!(Colour::Fixed(44).paint("s"), file_type.render(&colors));
     }
+
+    #[test]
+    fn test_ls_char_dir() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let meta = Meta::from_path(tmp_dir.path(), false).expect("failed to get tempdir path");
+
+        let colors = Colors::new(Theme::NoLscolors);
+        assert_eq!(
+            Colour::Fixed(33).paint("d"),
+            meta.file_type.render_ls_char(&colors)
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_ls_char_symlink_and_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+        let symlink_path = tmp_dir.path().join("link");
+        symlink(&file_path, &symlink_path).expect("failed to create symlink");
+
+        let colors = Colors::new(Theme::NoColor);
+        let file = Meta::from_path(&file_path, false).expect("failed to get meta");
+        let link = Meta::from_path(&symlink_path, false).expect("failed to get meta");
+
+        assert_eq!("-", file.file_type.render_ls_char(&colors).to_string());
+        assert_eq!("l", link.file_type.render_ls_char(&colors).to_string());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_ls_char_pipe() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let pipe_path = tmp_dir.path().join("pipe");
+        let success = Command::new("mkfifo")
+            .arg(&pipe_path)
+            .status()
+            .expect("failed to exec mkfifo")
+            .success();
+        assert!(success, "failed to exec mkfifo");
+
+        let colors = Colors::new(Theme::NoLscolors);
+        let pipe = Meta::from_path(&pipe_path, false).expect("failed to get meta");
+        assert_eq!(
+            Colour::Fixed(44).paint("p"),
+            pipe.file_type.render_ls_char(&colors)
+        );
+    }
 }
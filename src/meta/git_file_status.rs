@@ -0,0 +1,312 @@
+use crate::color::{ColoredString, Colors, Elem};
+use ansi_term::ANSIStrings;
+use std::path::Path;
+use std::process::Command;
+
+/// The status of a file in either the index or the working tree, as reported by
+/// `git status --porcelain`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum GitStatus {
+    Unmodified,
+    Modified,
+    TypeChange,
+    New,
+    Deleted,
+    Renamed,
+    Copied,
+    Conflicted,
+    Untracked,
+    Ignored,
+}
+
+impl GitStatus {
+    fn from_porcelain(c: char) -> Self {
+        match c {
+            'M' => Self::Modified,
+            'T' => Self::TypeChange,
+            'A' => Self::New,
+            'D' => Self::Deleted,
+            'R' => Self::Renamed,
+            'C' => Self::Copied,
+            'U' => Self::Conflicted,
+            '?' => Self::Untracked,
+            '!' => Self::Ignored,
+            _ => Self::Unmodified,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Self::Unmodified => ".",
+            Self::Modified => "M",
+            Self::TypeChange => "T",
+            Self::New => "A",
+            Self::Deleted => "D",
+            Self::Renamed => "R",
+            Self::Copied => "C",
+            Self::Conflicted => "U",
+            Self::Untracked => "?",
+            Self::Ignored => "!",
+        }
+    }
+}
+
+/// The git status of a file, in the index and in the working tree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GitFileStatus {
+    pub index: GitStatus,
+    pub workdir: GitStatus,
+}
+
+impl Default for GitFileStatus {
+    fn default() -> Self {
+        Self {
+            index: GitStatus::Unmodified,
+            workdir: GitStatus::Unmodified,
+        }
+    }
+}
+
+impl GitFileStatus {
+    fn from_porcelain(index: char, workdir: char) -> Self {
+        Self {
+            index: GitStatus::from_porcelain(index),
+            workdir: GitStatus::from_porcelain(workdir),
+        }
+    }
+
+    /// Merge the status of a file inside a directory into the status of that directory.
+    fn merge(self, other: Self) -> Self {
+        let pick = |current: GitStatus, new: GitStatus| {
+            if current == GitStatus::Unmodified {
+                new
+            } else {
+                current
+            }
+        };
+
+        Self {
+            index: pick(self.index, other.index),
+            workdir: pick(self.workdir, other.workdir),
+        }
+    }
+
+    pub fn render(&self, colors: &Colors) -> ColoredString<'_> {
+        let strings: &[ColoredString] = &[
+            colors.colorize(
+                self.index.symbol().to_string(),
+                &Elem::GitStatus { status: self.index },
+            ),
+            colors.colorize(
+                self.workdir.symbol().to_string(),
+                &Elem::GitStatus {
+                    status: self.workdir,
+                },
+            ),
+        ];
+
+        ColoredString::from(ANSIStrings(strings).to_string())
+    }
+}
+
+/// The git statuses of the entries of a single directory.
+///
+/// `git status` is run once when the cache is created, so looking up the entries of the directory
+/// does not spawn any other process.
+#[derive(Debug, Default)]
+pub struct GitCache {
+    in_repo: bool,
+    /// The status applied to every entry, e.g. when the whole directory is untracked.
+    default: GitFileStatus,
+    /// The paths of the changed files, relative to the directory.
+    statuses: Vec<(String, GitFileStatus)>,
+}
+
+impl GitCache {
+    pub fn new(dir: &Path) -> Self {
+        let prefix = match git_output(dir, &["rev-parse", "--show-prefix"]) {
+            Some(output) => output.trim_end_matches('\n').to_string(),
+            None => return Self::default(),
+        };
+
+        match git_output(
+            dir,
+            &[
+                "status",
+                "--porcelain",
+                "-z",
+                "--ignored",
+                "--untracked-files=normal",
+                "--",
+                ".",
+            ],
+        ) {
+            Some(output) => Self::from_porcelain(&prefix, &output),
+            None => Self::default(),
+        }
+    }
+
+    /// Build the cache from the output of `git status --porcelain -z`, run in the directory whose
+    /// path relative to the root of the repository is `prefix`.
+    fn from_porcelain(prefix: &str, output: &str) -> Self {
+        let mut cache = Self {
+            in_repo: true,
+            ..Self::default()
+        };
+
+        let mut records = output.split('\0');
+        while let Some(record) = records.next() {
+            if record.len() < 4 {
+                continue;
+            }
+
+            let mut chars = record.chars();
+            let status =
+                GitFileStatus::from_porcelain(chars.next().unwrap(), chars.next().unwrap());
+            let path = &record[3..];
+
+            // Renamed and copied files are followed by their original path.
+            if matches!(status.index, GitStatus::Renamed | GitStatus::Copied) {
+                records.next();
+            }
+
+            if path.starts_with(prefix) && path.len() > prefix.len() {
+                let relative = &path[prefix.len()..];
+                cache.statuses.push((relative.to_string(), status));
+                continue;
+            }
+
+            // An untracked or ignored directory containing the listed directory.
+            if path.ends_with('/') && prefix.starts_with(path) {
+                cache.default = status;
+            }
+        }
+
+        cache
+    }
+
+    /// Get the status of the entry at `path`, or `None` if the directory is not in a repository.
+    pub fn get(&self, path: &Path) -> Option<GitFileStatus> {
+        if !self.in_repo {
+            return None;
+        }
+
+        let name = match path.file_name() {
+            Some(name) => name.to_string_lossy(),
+            None => return Some(self.default),
+        };
+
+        let mut result = self.default;
+        for (relative, status) in &self.statuses {
+            if !relative.starts_with(name.as_ref()) {
+                continue;
+            }
+            match &relative[name.len()..] {
+                "" | "/" => return Some(*status),
+                rest if rest.starts_with('/') => result = result.merge(*status),
+                _ => {}
+            }
+        }
+
+        Some(result)
+    }
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .ok()?;
+
+    if output.status.success() {
+        Some(String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{GitCache, GitFileStatus, GitStatus};
+    use crate::color::{Colors, Theme};
+    use std::path::Path;
+
+    fn status(index: GitStatus, workdir: GitStatus) -> Option<GitFileStatus> {
+        Some(GitFileStatus { index, workdir })
+    }
+
+    #[test]
+    fn test_parse_porcelain() {
+        let cache = GitCache::from_porcelain(
+            "",
+            " M modified\0A  new\0R  renamed\0original\0?? untracked/\0!! ignored\0",
+        );
+
+        let get = |name| cache.get(Path::new(name));
+        assert_eq!(
+            status(GitStatus::Unmodified, GitStatus::Modified),
+            get("modified")
+        );
+        assert_eq!(status(GitStatus::New, GitStatus::Unmodified), get("new"));
+        assert_eq!(
+            status(GitStatus::Renamed, GitStatus::Unmodified),
+            get("renamed")
+        );
+        assert_eq!(
+            status(GitStatus::Untracked, GitStatus::Untracked),
+            get("untracked")
+        );
+        assert_eq!(
+            status(GitStatus::Ignored, GitStatus::Ignored),
+            get("ignored")
+        );
+        assert_eq!(
+            status(GitStatus::Unmodified, GitStatus::Unmodified),
+            get("original")
+        );
+    }
+
+    #[test]
+    fn test_directory_merges_its_content() {
+        let cache = GitCache::from_porcelain("src/", " M src/dir/a\0A  src/dir/b\0 M other/c\0");
+
+        assert_eq!(
+            status(GitStatus::New, GitStatus::Modified),
+            cache.get(Path::new("src/dir"))
+        );
+        assert_eq!(
+            status(GitStatus::Unmodified, GitStatus::Unmodified),
+            cache.get(Path::new("src/di"))
+        );
+    }
+
+    #[test]
+    fn test_inside_untracked_directory() {
+        let cache = GitCache::from_porcelain("new/dir/", "?? new/\0");
+
+        assert_eq!(
+            status(GitStatus::Untracked, GitStatus::Untracked),
+            cache.get(Path::new("file"))
+        );
+    }
+
+    #[test]
+    fn test_outside_of_repository() {
+        assert_eq!(None, GitCache::default().get(Path::new("file")));
+    }
+
+    #[test]
+    fn test_render() {
+        let status = GitFileStatus {
+            index: GitStatus::Modified,
+            workdir: GitStatus::Unmodified,
+        };
+
+        assert_eq!(
+            "M.",
+            status.render(&Colors::new(Theme::NoColor)).to_string()
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::path::{Path, PathBuf};
+
+/// The ignore rules of a git repository which apply to the entries of a directory.
+pub struct GitIgnoreMatcher {
+    /// The canonical path of the directory, which the entries are matched relative to.
+    dir: PathBuf,
+    /// The matchers from the most to the least specific one. The `.gitignore` files of the
+    /// deeper directories come first, then `.git/info/exclude`, and the global excludes file of
+    /// git comes last.
+    matchers: Vec<Gitignore>,
+}
+
+impl GitIgnoreMatcher {
+    /// Get the ignore rules applying to the entries of `dir`. This returns [None] when `dir` is
+    /// not inside a git repository.
+    pub fn new(dir: &Path) -> Option<Self> {
+        let dir = dir.canonicalize().ok()?;
+        let root = dir.ancestors().find(|path| path.join(".git").exists())?;
+
+        let mut matchers: Vec<Gitignore> = dir
+            .ancestors()
+            .take_while(|path| path.starts_with(root))
+            .map(|path| path.join(".gitignore"))
+            .filter(|path| path.is_file())
+            .map(|path| Gitignore::new(path).0)
+            .collect();
+
+        let exclude = root.join(".git").join("info").join("exclude");
+        if exclude.is_file() {
+            let mut builder = GitignoreBuilder::new(root);
+            builder.add(exclude);
+            if let Ok(matcher) = builder.build() {
+                matchers.push(matcher);
+            }
+        }
+
+        // The file of the `core.excludesFile` setting, or else `$XDG_CONFIG_HOME/git/ignore`.
+        let (global, _) = GitignoreBuilder::new(root).build_global();
+        matchers.push(global);
+
+        Some(Self { dir, matchers })
+    }
+
+    /// Whether the entry of the directory named `name` is ignored by git.
+    pub fn is_ignored(&self, name: &Path, is_dir: bool) -> bool {
+        let path = self.dir.join(name);
+
+        for matcher in &self.matchers {
+            let matched = matcher.matched(&path, is_dir);
+            if !matched.is_none() {
+                return matched.is_ignore();
+            }
+        }
+
+        false
+    }
+
+    /// Whether the entry of the directory named `name`, or one of the directories it is in, is
+    /// ignored by git. Like git, the entries of an ignored directory are ignored too.
+    pub fn is_ignored_or_in_ignored_dir(&self, name: &Path, is_dir: bool) -> bool {
+        let path = self.dir.join(name);
+
+        for matcher in &self.matchers {
+            let matched = matcher.matched_path_or_any_parents(&path, is_dir);
+            if !matched.is_none() {
+                return matched.is_ignore();
+            }
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GitIgnoreMatcher;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_outside_of_a_repository() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::write(tmp_dir.path().join(".gitignore"), "target/\n").unwrap();
+
+        assert!(GitIgnoreMatcher::new(tmp_dir.path()).is_none());
+    }
+
+    #[test]
+    fn test_nested_ignore_files() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path();
+        fs::create_dir_all(root.join(".git").join("info")).unwrap();
+        fs::write(root.join(".git/info/exclude"), "*.swp\n").unwrap();
+        fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+        fs::create_dir(root.join("sub")).unwrap();
+        fs::write(root.join("sub").join(".gitignore"), "!keep.log\n").unwrap();
+
+        let matcher = GitIgnoreMatcher::new(root).unwrap();
+        assert!(matcher.is_ignored(Path::new("target"), true));
+        assert!(!matcher.is_ignored(Path::new("target"), false));
+        assert!(matcher.is_ignored(Path::new("file.swp"), false));
+        assert!(matcher.is_ignored(Path::new("root.log"), false));
+        assert!(!matcher.is_ignored(Path::new("src"), true));
+
+        let matcher = GitIgnoreMatcher::new(&root.join("sub")).unwrap();
+        assert!(matcher.is_ignored(Path::new("other.log"), false));
+        assert!(!matcher.is_ignored(Path::new("keep.log"), false));
+    }
+
+    #[test]
+    fn test_entries_of_an_ignored_directory() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let root = tmp_dir.path();
+        fs::create_dir(root.join(".git")).unwrap();
+        fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        fs::create_dir_all(root.join("target").join("debug")).unwrap();
+
+        let matcher = GitIgnoreMatcher::new(&root.join("target").join("debug")).unwrap();
+        assert!(!matcher.is_ignored(Path::new("lsd"), false));
+        assert!(matcher.is_ignored_or_in_ignored_dir(Path::new("lsd"), false));
+
+        let matcher = GitIgnoreMatcher::new(root).unwrap();
+        assert!(matcher.is_ignored_or_in_ignored_dir(Path::new("target"), true));
+        assert!(!matcher.is_ignored_or_in_ignored_dir(Path::new("src"), true));
+    }
+}
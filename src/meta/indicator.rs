@@ -1,20 +1,20 @@
 use crate::color::ColoredString;
-use crate::flags::Flags;
+use crate::flags::{Flags, IndicatorKind};
 use crate::meta::FileType;
 use ansi_term::ANSIString;
 
 #[derive(Clone, Debug)]
-pub struct Indicator(&'static str);
+pub struct Indicator(Option<IndicatorKind>);
 
 impl From<FileType> for Indicator {
     fn from(file_type: FileType) -> Self {
         let res = match file_type {
-            FileType::Directory { .. } => "/",
-            FileType::File { exec: true, .. } => "*",
-            FileType::Pipe => "|",
-            FileType::Socket => "=",
-            FileType::SymLink { .. } => "@",
-            _ => "",
+            FileType::Directory { .. } => Some(IndicatorKind::Dir),
+            FileType::File { exec: true, .. } => Some(IndicatorKind::Exec),
+            FileType::Pipe => Some(IndicatorKind::Pipe),
+            FileType::Socket => Some(IndicatorKind::Socket),
+            FileType::SymLink { .. } => Some(IndicatorKind::SymLink),
+            _ => None,
         };
 
         Indicator(res)
@@ -23,10 +23,11 @@ impl From<FileType> for Indicator {
 
 impl Indicator {
     pub fn render(&self, flags: &Flags) -> ColoredString {
-        if flags.display_indicators.0 {
-            ANSIString::from(self.0)
-        } else {
-            ANSIString::from("")
+        match self.0 {
+            Some(kind) if flags.display_indicators.0 => {
+                ANSIString::from(flags.indicator_chars.get(kind).to_string())
+            }
+            _ => ANSIString::from(""),
         }
     }
 }
@@ -34,7 +35,7 @@ impl Indicator {
 #[cfg(test)]
 mod test {
     use super::Indicator;
-    use crate::flags::{Flags, Indicators};
+    use crate::flags::{Flags, IndicatorKind, Indicators};
     use crate::meta::FileType;
 
     #[test]
@@ -95,4 +96,25 @@ mod test {
 
         assert_eq!("", file_type.render(&flags).to_string().as_str());
     }
+
+    #[test]
+    fn test_only_executable_indicator() {
+        let mut flags = Flags {
+            display_indicators: Indicators(true),
+            ..Flags::default()
+        };
+        flags
+            .indicator_chars
+            .0
+            .retain(|kind, _| *kind == IndicatorKind::Exec);
+
+        let file_type = Indicator::from(FileType::File {
+            uid: false,
+            exec: true,
+        });
+        assert_eq!("*", file_type.render(&flags).to_string().as_str());
+
+        let file_type = Indicator::from(FileType::Directory { uid: false });
+        assert_eq!("", file_type.render(&flags).to_string().as_str());
+    }
 }
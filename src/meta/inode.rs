@@ -23,19 +23,33 @@ impl<'a> From<&'a Metadata> for INode {
 }
 
 impl INode {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
+    pub fn value_string(&self) -> String {
         match self.index {
-            Some(i) => colors.colorize(i.to_string(), &Elem::INode { valid: true }),
-            None => colors.colorize(String::from("-"), &Elem::INode { valid: false }),
+            Some(i) => i.to_string(),
+            None => String::from("-"),
         }
     }
+
+    /// Render the inode number, right-aligned to `val_alignment` characters.
+    pub fn render(&self, colors: &Colors, val_alignment: usize) -> ColoredString<'_> {
+        let value = format!("{:>1$}", self.value_string(), val_alignment);
+
+        colors.colorize(
+            value,
+            &Elem::INode {
+                valid: self.index.is_some(),
+            },
+        )
+    }
 }
 
 #[cfg(test)]
 #[cfg(unix)]
 mod tests {
     use super::INode;
+    use crate::color::{Colors, Theme};
     use std::env;
+    use std::fs;
     use std::io;
     use std::path::Path;
     use std::process::{Command, ExitStatus};
@@ -59,4 +73,36 @@ mod tests {
         #[cfg(windows)]
         assert!(inode.index.is_none());
     }
+
+    #[test]
+    fn test_inode_hard_links() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        let link_path = tmp_dir.path().join("link");
+        let other_path = tmp_dir.path().join("other");
+
+        assert!(cross_platform_touch(&file_path).unwrap().success());
+        assert!(cross_platform_touch(&other_path).unwrap().success());
+        fs::hard_link(&file_path, &link_path).expect("failed to create hard link");
+
+        let inode = INode::from(&file_path.metadata().unwrap());
+        let link_inode = INode::from(&link_path.metadata().unwrap());
+        let other_inode = INode::from(&other_path.metadata().unwrap());
+
+        assert_eq!(inode, link_inode);
+        assert_ne!(inode, other_inode);
+    }
+
+    #[test]
+    fn test_inode_render_right_aligned() {
+        let colors = Colors::new(Theme::NoColor);
+        let inode = INode { index: Some(42) };
+
+        assert_eq!("42", inode.render(&colors, 0).to_string());
+        assert_eq!("   42", inode.render(&colors, 5).to_string());
+        assert_eq!(
+            "    -",
+            INode { index: None }.render(&colors, 5).to_string()
+        );
+    }
 }
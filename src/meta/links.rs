@@ -0,0 +1,89 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::fs::Metadata;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct Links {
+    nlink: Option<u64>,
+}
+
+impl From<&Metadata> for Links {
+    #[cfg(unix)]
+    fn from(meta: &Metadata) -> Self {
+        use std::os::unix::fs::MetadataExt;
+
+        let nlink = meta.nlink();
+
+        Self { nlink: Some(nlink) }
+    }
+
+    #[cfg(windows)]
+    fn from(_: &Metadata) -> Self {
+        Self { nlink: None }
+    }
+}
+
+impl Links {
+    pub fn value_string(&self) -> String {
+        match self.nlink {
+            Some(nlink) => nlink.to_string(),
+            None => String::from("-"),
+        }
+    }
+
+    /// Render the number of hard links, right-aligned to `val_alignment` characters.
+    pub fn render(&self, colors: &Colors, val_alignment: usize) -> ColoredString<'_> {
+        let value = format!("{:>1$}", self.value_string(), val_alignment);
+
+        colors.colorize(
+            value,
+            &Elem::Links {
+                valid: self.nlink.is_some(),
+            },
+        )
+    }
+}
+
+#[cfg(test)]
+#[cfg(unix)]
+mod tests {
+    use super::Links;
+    use crate::color::{Colors, Theme};
+    use std::fs::{self, File};
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_hard_links_file() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        let links = Links::from(&file_path.metadata().unwrap());
+        assert_eq!(Some(1), links.nlink);
+
+        fs::hard_link(&file_path, tmp_dir.path().join("link")).expect("failed to create link");
+
+        let links = Links::from(&file_path.metadata().unwrap());
+        assert_eq!(Some(2), links.nlink);
+    }
+
+    #[test]
+    fn test_hard_links_directory() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+
+        let links = Links::from(&dir_path.metadata().unwrap());
+        assert!(links.nlink.unwrap() >= 2);
+    }
+
+    #[test]
+    fn test_hard_links_render_right_aligned() {
+        let colors = Colors::new(Theme::NoColor);
+
+        assert_eq!(
+            "  2",
+            Links { nlink: Some(2) }.render(&colors, 3).to_string()
+        );
+        assert_eq!("  -", Links { nlink: None }.render(&colors, 3).to_string());
+    }
+}
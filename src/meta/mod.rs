@@ -1,33 +1,61 @@
+mod access_control;
+#[cfg(windows)]
+mod attributes;
 mod date;
+mod device;
+mod file_flags;
 mod filetype;
+mod git_file_status;
+mod git_ignore;
 mod indicator;
 mod inode;
+mod links;
 pub mod name;
 mod owner;
 mod permissions;
 mod size;
 mod symlink;
+mod xattr;
 
 #[cfg(windows)]
 mod windows_utils;
 
+pub use self::access_control::AccessControl;
+#[cfg(windows)]
+pub use self::attributes::Attributes;
 pub use self::date::Date;
+pub use self::device::Device;
+pub use self::file_flags::FileFlags;
 pub use self::filetype::FileType;
+pub use self::git_file_status::{GitCache, GitFileStatus, GitStatus};
+pub use self::git_ignore::GitIgnoreMatcher;
 pub use self::indicator::Indicator;
 pub use self::inode::INode;
+pub use self::links::Links;
 pub use self::name::Name;
 pub use self::owner::Owner;
 pub use self::permissions::Permissions;
 pub use self::size::Size;
 pub use self::symlink::SymLink;
+pub use self::xattr::XAttrs;
 pub use crate::icon::Icons;
 
-use crate::flags::{Display, Flags, Layout};
+use crate::core::ExitCode;
+use crate::flags::{Block, DereferenceColumn, DereferenceColumns, Display, Flags, Layout};
 use crate::print_error;
+use crate::progress;
 
-use std::fs::read_link;
+use std::collections::HashSet;
+use std::fs::{read_link, Metadata, ReadDir};
 use std::io::{Error, ErrorKind};
 use std::path::{Component, Path, PathBuf};
+use std::thread;
+
+/// The maximum number of threads getting the metadata of the entries of a directory.
+const MAX_THREADS: usize = 8;
+/// The number of entries below which a directory is read by a single thread, as starting the
+/// threads would take longer than reading it.
+const PARALLEL_THRESHOLD: usize = 64;
 
 #[derive(Clone, Debug)]
 pub struct Meta {
@@ -38,10 +66,39 @@ pub struct Meta {
     pub owner: Owner,
     pub file_type: FileType,
     pub size: Size,
+    pub device: Device,
     pub symlink: SymLink,
     pub indicator: Indicator,
     pub inode: INode,
+    pub links: Links,
+    pub git_status: Option<GitFileStatus>,
+    pub xattrs: XAttrs,
+    pub access_control: AccessControl,
+    pub file_flags: FileFlags,
+    #[cfg(windows)]
+    pub attributes: Attributes,
     pub content: Option<Vec<Meta>>,
+    /// Whether the entry is a directory which is also one of its ancestors, whose content is not
+    /// listed again.
+    pub cycle: bool,
+}
+
+/// The device and the inode of a directory, which identify it even when it is reached through
+/// different paths.
+type DirKey = (u64, u64);
+
+#[cfg(unix)]
+fn dir_key(path: &Path) -> Option<DirKey> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = path.metadata().ok()?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// The inodes are not available on Windows, where the cycles are not detected.
+#[cfg(not(unix))]
+fn dir_key(_: &Path) -> Option<DirKey> {
+    None
 }
 
 impl Meta {
@@ -49,6 +106,18 @@ impl Meta {
         &self,
         depth: usize,
         flags: &Flags,
+    ) -> Result<Option<Vec<Meta>>, std::io::Error> {
+        let mut ancestors: Vec<DirKey> = dir_key(&self.path).into_iter().collect();
+        self.recurse_into_with(depth, flags, &mut ancestors)
+    }
+
+    /// Get the content of the directory like [recurse_into](Meta::recurse_into), where
+    /// `ancestors` are the keys of the directories being listed above it, including itself.
+    fn recurse_into_with(
+        &self,
+        depth: usize,
+        flags: &Flags,
+        ancestors: &mut Vec<DirKey>,
     ) -> Result<Option<Vec<Meta>>, std::io::Error> {
         if depth == 0 {
             return Ok(None);
@@ -68,31 +137,90 @@ impl Meta {
             _ => return Ok(None),
         }
 
-        let entries = match self.path.read_dir() {
-            Ok(entries) => entries,
+        let entries = match self.read_dir() {
+            Some(entries) => entries,
+            None => return Ok(None),
+        };
+
+        let mut content: Vec<Meta> = Vec::new();
+        self.list_entries(entries, depth, flags, ancestors, usize::MAX, &mut |entry| {
+            content.push(entry)
+        })?;
+
+        Ok(Some(content))
+    }
+
+    /// Open the directory to list its entries. When it can not be read, the error is reported
+    /// and this returns [None].
+    pub fn read_dir(&self) -> Option<ReadDir> {
+        match self.path.read_dir() {
+            Ok(entries) => Some(entries),
             Err(err) => {
                 print_error!("lsd: {}: {}\n", self.path.display(), err);
-                return Ok(None);
+                ExitCode::MajorIssue.set_if_greater();
+                None
             }
+        }
+    }
+
+    /// Pass the entries of the directory to `emit` one at a time, in directory order, without
+    /// recursing into them. The metadata is read `chunk_size` entries at a time, so that the first
+    /// entries of a large directory can be displayed before the last ones are read.
+    pub fn stream_entries<F: FnMut(Meta)>(
+        &self,
+        entries: ReadDir,
+        flags: &Flags,
+        chunk_size: usize,
+        mut emit: F,
+    ) -> Result<(), Error> {
+        let mut ancestors: Vec<DirKey> = dir_key(&self.path).into_iter().collect();
+        self.list_entries(entries, 1, flags, &mut ancestors, chunk_size, &mut emit)
+    }
+
+    /// Pass the `entries` of the directory to `emit`, with their content down to `depth`, reading
+    /// the metadata `chunk_size` entries at a time.
+    fn list_entries(
+        &self,
+        entries: ReadDir,
+        depth: usize,
+        flags: &Flags,
+        ancestors: &mut Vec<DirKey>,
+        chunk_size: usize,
+        emit: &mut dyn FnMut(Meta),
+    ) -> Result<(), Error> {
+        let git_cache = if flags.blocks.0.contains(&Block::GitStatus) {
+            Some(GitCache::new(&self.path))
+        } else {
+            None
         };
 
-        let mut content: Vec<Meta> = Vec::new();
+        let git_ignore = if flags.git_ignore.0 || flags.dim_ignored.0 {
+            GitIgnoreMatcher::new(&self.path)
+        } else {
+            None
+        };
 
-        if let Display::All = flags.display {
+        // In a tree, the directories are already the parents of their entries.
+        if flags.display == Display::All && flags.layout != Layout::Tree {
             let mut current_meta;
 
             current_meta = self.clone();
             current_meta.name.name = ".".to_owned();
 
-            let parent_meta =
+            let mut parent_meta =
                 Self::from_path(&self.path.join(Component::ParentDir), flags.dereference.0)?;
+            parent_meta.name.name = "..".to_owned();
 
-            content.push(current_meta);
-            content.push(parent_meta);
+            emit(current_meta);
+            emit(parent_meta);
         }
 
+        let mut paths = Vec::new();
+        // The entries ignored by git which are listed with a dimmed name.
+        let mut ignored = HashSet::new();
         for entry in entries {
-            let path = entry?.path();
+            let entry = entry?;
+            let path = entry.path();
 
             let name = path
                 .file_name()
@@ -102,75 +230,267 @@ impl Meta {
                 continue;
             }
 
+            // The hidden names are listed with the dot files, by "all" and "almost-all".
             if let Display::DisplayOnlyVisible = flags.display {
-                if name.to_string_lossy().starts_with('.') {
+                if name.to_string_lossy().starts_with('.') || flags.hide.0.is_match(name) {
                     continue;
                 }
             }
 
-            let mut entry_meta = match Self::from_path(&path, flags.dereference.0) {
-                Ok(res) => res,
-                Err(err) => {
-                    print_error!("lsd: {}: {}\n", path.display(), err);
+            if let Some(git_ignore) = &git_ignore {
+                let is_dir = matches!(entry.file_type(), Ok(file_type) if file_type.is_dir());
+                if flags.git_ignore.0 && git_ignore.is_ignored(Path::new(name), is_dir) {
                     continue;
                 }
-            };
+                // The entries of an ignored directory, which are only listed by --gitignore when
+                // the directory is given as an argument, are dimmed too.
+                if flags.dim_ignored.0
+                    && git_ignore.is_ignored_or_in_ignored_dir(Path::new(name), is_dir)
+                {
+                    ignored.insert(path.clone());
+                }
+            }
 
-            match entry_meta.recurse_into(depth - 1, &flags) {
-                Ok(content) => entry_meta.content = content,
-                Err(err) => {
-                    print_error!("lsd: {}: {}\n", path.display(), err);
-                    continue;
+            paths.push(path);
+        }
+
+        for chunk in paths.chunks(chunk_size.max(1)) {
+            let entry_metas = Self::from_paths(chunk, flags.dereference.0);
+            progress::entries_read(chunk.len());
+            for (path, entry_meta) in chunk.iter().zip(entry_metas) {
+                let mut entry_meta = match entry_meta {
+                    Ok(res) => res,
+                    Err(err) => {
+                        print_error!("lsd: {}: {}\n", path.display(), err);
+                        ExitCode::MajorIssue.set_if_greater();
+                        continue;
+                    }
+                };
+
+                // On Windows, the files with the hidden attribute are hidden like the dot files.
+                #[cfg(windows)]
+                {
+                    if let Display::DisplayOnlyVisible = flags.display {
+                        if entry_meta.attributes.is_hidden() {
+                            continue;
+                        }
+                    }
+                }
+
+                if let Some(cache) = &git_cache {
+                    entry_meta.git_status = cache.get(path);
                 }
-            };
 
-            content.push(entry_meta);
+                entry_meta.name.git_ignored = ignored.contains(path);
+
+                if flags.blocks.0.contains(&Block::XAttr) {
+                    entry_meta.xattrs = XAttrs::from(path.as_path());
+                }
+
+                if flags.blocks.0.contains(&Block::Context) {
+                    entry_meta.access_control = AccessControl::from(path.as_path());
+                }
+
+                let key = if entry_meta.file_type.is_dirlike() {
+                    dir_key(path)
+                } else {
+                    None
+                };
+                if let (FileType::Directory { .. }, Some((device, _)), Some((parent_device, _))) =
+                    (entry_meta.file_type, key, ancestors.last())
+                {
+                    entry_meta.name.mount_point = device != *parent_device;
+                }
+
+                // A directory which is also one of its ancestors, through a link or a bind mount,
+                // would be listed endlessly. It is marked and not recursed into instead.
+                let key = key.filter(|_| depth > 1);
+                if let Some(key) = key {
+                    if ancestors.contains(&key) {
+                        entry_meta.cycle = true;
+                        emit(entry_meta);
+                        continue;
+                    }
+                    ancestors.push(key);
+                }
+
+                let entry_content = entry_meta.recurse_into_with(depth - 1, flags, ancestors);
+                if key.is_some() {
+                    ancestors.pop();
+                }
+                match entry_content {
+                    Ok(content) => entry_meta.content = content,
+                    Err(err) => {
+                        print_error!("lsd: {}: {}\n", path.display(), err);
+                        ExitCode::MajorIssue.set_if_greater();
+                        continue;
+                    }
+                };
+
+                emit(entry_meta);
+            }
         }
 
-        Ok(Some(content))
+        Ok(())
+    }
+
+    /// Get the metas of the paths, in the same order. The paths of a large directory are split
+    /// between several threads, since getting the metadata is slow on network file systems.
+    fn from_paths(paths: &[PathBuf], dereference: bool) -> Vec<Result<Self, Error>> {
+        let threads = available_threads().min(MAX_THREADS);
+        Self::from_paths_on_threads(paths, dereference, threads)
+    }
+
+    fn from_paths_on_threads(
+        paths: &[PathBuf],
+        dereference: bool,
+        threads: usize,
+    ) -> Vec<Result<Self, Error>> {
+        if threads < 2 || paths.len() < PARALLEL_THRESHOLD {
+            return paths
+                .iter()
+                .map(|path| Self::from_path(path, dereference))
+                .collect();
+        }
+
+        let chunk_size = (paths.len() + threads - 1) / threads;
+        let handles: Vec<_> = paths
+            .chunks(chunk_size)
+            .map(|chunk| {
+                let chunk = chunk.to_vec();
+                thread::spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|path| Self::from_path(path, dereference))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("failed to get the metadata"))
+            .collect()
+    }
+
+    /// Replace the sizes of the directories by the total size of their content, which adds up the
+    /// sizes allocated on the disk instead of the apparent sizes when `block_usage` is set.
+    pub fn calculate_total_size(&mut self, block_usage: bool) {
+        self.calculate_total_size_with(&mut HashSet::new(), block_usage);
     }
 
-    pub fn calculate_total_size(&mut self) {
+    /// Computes the recursive size of the directories, and returns the number of bytes the entry
+    /// adds to the total size of its parent. Symbolic links are never followed, and hard linked
+    /// files already in `seen` are not counted twice.
+    fn calculate_total_size_with(
+        &mut self,
+        seen: &mut HashSet<(u64, u64)>,
+        block_usage: bool,
+    ) -> u64 {
         if let FileType::Directory { .. } = self.file_type {
             if let Some(metas) = &mut self.content {
                 let mut size_accumulated = self.size.get_bytes();
-                for x in &mut metas.iter_mut() {
-                    x.calculate_total_size();
-                    size_accumulated += x.size.get_bytes();
+                for x in metas.iter_mut().filter(|x| !x.is_implied_dir()) {
+                    size_accumulated += x.calculate_total_size_with(seen, block_usage);
                 }
                 self.size = Size::new(size_accumulated);
+
+                for x in metas.iter_mut().filter(|x| x.is_implied_dir()) {
+                    if x.name.name == "." {
+                        x.size = Size::new(size_accumulated);
+                    } else {
+                        x.calculate_total_size(block_usage);
+                    }
+                }
             } else {
                 // possibility that 'depth' limited the recursion in 'recurse_into'
-                self.size = Size::new(Meta::calculate_total_file_size(&self.path));
+                self.size = Size::new(Meta::calculate_total_file_size(
+                    &self.path,
+                    seen,
+                    block_usage,
+                ));
+            }
+            self.size.get_bytes()
+        } else {
+            match self.path.symlink_metadata() {
+                Ok(metadata) if !Meta::is_first_link(&metadata, seen) => 0,
+                _ => self.size.get_bytes(),
             }
         }
     }
 
-    fn calculate_total_file_size(path: &PathBuf) -> u64 {
-        let metadata = if read_link(&path).is_ok() {
-            // If the file is a link, retrieve the metadata without following
-            // the link.
-            path.symlink_metadata()
-        } else {
-            path.metadata()
-        };
-        let metadata = match metadata {
+    /// Display the number of entries of the directories in the size column, for the entry and
+    /// the content listed below it.
+    pub fn count_dir_entries(&mut self) {
+        if let FileType::Directory { .. } = self.file_type {
+            self.size.count_entries(&self.path);
+        }
+
+        if let Some(metas) = &mut self.content {
+            for meta in metas.iter_mut() {
+                meta.count_dir_entries();
+            }
+        }
+    }
+
+    /// Whether the entry is one of the implied `.` and `..` directories.
+    pub fn is_implied_dir(&self) -> bool {
+        self.name.name == "." || self.path.ends_with(Component::ParentDir)
+    }
+
+    /// The combined size of the entries, counting the hard links to the same file once, like
+    /// `du`. The size of the directories is their own size, or their total size once computed.
+    pub fn total_bytes(metas: &[Meta]) -> u64 {
+        let mut seen = HashSet::new();
+        metas
+            .iter()
+            .map(|meta| match meta.file_type {
+                FileType::Directory { .. } => meta.size.get_bytes(),
+                _ => match meta.path.symlink_metadata() {
+                    Ok(metadata) if !Meta::is_first_link(&metadata, &mut seen) => 0,
+                    _ => meta.size.get_bytes(),
+                },
+            })
+            .sum()
+    }
+
+    /// Whether the file has not been seen yet, recording it in `seen` if it has other hard links.
+    #[cfg(unix)]
+    fn is_first_link(metadata: &Metadata, seen: &mut HashSet<(u64, u64)>) -> bool {
+        use std::os::unix::fs::MetadataExt;
+
+        metadata.nlink() <= 1 || seen.insert((metadata.dev(), metadata.ino()))
+    }
+
+    #[cfg(windows)]
+    fn is_first_link(_: &Metadata, _: &mut HashSet<(u64, u64)>) -> bool {
+        true
+    }
+
+    fn calculate_total_file_size(
+        path: &Path,
+        seen: &mut HashSet<(u64, u64)>,
+        block_usage: bool,
+    ) -> u64 {
+        // Retrieve the metadata without following the links, so that link loops can not cause
+        // an infinite recursion.
+        let metadata = match path.symlink_metadata() {
             Ok(meta) => meta,
             Err(err) => {
                 print_error!("lsd: {}: {}\n", path.display(), err);
+                ExitCode::MajorIssue.set_if_greater();
                 return 0;
             }
         };
         let file_type = metadata.file_type();
-        if file_type.is_file() {
-            metadata.len()
-        } else if file_type.is_dir() {
-            let mut size = metadata.len();
+        if file_type.is_dir() {
+            let mut size = Meta::file_size(&metadata, block_usage);
 
             let entries = match path.read_dir() {
                 Ok(entries) => entries,
                 Err(err) => {
                     print_error!("lsd: {}: {}\n", path.display(), err);
+                    ExitCode::MajorIssue.set_if_greater();
                     return size;
                 }
             };
@@ -179,23 +499,113 @@ impl Meta {
                     Ok(entry) => entry.path(),
                     Err(err) => {
                         print_error!("lsd: {}: {}\n", path.display(), err);
+                        ExitCode::MajorIssue.set_if_greater();
                         continue;
                     }
                 };
-                size += Meta::calculate_total_file_size(&path);
+                progress::entries_read(1);
+                size += Meta::calculate_total_file_size(&path, seen, block_usage);
             }
             size
+        } else if Meta::is_first_link(&metadata, seen) {
+            Meta::file_size(&metadata, block_usage)
         } else {
             0
         }
     }
 
+    /// Mark the directory as a mount point when it is on another device than its parent. The
+    /// entries found when recursing are compared to their parent directory instead.
+    pub fn detect_mount_point(&mut self) {
+        if let FileType::Directory { .. } = self.file_type {
+            if let (Some((device, _)), Some((parent_device, _))) = (
+                dir_key(&self.path),
+                dir_key(&self.path.join(Component::ParentDir)),
+            ) {
+                self.name.mount_point = device != parent_device;
+            }
+        }
+    }
+
+    fn file_size(metadata: &Metadata, block_usage: bool) -> u64 {
+        if block_usage {
+            Size::allocated_bytes(metadata)
+        } else {
+            metadata.len()
+        }
+    }
+
+    /// Show the size and the date of the file a link points to in the columns which dereference
+    /// it, for the entry and the content listed below it. The broken links keep their own, and
+    /// the file type of the target is only picked when it is displayed.
+    pub fn dereference_columns(&mut self, columns: &DereferenceColumns) {
+        if let FileType::SymLink { .. } = self.file_type {
+            if let Ok(target) = self.path.metadata() {
+                if columns.contains(DereferenceColumn::Size) {
+                    self.size = Size::from(&target);
+                }
+                if columns.contains(DereferenceColumn::Date) {
+                    self.date = Date::from(&target);
+                }
+            }
+        }
+
+        if let Some(metas) = &mut self.content {
+            for meta in metas.iter_mut() {
+                meta.dereference_columns(columns);
+            }
+        }
+    }
+
+    /// The file type shown by the permission and the filetype blocks, which is the one of the
+    /// target of a link when the file type column dereferences it.
+    pub fn column_file_type(&self, flags: &Flags) -> FileType {
+        match self.symlink.target_type() {
+            Some(file_type)
+                if flags
+                    .dereference_columns
+                    .contains(DereferenceColumn::FileType) =>
+            {
+                file_type
+            }
+            _ => self.file_type,
+        }
+    }
+
+    /// Display the sizes allocated on the disk instead of the apparent sizes, for the entry and
+    /// the content listed below it.
+    pub fn use_block_usage(&mut self) {
+        self.size.use_allocated();
+
+        if let Some(metas) = &mut self.content {
+            for meta in metas.iter_mut() {
+                meta.use_block_usage();
+            }
+        }
+    }
+
     pub fn from_path(path: &Path, dereference: bool) -> Result<Self, std::io::Error> {
         // If the file is a link then retrieve link metadata instead with target metadata (if present).
-        let (metadata, symlink_meta) = if read_link(path).is_ok() && !dereference {
+        let is_link = read_link(path).is_ok();
+        let mut broken_dereference = false;
+        let (metadata, symlink_meta) = if is_link && !dereference {
             (path.symlink_metadata()?, path.metadata().ok())
         } else {
-            (path.metadata()?, None)
+            match path.metadata() {
+                Ok(metadata) => (metadata, None),
+                // A broken link can not be dereferenced, so the link itself is listed instead.
+                Err(err) if is_link => {
+                    print_error!(
+                        "lsd: {}: cannot dereference the broken link: {}\n",
+                        path.display(),
+                        err
+                    );
+                    ExitCode::MinorIssue.set_if_greater();
+                    broken_dereference = true;
+                    (path.symlink_metadata()?, None)
+                }
+                Err(err) => return Err(err),
+            }
         };
 
         #[cfg(unix)]
@@ -209,19 +619,268 @@ impl Meta {
         let file_type = FileType::new(&metadata, symlink_meta.as_ref(), &permissions);
         let name = Name::new(&path, file_type);
         let inode = INode::from(&metadata);
+        let links = Links::from(&metadata);
 
         Ok(Self {
             inode,
+            links,
             path: path.to_path_buf(),
-            symlink: SymLink::from(path),
-            size: Size::from(&metadata),
+            // A dereferenced link is shown as its target, without the arrow pointing to it.
+            symlink: if dereference && !broken_dereference {
+                SymLink::default()
+            } else {
+                SymLink::from(path)
+            },
+            size: if broken_dereference {
+                Size::unknown()
+            } else {
+                Size::from(&metadata)
+            },
+            device: Device::new(&metadata, file_type),
             date: Date::from(&metadata),
             indicator: Indicator::from(file_type),
             owner,
             permissions,
             name,
             file_type,
+            git_status: None,
+            xattrs: XAttrs::default(),
+            access_control: AccessControl::default(),
+            file_flags: FileFlags::from(&metadata),
+            #[cfg(windows)]
+            attributes: Attributes::from(&metadata),
             content: None,
+            cycle: false,
         })
     }
 }
+
+/// The number of processors online, which the metadata can be got on at the same time.
+#[cfg(unix)]
+fn available_threads() -> usize {
+    let processors = unsafe { libc::sysconf(libc::_SC_NPROCESSORS_ONLN) };
+    if processors < 1 {
+        1
+    } else {
+        processors as usize
+    }
+}
+
+/// The number of processors, which Windows gives in the environment.
+#[cfg(windows)]
+fn available_threads() -> usize {
+    std::env::var("NUMBER_OF_PROCESSORS")
+        .ok()
+        .and_then(|processors| processors.parse().ok())
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Meta;
+    use crate::flags::Flags;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    #[cfg(unix)]
+    fn create_nested_fixture(root: &std::path::Path) -> u64 {
+        use std::os::unix::fs::symlink;
+
+        File::create(root.join("a"))
+            .unwrap()
+            .write_all(&[0; 10])
+            .unwrap();
+        fs::create_dir(root.join("sub")).unwrap();
+        File::create(root.join("sub/b"))
+            .unwrap()
+            .write_all(&[0; 20])
+            .unwrap();
+        fs::hard_link(root.join("sub/b"), root.join("sub/hard")).unwrap();
+        symlink("..", root.join("sub/loop")).unwrap();
+
+        let len = |path: &str| root.join(path).symlink_metadata().unwrap().len();
+        len("") + len("sub") + len("sub/loop") + 10 + 20
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_total_size_of_listed_content() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let expected = create_nested_fixture(tmp_dir.path());
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        meta.content = meta.recurse_into(3, &Flags::default()).unwrap();
+        meta.calculate_total_size(false);
+
+        assert_eq!(expected, meta.size.get_bytes());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_total_size_beyond_recursion_depth() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let expected = create_nested_fixture(tmp_dir.path());
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        meta.calculate_total_size(false);
+
+        assert_eq!(expected, meta.size.get_bytes());
+    }
+
+    #[test]
+    fn test_from_paths_matches_the_serial_metas() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut paths = Vec::new();
+        for index in 0..(super::PARALLEL_THRESHOLD * 4 + 3) {
+            let path = tmp_dir.path().join(format!("file{}", index));
+            fs::write(&path, "x".repeat(index)).expect("failed to write file");
+            paths.push(path);
+        }
+        // The errors keep their place among the metas.
+        paths.insert(10, tmp_dir.path().join("missing"));
+
+        let metas = Meta::from_paths_on_threads(&paths, false, 4);
+
+        assert_eq!(paths.len(), metas.len());
+        for (path, meta) in paths.iter().zip(metas) {
+            match (Meta::from_path(path, false), meta) {
+                (Ok(expected), Ok(meta)) => {
+                    assert_eq!(expected.path, meta.path);
+                    assert_eq!(expected.size.get_bytes(), meta.size.get_bytes());
+                }
+                (Err(_), Err(_)) => {}
+                _ => panic!("{} differs from the serial meta", path.display()),
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_recurse_into_stops_at_a_link_to_an_ancestor() {
+        use crate::flags::{Layout, Recursion};
+        use std::os::unix::fs::symlink;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let nested = tmp_dir.path().join("a").join("b");
+        fs::create_dir_all(&nested).unwrap();
+        symlink("../..", nested.join("up")).unwrap();
+
+        let flags = Flags {
+            layout: Layout::Tree,
+            recursion: Recursion {
+                enabled: true,
+                depth: usize::MAX,
+            },
+            ..Flags::default()
+        };
+        let meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        let content = meta.recurse_into(usize::MAX, &flags).unwrap().unwrap();
+
+        let b = &content[0].content.as_ref().unwrap()[0];
+        let up = &b.content.as_ref().unwrap()[0];
+        assert_eq!("up", up.name.name);
+        assert!(up.cycle);
+        assert!(up.content.is_none());
+        assert!(!b.cycle);
+    }
+
+    #[test]
+    fn test_detect_mount_point_of_an_ordinary_directory() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+
+        let mut meta = Meta::from_path(&dir_path, false).unwrap();
+        meta.detect_mount_point();
+        assert!(!meta.name.mount_point);
+
+        let content = Meta::from_path(tmp_dir.path(), false)
+            .unwrap()
+            .recurse_into(1, &Flags::default())
+            .unwrap()
+            .unwrap();
+        assert!(!content[0].name.mount_point);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_detect_mount_point_of_proc() {
+        use std::os::unix::fs::MetadataExt;
+        use std::path::Path;
+
+        let device = |path: &str| Path::new(path).metadata().map(|meta| meta.dev()).ok();
+        if device("/proc").is_none() || device("/proc") == device("/") {
+            // The proc file system is not mounted.
+            return;
+        }
+
+        let mut meta = Meta::from_path(Path::new("/proc"), false).unwrap();
+        meta.detect_mount_point();
+        assert!(meta.name.mount_point);
+
+        let content = Meta::from_path(Path::new("/"), false)
+            .unwrap()
+            .recurse_into(1, &Flags::default())
+            .unwrap()
+            .unwrap();
+        let proc = content
+            .iter()
+            .find(|meta| meta.name.name == "proc")
+            .unwrap();
+        assert!(proc.name.mount_point);
+        let etc = content.iter().find(|meta| meta.name.name == "etc");
+        assert!(!matches!(etc, Some(etc) if etc.name.mount_point));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_total_bytes_counts_hard_links_once() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path)
+            .unwrap()
+            .write_all(&[0; 1024 * 1024])
+            .unwrap();
+        fs::hard_link(&file_path, tmp_dir.path().join("hard")).unwrap();
+        File::create(tmp_dir.path().join("other"))
+            .unwrap()
+            .write_all(&[0; 10])
+            .unwrap();
+
+        let mut meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        let content = meta.recurse_into(1, &Flags::default()).unwrap().unwrap();
+        let files: Vec<Meta> = content
+            .into_iter()
+            .filter(|meta| !meta.is_implied_dir())
+            .collect();
+        assert_eq!(1024 * 1024 + 10, Meta::total_bytes(&files));
+
+        meta.calculate_total_size(false);
+        let dir_len = tmp_dir.path().symlink_metadata().unwrap().len();
+        assert_eq!(dir_len + 1024 * 1024 + 10, meta.size.get_bytes());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_dereference_columns_of_a_link() {
+        use crate::flags::{DereferenceColumn, DereferenceColumns};
+        use std::os::unix::fs::symlink;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        fs::write(tmp_dir.path().join("big"), [0; 1000]).unwrap();
+        symlink("big", tmp_dir.path().join("link")).unwrap();
+        symlink("missing", tmp_dir.path().join("broken")).unwrap();
+
+        let columns = DereferenceColumns(vec![DereferenceColumn::Size]);
+        let mut link = Meta::from_path(&tmp_dir.path().join("link"), false).unwrap();
+        link.dereference_columns(&columns);
+        assert_eq!(1000, link.size.get_bytes());
+        assert!(matches!(link.file_type, super::FileType::SymLink { .. }));
+
+        // A broken link keeps its own size, which is the length of its target.
+        let mut broken = Meta::from_path(&tmp_dir.path().join("broken"), false).unwrap();
+        broken.dereference_columns(&columns);
+        assert_eq!(7, broken.size.get_bytes());
+    }
+}
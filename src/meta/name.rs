@@ -1,9 +1,11 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::display::{DIRED_END, DIRED_NAME_START};
+use crate::flags::{Display, Flags, HyperlinkOption, Layout, QuotingStyle};
 use crate::icon::Icons;
 use crate::meta::filetype::FileType;
 use std::cmp::{Ordering, PartialOrd};
-use std::ffi::OsStr;
 use std::path::{Component, Path, PathBuf};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 #[derive(Debug)]
 pub enum DisplayOption<'a> {
@@ -18,6 +20,10 @@ pub struct Name {
     path: PathBuf,
     extension: Option<String>,
     file_type: FileType,
+    /// Whether the entry is a directory on another device than its parent.
+    pub mount_point: bool,
+    /// Whether the entry is ignored by git, when the ignored entries are dimmed.
+    pub git_ignored: bool,
 }
 
 impl Name {
@@ -36,14 +42,15 @@ impl Name {
             path: PathBuf::from(path),
             extension,
             file_type,
+            mount_point: false,
+            git_ignored: false,
         }
     }
 
+    /// The name of the entry, which is the last component of its path, or the path itself when
+    /// it has none. The implied `.` and `..` directories are named after the component instead.
     pub fn file_name(&self) -> &str {
-        self.path
-            .file_name()
-            .and_then(OsStr::to_str)
-            .unwrap_or(&self.name)
+        &self.name
     }
 
     fn relative_path<T: AsRef<Path> + Clone>(&self, base_path: T) -> PathBuf {
@@ -96,30 +103,92 @@ impl Name {
         }
     }
 
+    /// Quote the string according to the `style`. Control characters are escaped in any case, so
+    /// the quoted name always fits on a single line.
+    pub fn quote(&self, string: &str, style: QuotingStyle) -> String {
+        match style {
+            QuotingStyle::Literal => self.escape(string),
+            QuotingStyle::Shell if !needs_shell_quotes(string) => self.escape(string),
+            QuotingStyle::Shell | QuotingStyle::ShellAlways => {
+                format!("'{}'", self.escape(&string.replace('\'', "'\\''")))
+            }
+            QuotingStyle::C => {
+                let mut chars = String::from("\"");
+                for c in string.chars() {
+                    match c {
+                        '\x07' => chars += "\\a",
+                        '\x08' => chars += "\\b",
+                        '\t' => chars += "\\t",
+                        '\n' => chars += "\\n",
+                        '\x0b' => chars += "\\v",
+                        '\x0c' => chars += "\\f",
+                        '\r' => chars += "\\r",
+                        '"' => chars += "\\\"",
+                        '\\' => chars += "\\\\",
+                        c if c < 0x20 as char || c == 0x7f as char => {
+                            chars += &format!("\\{:03o}", c as u32)
+                        }
+                        c => chars.push(c),
+                    }
+                }
+                chars.push('"');
+                chars
+            }
+        }
+    }
+
+    /// Truncate the string to the number of columns of the "truncate-names" flag, if it is wider,
+    /// with an ellipsis as its last column. The names are kept whole in the tree layout.
+    fn truncate(string: String, flags: &Flags) -> String {
+        match flags.truncate_names.0 {
+            Some(width) if flags.layout != Layout::Tree && string.width() > width => {
+                let mut truncated = String::new();
+                let mut truncated_width = 0;
+                for c in string.chars() {
+                    truncated_width += c.width().unwrap_or(0);
+                    if truncated_width >= width {
+                        break;
+                    }
+                    truncated.push(c);
+                }
+                truncated.push('…');
+                truncated
+            }
+            _ => string,
+        }
+    }
+
     pub fn render(
         &self,
         colors: &Colors,
         icons: &Icons,
         display_option: &DisplayOption,
+        flags: &Flags,
     ) -> ColoredString {
-        let content = match display_option {
-            DisplayOption::FileName => {
-                format!("{}{}", icons.get(self), self.escape(self.file_name()))
-            }
-            DisplayOption::Relative { base_path } => format!(
-                "{}{}",
-                icons.get(self),
-                self.escape(&self.relative_path(base_path).to_string_lossy())
+        let name = match display_option {
+            DisplayOption::FileName => self.quote(self.file_name(), flags.quoting_style),
+            DisplayOption::Relative { base_path } => self.quote(
+                &self.relative_path(base_path).to_string_lossy(),
+                flags.quoting_style,
             ),
-            DisplayOption::None => format!(
-                "{}{}",
+            DisplayOption::None => self.quote(&self.path.to_string_lossy(), flags.quoting_style),
+        };
+        let name = Self::truncate(name, flags);
+        let content = if flags.dired.0 {
+            format!(
+                "{}{}{}{}",
                 icons.get(self),
-                self.escape(&self.path.to_string_lossy())
-            ),
+                DIRED_NAME_START,
+                name,
+                DIRED_END
+            )
+        } else {
+            format!("{}{}", icons.get(self), name)
         };
 
         let elem = match self.file_type {
             FileType::CharDevice => Elem::CharDevice,
+            FileType::Directory { .. } if self.mount_point => Elem::MountPoint,
             FileType::Directory { uid } => Elem::Dir { uid },
             FileType::SymLink { .. } => Elem::SymLink,
             FileType::File { uid, exec } => Elem::File { uid, exec },
@@ -129,7 +198,40 @@ impl Name {
             },
         };
 
-        colors.colorize_using_path(content, &self.path, &elem)
+        let name = if self.git_ignored {
+            colors.colorize_ignored(content)
+        } else if self.is_hidden() && flags.display != Display::DisplayOnlyVisible {
+            colors.colorize_hidden_using_path(content, &self.path, &elem)
+        } else {
+            colors.colorize_using_path(content, &self.path, &elem)
+        };
+
+        match flags.hyperlink {
+            HyperlinkOption::Never => name,
+            _ => ColoredString::from(format!(
+                "\x1B]8;;{}\x1B\\{}\x1B]8;;\x1B\\",
+                self.hyperlink_target(flags),
+                name
+            )),
+        }
+    }
+
+    /// The `file://` url of the file, used as the target of the OSC 8 hyperlink. The relative
+    /// paths are resolved against the working directory of the flags.
+    fn hyperlink_target(&self, flags: &Flags) -> String {
+        let path = match &flags.current_dir {
+            Some(dir) if !self.path.is_absolute() => dir.join(&self.path),
+            _ => self.path.clone(),
+        };
+
+        format!("file://{}", percent_encode(&path))
+    }
+
+    /// Whether the entry is a dotfile, which is only listed with `--all` or `--almost-all`. The
+    /// implied `.` and `..` directories are not dotfiles.
+    pub fn is_hidden(&self) -> bool {
+        let name = self.file_name();
+        name.starts_with('.') && name != "." && name != ".."
     }
 
     pub fn extension(&self) -> Option<&str> {
@@ -141,6 +243,36 @@ impl Name {
     }
 }
 
+/// Percent-encode the bytes of the path outside of the unreserved characters of the urls, keeping
+/// the `/` separators.
+fn percent_encode(path: &Path) -> String {
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        path.as_os_str().as_bytes().to_vec()
+    };
+    #[cfg(not(unix))]
+    let bytes = path.to_string_lossy().replace('\\', "/").into_bytes();
+
+    bytes
+        .iter()
+        .map(|&byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Whether the string has to be quoted to be used as a single shell word.
+fn needs_shell_quotes(string: &str) -> bool {
+    string.is_empty()
+        || string
+            .chars()
+            .any(|c| !c.is_alphanumeric() && !"%+,-./:=@_".contains(c))
+}
+
 impl Ord for Name {
     fn cmp(&self, other: &Self) -> Ordering {
         self.name.to_lowercase().cmp(&other.name.to_lowercase())
@@ -166,6 +298,9 @@ mod test {
     use super::DisplayOption;
     use super::Name;
     use crate::color::{self, Colors};
+    use crate::flags::{
+        DimIgnored, Display, Flags, HyperlinkOption, Layout, QuotingStyle, TruncateNames,
+    };
     use crate::icon::{self, Icons};
     use crate::meta::FileType;
     use crate::meta::Meta;
@@ -180,6 +315,7 @@ mod test {
     #[cfg(unix)]
     use std::process::Command;
     use tempfile::tempdir;
+    use unicode_width::UnicodeWidthStr;
 
     #[test]
     #[cfg(unix)] // Windows uses different default permissions
@@ -198,7 +334,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -216,7 +352,28 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(33).paint(" directory"),
-            meta.name.render(&colors, &icons, &DisplayOption::FileName)
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+    }
+
+    #[test]
+    fn test_print_mount_point_name() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::Fancy);
+
+        let dir_path = tmp_dir.path().join("mnt");
+        fs::create_dir(&dir_path).expect("failed to create the dir");
+        let mut meta = Meta::from_path(&dir_path, false).unwrap();
+        meta.name.mount_point = true;
+
+        // The color of LS_COLORS for the directories does not apply to the mount points.
+        let colors = Colors::new(color::Theme::Default);
+
+        assert_eq!(
+            Colour::Fixed(39).paint("\u{f115} mnt"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -244,7 +401,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(44).paint(" target.tmp"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -272,7 +429,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(44).paint(" target.d"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -298,7 +455,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" pipe.tmp"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
     }
 
@@ -317,12 +474,159 @@ mod test {
         assert_eq!(
             "file.txt",
             meta.name
-                .render(&colors, &icons, &DisplayOption::FileName)
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
                 .to_string()
                 .as_str()
         );
     }
 
+    #[test]
+    fn test_print_name_with_icon_spacing() {
+        let name = Name::new(
+            Path::new("file.txt"),
+            FileType::File {
+                uid: false,
+                exec: false,
+            },
+        );
+        let colors = Colors::new(color::Theme::NoColor);
+        let render = |spacing| {
+            let icons = Icons::new(icon::Theme::Fancy).with_spacing(spacing);
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+                .to_string()
+        };
+
+        let single = render(1);
+        let double = render(2);
+        assert_eq!(single.len() + 1, double.len());
+        assert_eq!(single.replacen(' ', "  ", 1), double);
+        assert_eq!(
+            UnicodeWidthStr::width(single.as_str()) + 1,
+            UnicodeWidthStr::width(double.as_str())
+        );
+    }
+
+    #[test]
+    fn test_print_hidden_file_name() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon);
+
+        let file_path = tmp_dir.path().join(".bashrc");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let colors = Colors::new(color::Theme::NoLscolors);
+        assert_eq!(
+            Colour::Fixed(184).paint(".bashrc"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+
+        let mut flags = Flags {
+            display: Display::All,
+            ..Flags::default()
+        };
+        assert_eq!(
+            Colour::Fixed(245).paint(".bashrc"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+        );
+
+        flags.display = Display::AlmostAll;
+        assert_eq!(
+            Colour::Fixed(245).paint(".bashrc"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+        );
+    }
+
+    #[test]
+    fn test_print_git_ignored_name() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon);
+        fs::create_dir(tmp_dir.path().join(".git")).expect("failed to create the dir");
+        fs::write(tmp_dir.path().join(".gitignore"), "*.log\n").expect("failed to write");
+        File::create(tmp_dir.path().join("build.log")).expect("failed to create file");
+        File::create(tmp_dir.path().join("main.rs")).expect("failed to create file");
+
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let flags = Flags {
+            dim_ignored: DimIgnored(true),
+            ..Flags::default()
+        };
+        let meta = Meta::from_path(tmp_dir.path(), false).unwrap();
+        let content = meta.recurse_into(1, &flags).unwrap().unwrap();
+        let mut names: Vec<_> = content
+            .iter()
+            .map(|meta| {
+                meta.name
+                    .render(&colors, &icons, &DisplayOption::FileName, &flags)
+            })
+            .collect();
+        names.sort_by_key(|name| name.to_string());
+
+        assert_eq!(
+            vec![
+                Colour::Fixed(240).dimmed().paint("build.log"),
+                Colour::Fixed(184).paint("main.rs"),
+            ],
+            names
+        );
+    }
+
+    #[test]
+    fn test_print_hidden_dir_name() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon);
+
+        let dir_path = tmp_dir.path().join(".config");
+        fs::create_dir(&dir_path).expect("failed to create the dir");
+        let meta = Meta::from_path(&dir_path, false).unwrap();
+
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let flags = Flags {
+            display: Display::All,
+            ..Flags::default()
+        };
+
+        assert_eq!(
+            Colour::Fixed(33).dimmed().paint(".config"),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+        );
+    }
+
+    #[test]
+    fn test_print_implied_dir_names() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon);
+        fs::create_dir(tmp_dir.path().join("dir")).expect("failed to create the dir");
+
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let flags = Flags {
+            display: Display::All,
+            ..Flags::default()
+        };
+        let meta = Meta::from_path(&tmp_dir.path().join("dir"), false).unwrap();
+        let content = meta.recurse_into(1, &flags).unwrap().unwrap();
+        let names: Vec<String> = content
+            .iter()
+            .map(|meta| {
+                meta.name
+                    .render(&colors, &icons, &DisplayOption::FileName, &flags)
+                    .to_string()
+            })
+            .collect();
+
+        assert_eq!(
+            vec![
+                Colour::Fixed(33).paint(".").to_string(),
+                Colour::Fixed(33).paint("..").to_string()
+            ],
+            names
+        );
+    }
+
     #[test]
     fn test_extensions_with_valid_file() {
         let path = Path::new("some-file.txt");
@@ -533,7 +837,7 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file\\ttab.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
         );
 
         let file_path = tmp_dir.path().join("file\nnewline.txt");
@@ -546,7 +850,241 @@ mod test {
 
         assert_eq!(
             Colour::Fixed(184).paint(" file\\nnewline.txt"),
-            name.render(&colors, &icons, &DisplayOption::FileName)
+            name.render(&colors, &icons, &DisplayOption::FileName, &Flags::default())
+        );
+    }
+
+    #[test]
+    fn test_print_hyperlink_always() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon);
+
+        // Create the file;
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let colors = Colors::new(color::Theme::NoColor);
+        let flags = Flags {
+            hyperlink: HyperlinkOption::Always,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            format!(
+                "\x1B]8;;file://{}\x1B\\file.txt\x1B]8;;\x1B\\",
+                file_path.to_string_lossy()
+            ),
+            meta.name
+                .render(&colors, &icons, &DisplayOption::FileName, &flags)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_print_hyperlink_wraps_colors() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let icons = Icons::new(icon::Theme::NoIcon);
+
+        // Create the file;
+        let file_path = tmp_dir.path().join("file.txt");
+        File::create(&file_path).expect("failed to create file");
+        let meta = Meta::from_path(&file_path, false).unwrap();
+
+        let colors = Colors::new(color::Theme::NoLscolors);
+        let flags = Flags {
+            hyperlink: HyperlinkOption::Always,
+            ..Default::default()
+        };
+
+        let output = meta
+            .name
+            .render(&colors, &icons, &DisplayOption::FileName, &flags)
+            .to_string();
+
+        assert!(output.starts_with("\x1B]8;;file://"));
+        assert!(output.contains("\x1B\\\x1B[38;5;"));
+        assert!(output.ends_with("\x1B[0m\x1B]8;;\x1B\\"));
+    }
+
+    #[cfg(unix)]
+    fn render_hyperlink_target(file_name: &str) -> String {
+        let name = Name::new(
+            Path::new(file_name),
+            FileType::File {
+                uid: false,
+                exec: false,
+            },
+        );
+        let flags = Flags {
+            hyperlink: HyperlinkOption::Always,
+            current_dir: Some(PathBuf::from("/home/user")),
+            ..Default::default()
+        };
+
+        name.hyperlink_target(&flags)
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hyperlink_target_resolved_against_the_current_dir() {
+        assert_eq!(
+            "file:///home/user/file.txt",
+            render_hyperlink_target("file.txt")
+        );
+        assert_eq!(
+            "file:///tmp/file.txt",
+            render_hyperlink_target("/tmp/file.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hyperlink_target_with_space() {
+        assert_eq!(
+            "file:///home/user/a%20b.txt",
+            render_hyperlink_target("a b.txt")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hyperlink_target_with_hash() {
+        assert_eq!(
+            "file:///home/user/%23notes%3F%25",
+            render_hyperlink_target("#notes?%")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_hyperlink_target_with_non_ascii() {
+        assert_eq!(
+            "file:///home/user/caf%C3%A9",
+            render_hyperlink_target("café")
+        );
+    }
+
+    fn render_quoted(file_name: &str, style: QuotingStyle) -> String {
+        let name = Name::new(
+            Path::new(file_name),
+            FileType::File {
+                uid: false,
+                exec: false,
+            },
+        );
+        let flags = Flags {
+            quoting_style: style,
+            ..Default::default()
+        };
+
+        name.render(
+            &Colors::new(color::Theme::NoColor),
+            &Icons::new(icon::Theme::NoIcon),
+            &DisplayOption::FileName,
+            &flags,
+        )
+        .to_string()
+    }
+
+    #[test]
+    fn test_quoting_style_literal() {
+        assert_eq!("a b.txt", render_quoted("a b.txt", QuotingStyle::Literal));
+        assert_eq!("a\\nb", render_quoted("a\nb", QuotingStyle::Literal));
+    }
+
+    #[test]
+    fn test_quoting_style_shell() {
+        assert_eq!("'a b.txt'", render_quoted("a b.txt", QuotingStyle::Shell));
+        assert_eq!("ab.txt", render_quoted("ab.txt", QuotingStyle::Shell));
+        assert_eq!("'it'\\''s'", render_quoted("it's", QuotingStyle::Shell));
+        assert_eq!("'$HOME'", render_quoted("$HOME", QuotingStyle::Shell));
+    }
+
+    #[test]
+    fn test_quoting_style_shell_always() {
+        assert_eq!(
+            "'a b.txt'",
+            render_quoted("a b.txt", QuotingStyle::ShellAlways)
+        );
+        assert_eq!(
+            "'ab.txt'",
+            render_quoted("ab.txt", QuotingStyle::ShellAlways)
+        );
+    }
+
+    #[test]
+    fn test_quoting_style_c() {
+        assert_eq!("\"a b.txt\"", render_quoted("a b.txt", QuotingStyle::C));
+        assert_eq!("\"a\\nb\\tc\"", render_quoted("a\nb\tc", QuotingStyle::C));
+        assert_eq!("\"a\\033b\"", render_quoted("a\x1bb", QuotingStyle::C));
+        assert_eq!("\"\\\"q\\\\\"", render_quoted("\"q\\", QuotingStyle::C));
+    }
+
+    #[test]
+    fn test_quoting_happens_before_icons_and_colors() {
+        let name = Name::new(
+            Path::new("a b.txt"),
+            FileType::File {
+                uid: false,
+                exec: false,
+            },
+        );
+        let flags = Flags {
+            quoting_style: QuotingStyle::Shell,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Colour::Fixed(184).paint("\u{f15c} 'a b.txt'"),
+            name.render(
+                &Colors::new(color::Theme::NoLscolors),
+                &Icons::new(icon::Theme::Fancy),
+                &DisplayOption::FileName,
+                &flags,
+            )
+        );
+    }
+
+    #[test]
+    fn test_truncate_long_name() {
+        let long_name = "a".repeat(45) + ".txt";
+        let flags = Flags {
+            truncate_names: TruncateNames(Some(20)),
+            ..Flags::default()
+        };
+
+        let truncated = Name::truncate(long_name.clone(), &flags);
+        assert_eq!("a".repeat(19) + "…", truncated);
+        assert_eq!(20, truncated.width());
+
+        assert_eq!(
+            long_name,
+            Name::truncate(long_name.clone(), &Flags::default())
+        );
+        assert_eq!(
+            "short.txt",
+            Name::truncate(String::from("short.txt"), &flags)
+        );
+
+        let flags = Flags {
+            layout: Layout::Tree,
+            ..flags
+        };
+        assert_eq!(long_name, Name::truncate(long_name.clone(), &flags));
+    }
+
+    #[test]
+    fn test_truncate_wide_name() {
+        let flags = Flags {
+            truncate_names: TruncateNames(Some(6)),
+            ..Flags::default()
+        };
+
+        // The wide characters take 2 columns each, so only 2 of them fit before the ellipsis.
+        assert_eq!(
+            "日本…",
+            Name::truncate(String::from("日本語の名前"), &flags)
         );
     }
 }
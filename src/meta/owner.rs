@@ -1,17 +1,46 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
+use std::collections::HashMap;
 #[cfg(unix)]
 use std::fs::Metadata;
+use std::sync::Mutex;
 
+/// The user and group owning a file. On unix the names are only looked up when they are
+/// displayed, so that nothing is looked up when the numeric ids are requested.
 #[derive(Clone, Debug)]
 pub struct Owner {
-    user: String,
-    group: String,
+    user: Id,
+    group: Id,
+}
+
+/// A user or a group, known either by its numeric id or directly by its name.
+#[derive(Clone, Debug)]
+enum Id {
+    #[cfg_attr(windows, allow(dead_code))]
+    Numeric(u32),
+    #[cfg_attr(unix, allow(dead_code))]
+    Name(String),
+}
+
+impl Id {
+    /// The name of the id as found by `lookup`, or the numeric id itself when `numeric` is set or
+    /// when the lookup fails.
+    fn name_with<F: Fn(u32) -> Option<String>>(&self, numeric: bool, lookup: F) -> String {
+        match self {
+            Self::Numeric(id) if numeric => id.to_string(),
+            Self::Numeric(id) => lookup(*id).unwrap_or_else(|| id.to_string()),
+            Self::Name(name) => name.clone(),
+        }
+    }
 }
 
 impl Owner {
     #[cfg_attr(unix, allow(dead_code))]
     pub fn new(user: String, group: String) -> Self {
-        Self { user, group }
+        Self {
+            user: Id::Name(user),
+            group: Id::Name(group),
+        }
     }
 }
 
@@ -19,28 +48,148 @@ impl Owner {
 impl<'a> From<&'a Metadata> for Owner {
     fn from(meta: &Metadata) -> Self {
         use std::os::unix::fs::MetadataExt;
-        use users::{get_group_by_gid, get_user_by_uid};
 
-        let user = match get_user_by_uid(meta.uid()) {
-            Some(res) => res.name().to_string_lossy().to_string(),
-            None => meta.uid().to_string(),
+        Self {
+            user: Id::Numeric(meta.uid()),
+            group: Id::Numeric(meta.gid()),
+        }
+    }
+}
+
+impl Owner {
+    pub fn user(&self, flags: &Flags) -> String {
+        self.user
+            .name_with(flags.numeric_uid_gid.0, lookup_user_name)
+    }
+
+    pub fn group(&self, flags: &Flags) -> String {
+        self.group
+            .name_with(flags.numeric_uid_gid.0, lookup_group_name)
+    }
+
+    pub fn render_user(&self, colors: &Colors, flags: &Flags) -> ColoredString<'_> {
+        colors.colorize(self.user(flags), &Elem::User)
+    }
+
+    pub fn render_group(&self, colors: &Colors, flags: &Flags) -> ColoredString<'_> {
+        colors.colorize(self.group(flags), &Elem::Group)
+    }
+}
+
+/// The names found for the ids, including the failed lookups, so that each id is looked up at
+/// most once per run. The files of a directory have the same owner most of the time, and a lookup
+/// can be slow when the names come from the network.
+struct NameCache(Mutex<HashMap<u32, Option<String>>>);
+
+impl NameCache {
+    fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+
+    /// The cached name of the id, found by `lookup` the first time.
+    fn get_or_lookup<F: Fn(u32) -> Option<String>>(&self, id: u32, lookup: F) -> Option<String> {
+        // The lock is held during the lookup, so that concurrent callers do not look up the same
+        // id twice.
+        let mut names = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        names.entry(id).or_insert_with(|| lookup(id)).clone()
+    }
+}
+
+lazy_static! {
+    #[cfg_attr(not(unix), allow(dead_code))]
+    static ref USER_NAMES: NameCache = NameCache::new();
+    #[cfg_attr(not(unix), allow(dead_code))]
+    static ref GROUP_NAMES: NameCache = NameCache::new();
+}
+
+#[cfg(unix)]
+fn lookup_user_name(uid: u32) -> Option<String> {
+    USER_NAMES.get_or_lookup(uid, |uid| {
+        users::get_user_by_uid(uid).map(|user| user.name().to_string_lossy().to_string())
+    })
+}
+
+#[cfg(unix)]
+fn lookup_group_name(gid: u32) -> Option<String> {
+    GROUP_NAMES.get_or_lookup(gid, |gid| {
+        users::get_group_by_gid(gid).map(|group| group.name().to_string_lossy().to_string())
+    })
+}
+
+#[cfg(not(unix))]
+fn lookup_user_name(_: u32) -> Option<String> {
+    None
+}
+
+#[cfg(not(unix))]
+fn lookup_group_name(_: u32) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Id, NameCache};
+
+    use std::cell::Cell;
+
+    #[test]
+    fn test_numeric_id_is_not_looked_up() {
+        let lookups = Cell::new(0);
+        let lookup = |_| {
+            lookups.set(lookups.get() + 1);
+            Some(String::from("user"))
         };
 
-        let group = match get_group_by_gid(meta.gid()) {
-            Some(res) => res.name().to_string_lossy().to_string(),
-            None => meta.gid().to_string(),
+        assert_eq!("1000", Id::Numeric(1000).name_with(true, lookup));
+        assert_eq!(0, lookups.get());
+    }
+
+    #[test]
+    fn test_id_is_looked_up() {
+        let lookup = |id| match id {
+            1000 => Some(String::from("user")),
+            _ => None,
         };
 
-        Self { user, group }
+        assert_eq!("user", Id::Numeric(1000).name_with(false, lookup));
     }
-}
 
-impl Owner {
-    pub fn render_user(&self, colors: &Colors) -> ColoredString {
-        colors.colorize(self.user.clone(), &Elem::User)
+    #[test]
+    fn test_failed_lookup_falls_back_to_numeric_id() {
+        assert_eq!("4242", Id::Numeric(4242).name_with(false, |_| None));
+    }
+
+    #[test]
+    fn test_name_is_kept() {
+        assert_eq!(
+            "user",
+            Id::Name(String::from("user")).name_with(true, |_| None)
+        );
     }
 
-    pub fn render_group(&self, colors: &Colors) -> ColoredString {
-        colors.colorize(self.group.clone(), &Elem::Group)
+    #[test]
+    fn test_name_cache_looks_up_each_id_once() {
+        let cache = NameCache::new();
+        let lookups = Cell::new(0);
+        let lookup = |id| {
+            lookups.set(lookups.get() + 1);
+            match id {
+                1000 => Some(String::from("user")),
+                _ => None,
+            }
+        };
+
+        for _ in 0..100 {
+            assert_eq!(
+                Some(String::from("user")),
+                cache.get_or_lookup(1000, lookup)
+            );
+        }
+        assert_eq!(1, lookups.get());
+
+        for _ in 0..100 {
+            assert_eq!(None, cache.get_or_lookup(4242, lookup));
+        }
+        assert_eq!(2, lookups.get());
     }
 }
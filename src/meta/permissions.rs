@@ -1,4 +1,6 @@
 use crate::color::{ColoredString, Colors, Elem};
+use crate::flags::Flags;
+use crate::meta::FileType;
 use ansi_term::ANSIStrings;
 use std::fs::Metadata;
 
@@ -55,49 +57,104 @@ impl<'a> From<&'a Metadata> for Permissions {
 }
 
 impl Permissions {
-    pub fn render(&self, colors: &Colors) -> ColoredString {
-        let bit = |bit, chr: &'static str, elem: &Elem| {
+    /// Render the permissions like `rwxr-xr-x`, each symbol colored by its meaning with the
+    /// elements of the theme. When the writable files are highlighted, the permissions of those
+    /// are all colored with the [writable_elem](Permissions::writable_elem).
+    pub fn render(&self, colors: &Colors, file_type: FileType, flags: &Flags) -> ColoredString<'_> {
+        let highlight = if flags.writable_color.0 {
+            self.writable_elem(file_type)
+        } else {
+            None
+        };
+
+        let bit = |bit, chr: &'static str, elem: Elem| {
             if bit {
-                colors.colorize(String::from(chr), elem)
+                (chr, elem)
             } else {
-                colors.colorize(String::from("-"), &Elem::NoAccess)
+                ("-", Elem::NoAccess)
             }
         };
 
-        let strings: &[ColoredString] = &[
+        let symbols = [
             // User permissions
-            bit(self.user_read, "r", &Elem::Read),
-            bit(self.user_write, "w", &Elem::Write),
+            bit(self.user_read, "r", Elem::Read),
+            bit(self.user_write, "w", Elem::Write),
             match (self.user_execute, self.setuid) {
-                (false, false) => colors.colorize(String::from("-"), &Elem::NoAccess),
-                (true, false) => colors.colorize(String::from("x"), &Elem::Exec),
-                (false, true) => colors.colorize(String::from("S"), &Elem::ExecSticky),
-                (true, true) => colors.colorize(String::from("s"), &Elem::ExecSticky),
+                (false, false) => ("-", Elem::NoAccess),
+                (true, false) => ("x", Elem::Exec),
+                (false, true) => ("S", Elem::ExecSticky),
+                (true, true) => ("s", Elem::ExecSticky),
             },
             // Group permissions
-            bit(self.group_read, "r", &Elem::Read),
-            bit(self.group_write, "w", &Elem::Write),
+            bit(self.group_read, "r", Elem::Read),
+            bit(self.group_write, "w", Elem::Write),
             match (self.group_execute, self.setgid) {
-                (false, false) => colors.colorize(String::from("-"), &Elem::NoAccess),
-                (true, false) => colors.colorize(String::from("x"), &Elem::Exec),
-                (false, true) => colors.colorize(String::from("S"), &Elem::ExecSticky),
-                (true, true) => colors.colorize(String::from("s"), &Elem::ExecSticky),
+                (false, false) => ("-", Elem::NoAccess),
+                (true, false) => ("x", Elem::Exec),
+                (false, true) => ("S", Elem::ExecSticky),
+                (true, true) => ("s", Elem::ExecSticky),
             },
             // Other permissions
-            bit(self.other_read, "r", &Elem::Read),
-            bit(self.other_write, "w", &Elem::Write),
+            bit(self.other_read, "r", Elem::Read),
+            bit(self.other_write, "w", Elem::Write),
             match (self.other_execute, self.sticky) {
-                (false, false) => colors.colorize(String::from("-"), &Elem::NoAccess),
-                (true, false) => colors.colorize(String::from("x"), &Elem::Exec),
-                (false, true) => colors.colorize(String::from("T"), &Elem::ExecSticky),
-                (true, true) => colors.colorize(String::from("t"), &Elem::ExecSticky),
+                (false, false) => ("-", Elem::NoAccess),
+                (true, false) => ("x", Elem::Exec),
+                (false, true) => ("T", Elem::ExecSticky),
+                (true, true) => ("t", Elem::ExecSticky),
             },
         ];
 
-        let res = ANSIStrings(strings).to_string();
+        let strings: Vec<ColoredString> = symbols
+            .iter()
+            .map(|(chr, elem)| {
+                colors.colorize(String::from(*chr), highlight.as_ref().unwrap_or(elem))
+            })
+            .collect();
+
+        let res = ANSIStrings(&strings).to_string();
         ColoredString::from(res)
     }
 
+    /// The element highlighting the permissions of a file writable by its group or by the others.
+    /// The directories writable by the others without the sticky bit, in which anyone can delete
+    /// the files of the others, stand out the most.
+    pub fn writable_elem(&self, file_type: FileType) -> Option<Elem> {
+        let is_dir = matches!(file_type, FileType::Directory { .. });
+
+        if is_dir && self.other_write && !self.sticky {
+            Some(Elem::OtherWritableDir)
+        } else if self.other_write {
+            Some(Elem::OtherWritable)
+        } else if self.group_write {
+            Some(Elem::GroupWritable)
+        } else {
+            None
+        }
+    }
+
+    pub fn render_octal(&self, colors: &Colors) -> ColoredString<'_> {
+        colors.colorize(format!("{:04o}", self.octal()), &Elem::Octal)
+    }
+
+    /// The permission bits as a number, e.g. `0o755` for `rwxr-xr-x`.
+    pub fn octal(&self) -> u32 {
+        let bit = |bit: bool, value: u32| if bit { value } else { 0 };
+
+        bit(self.setuid, 0o4000)
+            | bit(self.setgid, 0o2000)
+            | bit(self.sticky, 0o1000)
+            | bit(self.user_read, 0o400)
+            | bit(self.user_write, 0o200)
+            | bit(self.user_execute, 0o100)
+            | bit(self.group_read, 0o040)
+            | bit(self.group_write, 0o020)
+            | bit(self.group_execute, 0o010)
+            | bit(self.other_read, 0o004)
+            | bit(self.other_write, 0o002)
+            | bit(self.other_execute, 0o001)
+    }
+
     pub fn is_executable(&self) -> bool {
         self.user_execute || self.group_execute || self.other_execute
     }
@@ -127,3 +184,214 @@ mod modes {
     pub const SETGID: Mode = libc::S_ISGID as Mode;
     pub const SETUID: Mode = libc::S_ISUID as Mode;
 }
+
+#[cfg(test)]
+#[cfg(unix)]
+mod test {
+    use super::Permissions;
+    use crate::color::{Colors, Elem, Theme};
+    use crate::flags::{Flags, WritableColor};
+    use crate::meta::FileType;
+    use ansi_term::{ANSIStrings, Colour};
+    use std::fs::{self, File};
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::tempdir;
+
+    fn permissions_with_mode(mode: u32) -> Permissions {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+        fs::set_permissions(&file_path, fs::Permissions::from_mode(mode))
+            .expect("failed to set permissions");
+
+        Permissions::from(&file_path.metadata().unwrap())
+    }
+
+    #[test]
+    fn test_octal() {
+        let colors = Colors::new(Theme::NoColor);
+
+        assert_eq!(
+            "0644",
+            permissions_with_mode(0o644)
+                .render_octal(&colors)
+                .to_string()
+        );
+        assert_eq!(
+            "0755",
+            permissions_with_mode(0o755)
+                .render_octal(&colors)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_octal_sticky() {
+        let colors = Colors::new(Theme::NoColor);
+        let permissions = permissions_with_mode(0o1777);
+
+        assert_eq!("1777", permissions.render_octal(&colors).to_string());
+        assert_eq!(
+            "rwxrwxrwt",
+            permissions
+                .render(
+                    &colors,
+                    FileType::File {
+                        uid: false,
+                        exec: true
+                    },
+                    &Flags::default()
+                )
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_octal_setuid_setgid() {
+        let colors = Colors::new(Theme::NoColor);
+
+        assert_eq!(
+            "6755",
+            permissions_with_mode(0o6755)
+                .render_octal(&colors)
+                .to_string()
+        );
+    }
+
+    fn dir_permissions_with_mode(mode: u32) -> Permissions {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let dir_path = tmp_dir.path().join("dir");
+        fs::create_dir(&dir_path).expect("failed to create dir");
+        fs::set_permissions(&dir_path, fs::Permissions::from_mode(mode))
+            .expect("failed to set permissions");
+
+        Permissions::from(&dir_path.metadata().unwrap())
+    }
+
+    #[test]
+    fn test_writable_elem() {
+        let dir = FileType::Directory { uid: false };
+        let file = FileType::File {
+            uid: false,
+            exec: false,
+        };
+
+        assert_eq!(
+            Some(Elem::OtherWritableDir),
+            dir_permissions_with_mode(0o777).writable_elem(dir)
+        );
+        assert_eq!(
+            Some(Elem::OtherWritable),
+            dir_permissions_with_mode(0o1777).writable_elem(dir)
+        );
+        assert_eq!(
+            Some(Elem::OtherWritable),
+            permissions_with_mode(0o666).writable_elem(file)
+        );
+        assert_eq!(
+            Some(Elem::GroupWritable),
+            permissions_with_mode(0o664).writable_elem(file)
+        );
+        assert_eq!(None, permissions_with_mode(0o644).writable_elem(file));
+    }
+
+    #[test]
+    fn test_render_highlights_world_writable_dir_without_sticky_bit() {
+        let colors = Colors::new(Theme::NoLscolors);
+        let dir = FileType::Directory { uid: false };
+        let flags = Flags {
+            writable_color: WritableColor(true),
+            ..Flags::default()
+        };
+        let highlighted =
+            |symbols: &str, elem: &Elem| colors.colorize(symbols.to_string(), elem).to_string();
+
+        let rendered = dir_permissions_with_mode(0o777)
+            .render(&colors, dir, &flags)
+            .to_string();
+        assert_eq!(highlighted("rwxrwxrwx", &Elem::OtherWritableDir), rendered);
+        assert!(rendered.contains("\u{1b}[48;5;226"));
+
+        let rendered = dir_permissions_with_mode(0o1777)
+            .render(&colors, dir, &flags)
+            .to_string();
+        assert_eq!(highlighted("rwxrwxrwt", &Elem::OtherWritable), rendered);
+        assert!(!rendered.contains("\u{1b}[48;5;226"));
+    }
+
+    #[test]
+    fn test_render_without_highlight() {
+        let colors = Colors::new(Theme::NoLscolors);
+        let rendered = dir_permissions_with_mode(0o777)
+            .render(
+                &colors,
+                FileType::Directory { uid: false },
+                &Flags::default(),
+            )
+            .to_string();
+
+        assert!(!rendered.contains("\u{1b}[48;5;226"));
+        assert_ne!(
+            colors
+                .colorize(String::from("rwxrwxrwx"), &Elem::OtherWritable)
+                .to_string(),
+            rendered
+        );
+    }
+
+    fn file_type() -> FileType {
+        FileType::File {
+            uid: false,
+            exec: true,
+        }
+    }
+
+    #[test]
+    fn test_render_colors_each_bit() {
+        let colors = Colors::new(Theme::NoLscolors);
+        let expected = [
+            Colour::Green.paint("r"),
+            Colour::Yellow.paint("w"),
+            Colour::Red.paint("x"),
+            Colour::Green.paint("r"),
+            Colour::Fixed(245).paint("-"),
+            Colour::Red.paint("x"),
+            Colour::Fixed(245).paint("---"),
+        ];
+
+        assert_eq!(
+            ANSIStrings(&expected).to_string(),
+            permissions_with_mode(0o750)
+                .render(&colors, file_type(), &Flags::default())
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_render_colors_each_bit_from_the_theme() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let theme_path = tmp_dir.path().join("theme.yaml");
+        fs::write(
+            &theme_path,
+            "read: yellow\nwrite: red\nexec: green\nno-access: 240\n",
+        )
+        .expect("failed to write theme");
+        let colors = Colors::new(Theme::NoLscolors).with_theme_file(&theme_path);
+        let expected = [
+            Colour::Yellow.paint("r"),
+            Colour::Red.paint("w"),
+            Colour::Green.paint("x"),
+            Colour::Yellow.paint("r"),
+            Colour::Fixed(240).paint("-"),
+            Colour::Green.paint("x"),
+            Colour::Fixed(240).paint("---"),
+        ];
+
+        assert_eq!(
+            ANSIStrings(&expected).to_string(),
+            permissions_with_mode(0o750)
+                .render(&colors, file_type(), &Flags::default())
+                .to_string()
+        );
+    }
+}
@@ -1,7 +1,8 @@
-use crate::color::{ColoredString, Colors, Elem};
+use crate::color::{ColoredString, Colors, Elem, SizeThresholds};
 use crate::flags::{Flags, SizeFlag};
 use ansi_term::ANSIStrings;
 use std::fs::Metadata;
+use std::path::Path;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Unit {
@@ -16,30 +17,107 @@ pub enum Unit {
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Size {
     bytes: u64,
+    /// The number of bytes allocated on the disk, which replaces the apparent size when the block
+    /// usage is displayed.
+    allocated: u64,
+    displayed: Displayed,
+    /// Whether this is the size of a directory itself rather than the total size of its content,
+    /// which is neutral on the heatmap of the sizes.
+    dir: bool,
+}
+
+/// What is displayed in the size column.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Displayed {
+    Bytes,
+    /// A dash, for the broken links which should have been dereferenced and the directories whose
+    /// entries can not be counted.
+    Unknown,
+    /// The number of entries of a directory.
+    Entries(u64),
 }
 
 impl<'a> From<&'a Metadata> for Size {
     fn from(meta: &Metadata) -> Self {
         let len = meta.len();
-        Self { bytes: len }
+        Self {
+            allocated: Self::allocated_bytes(meta),
+            dir: meta.is_dir(),
+            ..Self::new(len)
+        }
     }
 }
 
 impl Size {
     pub fn new(bytes: u64) -> Self {
-        Self { bytes }
+        Self {
+            bytes,
+            allocated: bytes,
+            displayed: Displayed::Bytes,
+            dir: false,
+        }
+    }
+
+    pub fn unknown() -> Self {
+        Self {
+            bytes: 0,
+            allocated: 0,
+            displayed: Displayed::Unknown,
+            dir: false,
+        }
+    }
+
+    /// Display the number of entries of the directory at `path` instead of the size, keeping the
+    /// size for sorting. The entries are counted without recursing into them.
+    pub fn count_entries(&mut self, path: &Path) {
+        self.displayed = match path.read_dir() {
+            Ok(entries) => Displayed::Entries(entries.count() as u64),
+            Err(_) => Displayed::Unknown,
+        };
+    }
+
+    /// The number of bytes allocated on the disk for the file of `meta`, counted in blocks of 512
+    /// bytes like `du`. Only the apparent size is known on Windows.
+    #[cfg(unix)]
+    pub fn allocated_bytes(meta: &Metadata) -> u64 {
+        use std::os::unix::fs::MetadataExt;
+
+        meta.blocks() * 512
+    }
+
+    #[cfg(windows)]
+    pub fn allocated_bytes(meta: &Metadata) -> u64 {
+        meta.len()
+    }
+
+    /// Display and sort by the size allocated on the disk instead of the apparent size.
+    pub fn use_allocated(&mut self) {
+        self.bytes = self.allocated;
     }
 
     pub fn get_bytes(&self) -> u64 {
         self.bytes
     }
 
-    fn format_size(&self, number: f64) -> String {
-        format!("{0:.1$}", number, if number < 10.0 { 1 } else { 0 })
+    /// Format a number of units with the configured number of decimal places, or with one
+    /// decimal place below 10 and none above by default.
+    fn format_size(&self, number: f64, flags: &Flags) -> String {
+        match flags.size_format.precision {
+            Some(precision) => {
+                let scale = 10_f64.powi(precision as i32);
+                format!("{0:.1$}", (number * scale).round() / scale, precision)
+            }
+            None => {
+                let number = (number * 10.0).round() / 10.0;
+                format!("{0:.1$}", number, if number < 10.0 { 1 } else { 0 })
+            }
+        }
     }
 
     pub fn get_unit(&self, flags: &Flags) -> Unit {
-        if self.bytes < 1024 || flags.size == SizeFlag::Bytes {
+        if self.displayed != Displayed::Bytes {
+            Unit::None
+        } else if self.bytes < 1024 || flags.size == SizeFlag::Bytes {
             Unit::Byte
         } else if self.bytes < 1024 * 1024 {
             Unit::Kilo
@@ -62,26 +140,41 @@ impl Size {
         }
 
         let mut strings: Vec<ColoredString> = vec![ColoredString::from(left_pad), val_content];
-        if flags.size != SizeFlag::Short {
-            strings.push(ColoredString::from(" "));
+        if self.displayed == Displayed::Bytes && flags.block_size.0.is_none() {
+            if flags.size != SizeFlag::Short {
+                strings.push(ColoredString::from(" "));
+            }
+            strings.push(unit_content);
         }
-        strings.push(unit_content);
 
         let res = ANSIStrings(&strings).to_string();
         ColoredString::from(res)
     }
 
     fn paint(&self, colors: &Colors, flags: &Flags, content: String) -> ColoredString {
-        let unit = self.get_unit(flags);
+        colors.colorize(content, &self.elem(colors.size_thresholds(), flags))
+    }
 
-        if unit == Unit::None {
-            colors.colorize(content, &Elem::NonFile)
-        } else if unit == Unit::Byte || unit == Unit::Kilo {
-            colors.colorize(content, &Elem::FileSmall)
-        } else if unit == Unit::Mega {
-            colors.colorize(content, &Elem::FileMedium)
-        } else {
-            colors.colorize(content, &Elem::FileLarge)
+    /// The element coloring the size. It depends on the unit, or on the thresholds when the sizes
+    /// are colored on a heatmap.
+    fn elem(&self, thresholds: SizeThresholds, flags: &Flags) -> Elem {
+        if flags.size_color.0 {
+            return if self.displayed != Displayed::Bytes || self.dir {
+                Elem::NonFile
+            } else if self.bytes >= thresholds.large {
+                Elem::SizeLarge
+            } else if self.bytes >= thresholds.medium {
+                Elem::SizeMedium
+            } else {
+                Elem::SizeSmall
+            };
+        }
+
+        match self.get_unit(flags) {
+            Unit::None => Elem::NonFile,
+            Unit::Byte | Unit::Kilo => Elem::FileSmall,
+            Unit::Mega => Elem::FileMedium,
+            _ => Elem::FileLarge,
         }
     }
 
@@ -92,20 +185,27 @@ impl Size {
     }
 
     pub fn value_string(&self, flags: &Flags) -> String {
+        match self.displayed {
+            Displayed::Bytes => {}
+            Displayed::Unknown => return String::from("-"),
+            Displayed::Entries(count) => return count.to_string(),
+        }
+
+        if let Some(block_size) = flags.block_size.0 {
+            return self.blocks(block_size).to_string();
+        }
+
         let unit = self.get_unit(flags);
 
         match unit {
             Unit::None => "".to_string(),
             Unit::Byte => self.bytes.to_string(),
-            Unit::Kilo => self.format_size(((self.bytes as f64) / 1024.0 * 10.0).round() / 10.0),
-            Unit::Mega => {
-                self.format_size(((self.bytes as f64) / (1024.0 * 1024.0) * 10.0).round() / 10.0)
-            }
-            Unit::Giga => self.format_size(
-                ((self.bytes as f64) / (1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0,
-            ),
+            Unit::Kilo => self.format_size(self.bytes as f64 / 1024.0, flags),
+            Unit::Mega => self.format_size(self.bytes as f64 / (1024.0 * 1024.0), flags),
+            Unit::Giga => self.format_size(self.bytes as f64 / (1024.0 * 1024.0 * 1024.0), flags),
             Unit::Tera => self.format_size(
-                ((self.bytes as f64) / (1024.0 * 1024.0 * 1024.0 * 1024.0) * 10.0).round() / 10.0,
+                self.bytes as f64 / (1024.0 * 1024.0 * 1024.0 * 1024.0),
+                flags,
             ),
         }
     }
@@ -116,7 +216,19 @@ impl Size {
         self.paint(colors, flags, content)
     }
 
+    /// The number of blocks of `block_size` bytes needed to hold the file, rounded up.
+    fn blocks(&self, block_size: u64) -> u64 {
+        match self.bytes % block_size {
+            0 => self.bytes / block_size,
+            _ => self.bytes / block_size + 1,
+        }
+    }
+
     pub fn unit_string(&self, flags: &Flags) -> String {
+        if self.displayed != Displayed::Bytes || flags.block_size.0.is_some() {
+            return String::new();
+        }
+
         let unit = self.get_unit(flags);
 
         match flags.size {
@@ -136,6 +248,14 @@ impl Size {
                 Unit::Giga => String::from("G"),
                 Unit::Tera => String::from("T"),
             },
+            SizeFlag::Binary => match unit {
+                Unit::None => String::from("-"),
+                Unit::Byte => String::from("B"),
+                Unit::Kilo => String::from("KiB"),
+                Unit::Mega => String::from("MiB"),
+                Unit::Giga => String::from("GiB"),
+                Unit::Tera => String::from("TiB"),
+            },
             SizeFlag::Bytes => String::from(""),
         }
     }
@@ -144,8 +264,8 @@ impl Size {
 #[cfg(test)]
 mod test {
     use super::Size;
-    use crate::color::{Colors, Theme};
-    use crate::flags::{Flags, SizeFlag};
+    use crate::color::{Colors, Elem, SizeThresholds, Theme};
+    use crate::flags::{BlockSize, Flags, SizeColor, SizeFlag, SizeFormat};
 
     #[test]
     fn render_byte() {
@@ -321,4 +441,281 @@ mod test {
         assert_eq!(size.render(&colors, &flags, 2).to_string(), "42K");
         assert_eq!(size.render(&colors, &flags, 3).to_string(), " 42K");
     }
+
+    #[test]
+    fn render_binary_kibibyte() {
+        let size = Size::new(1536); // 1.5 kibibytes
+        let flags = Flags {
+            size: SizeFlag::Binary,
+            ..Default::default()
+        };
+        let colors = Colors::new(Theme::NoColor);
+
+        assert_eq!(size.value_string(&flags).as_str(), "1.5");
+        assert_eq!(size.unit_string(&flags).as_str(), "KiB");
+        assert_eq!(size.render(&colors, &flags, 3).to_string(), "1.5 KiB");
+    }
+
+    #[test]
+    fn render_binary_units() {
+        let flags = Flags {
+            size: SizeFlag::Binary,
+            ..Default::default()
+        };
+
+        assert_eq!(Size::new(42).unit_string(&flags).as_str(), "B");
+        assert_eq!(
+            Size::new(42 * 1024 * 1024).unit_string(&flags).as_str(),
+            "MiB"
+        );
+        assert_eq!(
+            Size::new(42 * 1024 * 1024 * 1024)
+                .unit_string(&flags)
+                .as_str(),
+            "GiB"
+        );
+        assert_eq!(
+            Size::new(42 * 1024 * 1024 * 1024 * 1024)
+                .unit_string(&flags)
+                .as_str(),
+            "TiB"
+        );
+    }
+
+    #[test]
+    fn render_block_size() {
+        let size = Size::new(5000);
+        let mut flags = Flags {
+            block_size: BlockSize(Some(1024)),
+            ..Default::default()
+        };
+
+        assert_eq!(size.value_string(&flags).as_str(), "5");
+        assert_eq!(size.unit_string(&flags).as_str(), "");
+
+        flags.size = SizeFlag::Bytes;
+        assert_eq!(size.value_string(&flags).as_str(), "5");
+
+        assert_eq!(Size::new(0).value_string(&flags).as_str(), "0");
+        assert_eq!(Size::new(1024).value_string(&flags).as_str(), "1");
+        assert_eq!(Size::new(1025).value_string(&flags).as_str(), "2");
+
+        flags.block_size = BlockSize(Some(1));
+        assert_eq!(size.value_string(&flags).as_str(), "5000");
+    }
+
+    #[test]
+    fn render_block_size_without_unit() {
+        let size = Size::new(5000);
+        let flags = Flags {
+            block_size: BlockSize(Some(1024)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "  5",
+            size.render(&Colors::new(Theme::NoColor), &flags, 3)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn render_unknown_size() {
+        let size = Size::unknown();
+        let mut flags = Flags::default();
+
+        assert_eq!(0, size.get_bytes());
+        assert_eq!(size.value_string(&flags).as_str(), "-");
+        assert_eq!(size.unit_string(&flags).as_str(), "");
+
+        flags.block_size = BlockSize(Some(1024));
+        assert_eq!(size.value_string(&flags).as_str(), "-");
+        assert_eq!(size.unit_string(&flags).as_str(), "");
+    }
+
+    #[test]
+    fn render_entry_count() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        for name in &["a", "b", ".c"] {
+            std::fs::File::create(tmp_dir.path().join(name)).expect("failed to create file");
+        }
+
+        let mut size = Size::new(4096);
+        size.count_entries(tmp_dir.path());
+        let flags = Flags::default();
+
+        assert_eq!(4096, size.get_bytes());
+        assert_eq!(size.value_string(&flags).as_str(), "3");
+        assert_eq!(size.unit_string(&flags).as_str(), "");
+        assert_eq!(
+            " 3",
+            size.render(&Colors::new(Theme::NoColor), &flags, 2)
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn render_entry_count_of_unreadable_dir() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+
+        let mut size = Size::new(4096);
+        size.count_entries(&tmp_dir.path().join("missing"));
+        assert_eq!(size.value_string(&Flags::default()).as_str(), "-");
+    }
+
+    #[test]
+    fn heatmap_buckets() {
+        let flags = Flags {
+            size_color: SizeColor(true),
+            ..Flags::default()
+        };
+        let thresholds = SizeThresholds::default();
+
+        assert_eq!(Elem::SizeSmall, Size::new(10).elem(thresholds, &flags));
+        assert_eq!(
+            Elem::SizeMedium,
+            Size::new(10 * 1024).elem(thresholds, &flags)
+        );
+        assert_eq!(
+            Elem::SizeLarge,
+            Size::new(10 * 1024 * 1024).elem(thresholds, &flags)
+        );
+    }
+
+    #[test]
+    fn heatmap_custom_thresholds() {
+        let flags = Flags {
+            size_color: SizeColor(true),
+            ..Flags::default()
+        };
+        let thresholds = SizeThresholds {
+            medium: 100 * 1024,
+            large: 1024 * 1024 * 1024,
+        };
+
+        assert_eq!(
+            Elem::SizeSmall,
+            Size::new(10 * 1024).elem(thresholds, &flags)
+        );
+        assert_eq!(
+            Elem::SizeMedium,
+            Size::new(10 * 1024 * 1024).elem(thresholds, &flags)
+        );
+    }
+
+    #[test]
+    fn heatmap_directory_is_neutral() {
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let metadata = tmp_dir.path().metadata().expect("failed to get metadata");
+        let flags = Flags {
+            size_color: SizeColor(true),
+            ..Flags::default()
+        };
+
+        assert_eq!(
+            Elem::NonFile,
+            Size::from(&metadata).elem(SizeThresholds::default(), &flags)
+        );
+        // The total size of a directory is colored like the size of a file.
+        assert_eq!(
+            Elem::SizeSmall,
+            Size::new(10).elem(SizeThresholds::default(), &flags)
+        );
+    }
+
+    #[test]
+    fn without_heatmap_the_unit_colors_the_size() {
+        let flags = Flags::default();
+        let thresholds = SizeThresholds::default();
+
+        assert_eq!(
+            Elem::FileSmall,
+            Size::new(10 * 1024).elem(thresholds, &flags)
+        );
+        assert_eq!(
+            Elem::FileMedium,
+            Size::new(10 * 1024 * 1024).elem(thresholds, &flags)
+        );
+    }
+
+    #[test]
+    fn render_with_precision() {
+        let size = Size::new(1536);
+        let with_precision = |precision| Flags {
+            size_format: SizeFormat {
+                precision: Some(precision),
+                fixed_width: false,
+            },
+            ..Flags::default()
+        };
+
+        assert_eq!("2", size.value_string(&with_precision(0)));
+        assert_eq!("1.5", size.value_string(&with_precision(1)));
+        assert_eq!("1.50", size.value_string(&with_precision(2)));
+        assert_eq!("KB", size.unit_string(&with_precision(0)));
+        assert_eq!("1.5", size.value_string(&Flags::default()));
+    }
+
+    #[test]
+    fn render_with_precision_keeps_the_bytes_whole() {
+        let flags = Flags {
+            size_format: SizeFormat {
+                precision: Some(2),
+                fixed_width: false,
+            },
+            ..Flags::default()
+        };
+
+        assert_eq!("42", Size::new(42).value_string(&flags));
+        assert_eq!("1.00", Size::new(1024).value_string(&flags));
+        assert_eq!("20.00", Size::new(20 * 1024 * 1024).value_string(&flags));
+    }
+
+    #[test]
+    fn render_fixed_width_aligns_the_units() {
+        let flags = Flags {
+            size_format: SizeFormat {
+                precision: Some(1),
+                fixed_width: true,
+            },
+            ..Flags::default()
+        };
+        let colors = Colors::new(Theme::NoColor);
+        let width = flags.size_format.value_width();
+
+        assert_eq!(
+            "   1.5 KB",
+            Size::new(1536).render(&colors, &flags, width).to_string()
+        );
+        assert_eq!(
+            "    10 B",
+            Size::new(10).render(&colors, &flags, width).to_string()
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn block_usage_of_a_sparse_file() {
+        use std::fs::File;
+        use tempfile::tempdir;
+
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let path = tmp_dir.path().join("sparse");
+        let file = File::create(&path).expect("failed to create file");
+        file.set_len(16 * 1024 * 1024)
+            .expect("failed to extend file");
+
+        let mut size = Size::from(&path.metadata().unwrap());
+        assert_eq!(16 * 1024 * 1024, size.get_bytes());
+
+        size.use_allocated();
+        assert!(size.get_bytes() < 16 * 1024 * 1024);
+    }
+
+    #[test]
+    fn block_usage_keeps_a_computed_size() {
+        let mut size = Size::new(1234);
+        size.use_allocated();
+        assert_eq!(1234, size.get_bytes());
+    }
 }
@@ -1,69 +1,201 @@
 use crate::color::{ColoredString, Colors, Elem};
-use crate::flags::Flags;
+use crate::flags::{Flags, SymlinkTarget};
+use crate::meta::{FileType, Permissions};
 use ansi_term::{ANSIString, ANSIStrings};
-use std::fs::read_link;
-use std::path::Path;
+use std::env;
+use std::fs::{read_link, Metadata};
+use std::path::{Component, Path, PathBuf};
 
-#[derive(Clone, Debug)]
+/// The maximum number of links followed to find the target of a link, like the `SYMLOOP_MAX` of
+/// most systems. The links pointing to each other in a cycle are broken.
+const MAX_RESOLUTION_DEPTH: usize = 40;
+
+#[derive(Clone, Debug, Default)]
 pub struct SymLink {
     target: Option<String>,
     valid: bool,
+    /// The absolute directory of the link, which a relative target is relative to.
+    link_dir: Option<PathBuf>,
+    /// The file type and permissions of the file the link finally points to, when it exists.
+    target_file: Option<(FileType, Permissions)>,
 }
 
 impl<'a> From<&'a Path> for SymLink {
     fn from(path: &'a Path) -> Self {
-        if let Ok(target) = read_link(path) {
-            if target.is_absolute() || path.parent() == None {
-                return Self {
-                    valid: target.exists(),
-                    target: Some(
-                        target
-                            .to_str()
-                            .expect("failed to convert symlink to str")
-                            .to_string(),
-                    ),
-                };
-            }
-
-            return Self {
-                target: Some(
-                    target
-                        .to_str()
-                        .expect("failed to convert symlink to str")
-                        .to_string(),
-                ),
-                valid: path.parent().unwrap().join(target).exists(),
-            };
-        }
+        let target = match read_link(path) {
+            Ok(target) => target,
+            Err(_) => return Self::default(),
+        };
+        let target_metadata = resolve(path);
 
         Self {
-            target: None,
-            valid: false,
+            target: Some(
+                target
+                    .to_str()
+                    .expect("failed to convert symlink to str")
+                    .to_string(),
+            ),
+            valid: target_metadata.is_some(),
+            link_dir: path.parent().map(absolute),
+            target_file: target_metadata.as_ref().and_then(target_file),
+        }
+    }
+}
+
+/// Get the metadata of the file the link at `path` finally points to, following at most
+/// [MAX_RESOLUTION_DEPTH] links. This returns [None] for the broken links.
+fn resolve(path: &Path) -> Option<Metadata> {
+    let mut path = path.to_path_buf();
+
+    for _ in 0..MAX_RESOLUTION_DEPTH {
+        let metadata = path.symlink_metadata().ok()?;
+        if !metadata.file_type().is_symlink() {
+            return Some(metadata);
         }
+
+        // A relative target is relative to the directory of the link.
+        let target = read_link(&path).ok()?;
+        path = match path.parent() {
+            Some(parent) => parent.join(target),
+            None => target,
+        };
+    }
+
+    None
+}
+
+/// Make `path` absolute by joining it to the current directory, and normalize it.
+fn absolute(path: &Path) -> PathBuf {
+    match env::current_dir() {
+        Ok(current_dir) => normalize(&current_dir.join(path)),
+        Err(_) => normalize(path),
+    }
+}
+
+/// Remove the `.` components of `path` and the components followed by `..`, without reading the
+/// file system, so that the paths to missing files are normalized too.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                // The parent of the root is the root itself.
+                Some(Component::RootDir) | Some(Component::Prefix(_)) => {}
+                _ => normalized.push(component),
+            },
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The path leading from the `base` directory to `path`, both being absolute and normalized.
+/// This is `path` itself when both are on different Windows drives.
+fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    let mut path_components = path.components().peekable();
+    let mut base_components = base.components().peekable();
+    if path_components.peek() != base_components.peek() {
+        return path.to_path_buf();
+    }
+
+    while path_components.peek().is_some() && path_components.peek() == base_components.peek() {
+        path_components.next();
+        base_components.next();
+    }
+
+    let relative: PathBuf = base_components
+        .map(|_| Component::ParentDir)
+        .chain(path_components)
+        .collect();
+    if relative.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        relative
     }
 }
 
+#[cfg(unix)]
+fn target_file(metadata: &Metadata) -> Option<(FileType, Permissions)> {
+    let permissions = Permissions::from(metadata);
+    Some((FileType::new(metadata, None, &permissions), permissions))
+}
+
+/// The permissions can only be read from a path on Windows, so the targets are not described.
+#[cfg(windows)]
+fn target_file(_: &Metadata) -> Option<(FileType, Permissions)> {
+    None
+}
+
 impl SymLink {
-    pub fn symlink_string(&self) -> Option<String> {
-        if let Some(ref target) = self.target {
-            Some(target.to_string())
-        } else {
-            None
+    /// The target of the link, displayed as stored, as an absolute path or relative to the
+    /// directory of the link. The targets of the broken links are resolved too.
+    pub fn symlink_string(&self, mode: SymlinkTarget) -> Option<String> {
+        let target = self.target.as_ref()?;
+        let link_dir = match (&self.link_dir, mode) {
+            (Some(link_dir), SymlinkTarget::Absolute)
+            | (Some(link_dir), SymlinkTarget::Relative) => link_dir,
+            _ => return Some(target.to_string()),
+        };
+
+        let absolute_target = normalize(&link_dir.join(target));
+        let path = match mode {
+            SymlinkTarget::Relative => relative_to(&absolute_target, link_dir),
+            _ => absolute_target,
+        };
+        Some(path.to_string_lossy().to_string())
+    }
+
+    /// The file type of the file the link finally points to, when it exists.
+    pub fn target_type(&self) -> Option<FileType> {
+        self.target_file.map(|(file_type, _)| file_type)
+    }
+
+    /// The permissions of the file the link finally points to, when it exists.
+    pub fn target_permissions(&self) -> Option<&Permissions> {
+        self.target_file
+            .as_ref()
+            .map(|(_, permissions)| permissions)
+    }
+
+    /// The element used to color the target, which differs when the target does not exist. When
+    /// the links are resolved, the target is colored according to its file type.
+    fn elem(&self, flags: &Flags) -> Elem {
+        if !self.valid {
+            return Elem::BrokenSymLink;
+        }
+
+        match self.target_type() {
+            Some(file_type) if flags.resolve_symlinks.0 => match file_type {
+                FileType::File { uid, exec } => Elem::File { uid, exec },
+                FileType::Directory { uid } => Elem::Dir { uid },
+                FileType::Pipe => Elem::Pipe,
+                FileType::BlockDevice => Elem::BlockDevice,
+                FileType::CharDevice => Elem::CharDevice,
+                FileType::Socket => Elem::Socket,
+                FileType::SymLink { .. } => Elem::SymLink,
+                FileType::Special => Elem::Special,
+            },
+            _ => Elem::SymLink,
         }
     }
 
     pub fn render(&self, colors: &Colors, flag: &Flags) -> ColoredString {
-        if let Some(target_string) = self.symlink_string() {
-            let elem = if self.valid {
-                &Elem::SymLink
+        if let Some(target_string) = self.symlink_string(flag.symlink_target) {
+            let elem = self.elem(flag);
+
+            // The arrow of a broken link is colored too, so that it stands out.
+            let arrow = format!(" {} ", flag.symlink_arrow); // ⇒ \u{21d2}
+            let arrow = if self.valid {
+                ColoredString::from(arrow)
             } else {
-                &Elem::BrokenSymLink
+                colors.colorize(arrow, &elem)
             };
 
-            let strings: &[ColoredString] = &[
-                ColoredString::from(format!(" {} ", flag.symlink_arrow)), // ⇒ \u{21d2}
-                colors.colorize(target_string, elem),
-            ];
+            let strings: &[ColoredString] = &[arrow, colors.colorize(target_string, &elem)];
 
             let res = ANSIStrings(strings).to_string();
             ColoredString::from(res)
@@ -77,15 +209,16 @@ impl SymLink {
 mod tests {
     use super::SymLink;
     use crate::app;
-    use crate::color::{Colors, Theme};
+    use crate::color::{Colors, Elem, Theme};
     use crate::config_file::Config;
-    use crate::flags::Flags;
+    use crate::flags::{Flags, ResolveSymlinks};
     use yaml_rust::YamlLoader;
     #[test]
     fn test_symlink_render_default_valid_target_nocolor() {
         let link = SymLink {
             target: Some("/target".to_string()),
             valid: true,
+            ..SymLink::default()
         };
         let yaml_string = "---";
         let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
@@ -106,6 +239,7 @@ mod tests {
         let link = SymLink {
             target: Some("/target".to_string()),
             valid: false,
+            ..SymLink::default()
         };
         let yaml_string = "---";
         let yaml = YamlLoader::load_from_str(yaml_string).unwrap()[0].clone();
@@ -120,4 +254,190 @@ mod tests {
             .to_string()
         );
     }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_to_nonexistent_path_is_broken() {
+        use ansi_term::{ANSIStrings, Colour};
+        use std::os::unix::fs::symlink;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let link_path = tmp_dir.path().join("link");
+        symlink("missing", &link_path).expect("failed to create symlink");
+
+        let link = SymLink::from(link_path.as_path());
+        assert_eq!(Elem::BrokenSymLink, link.elem(&Flags::default()));
+
+        let dead_link = Colour::Fixed(124);
+        assert_eq!(
+            ANSIStrings(&[dead_link.paint(" ⇒ "), dead_link.paint("missing")]).to_string(),
+            link.render(&Colors::new(Theme::NoLscolors), &Flags::default())
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_to_existing_path_is_valid() {
+        use std::fs::File;
+        use std::os::unix::fs::symlink;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        File::create(tmp_dir.path().join("target")).expect("failed to create file");
+        let link_path = tmp_dir.path().join("link");
+        symlink("target", &link_path).expect("failed to create symlink");
+
+        assert_eq!(
+            Elem::SymLink,
+            SymLink::from(link_path.as_path()).elem(&Flags::default())
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_resolved_symlink_to_executable() {
+        use crate::meta::FileType;
+        use ansi_term::{ANSIStrings, Colour};
+        use std::fs::{self, File};
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let target_path = tmp_dir.path().join("script");
+        File::create(&target_path).expect("failed to create file");
+        fs::set_permissions(&target_path, fs::Permissions::from_mode(0o755))
+            .expect("failed to set permissions");
+        // The target is found through a chain of links.
+        symlink("script", tmp_dir.path().join("first")).expect("failed to create symlink");
+        let link_path = tmp_dir.path().join("link");
+        symlink("first", &link_path).expect("failed to create symlink");
+
+        let link = SymLink::from(link_path.as_path());
+        let exec = Elem::File {
+            exec: true,
+            uid: false,
+        };
+        assert_eq!(
+            Some(FileType::File {
+                exec: true,
+                uid: false
+            }),
+            link.target_type()
+        );
+        assert_eq!(0o755, link.target_permissions().unwrap().octal());
+        assert_eq!(Elem::SymLink, link.elem(&Flags::default()));
+
+        let flags = Flags {
+            resolve_symlinks: ResolveSymlinks(true),
+            ..Default::default()
+        };
+        assert_eq!(exec, link.elem(&flags));
+        assert_eq!(
+            ANSIStrings(&[
+                ansi_term::ANSIString::from(" ⇒ "),
+                Colour::Fixed(40).paint("first")
+            ])
+            .to_string(),
+            link.render(&Colors::new(Theme::NoLscolors), &flags)
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_symlink_cycle_is_broken() {
+        use std::os::unix::fs::symlink;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let link_path = tmp_dir.path().join("first");
+        symlink("second", &link_path).expect("failed to create symlink");
+        symlink("first", tmp_dir.path().join("second")).expect("failed to create symlink");
+
+        let link = SymLink::from(link_path.as_path());
+        assert_eq!(Elem::BrokenSymLink, link.elem(&Flags::default()));
+        assert_eq!(None, link.target_type());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_relative_symlink_target_modes() {
+        use crate::flags::SymlinkTarget;
+        use std::fs;
+        use std::os::unix::fs::symlink;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let link_dir = tmp_dir.path().join("links");
+        fs::create_dir(&link_dir).expect("failed to create dir");
+        let link_path = link_dir.join("link");
+        symlink("../data/./target", &link_path).expect("failed to create symlink");
+        let link = SymLink::from(link_path.as_path());
+
+        assert_eq!(
+            Some("../data/./target".to_string()),
+            link.symlink_string(SymlinkTarget::Raw)
+        );
+        assert_eq!(
+            Some(
+                tmp_dir
+                    .path()
+                    .join("data/target")
+                    .to_string_lossy()
+                    .to_string()
+            ),
+            link.symlink_string(SymlinkTarget::Absolute)
+        );
+        assert_eq!(
+            Some("../data/target".to_string()),
+            link.symlink_string(SymlinkTarget::Relative)
+        );
+
+        // The target does not exist, but it is still displayed in every mode.
+        assert_eq!(Elem::BrokenSymLink, link.elem(&Flags::default()));
+        let flags = Flags {
+            symlink_target: SymlinkTarget::Absolute,
+            ..Flags::default()
+        };
+        assert_eq!(
+            format!(" ⇒ {}", tmp_dir.path().join("data/target").display()),
+            link.render(&Colors::new(Theme::NoColor), &flags)
+                .to_string()
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_absolute_symlink_target_shown_relative() {
+        use crate::flags::SymlinkTarget;
+        use std::os::unix::fs::symlink;
+
+        let tmp_dir = tempfile::tempdir().expect("failed to create temp dir");
+        let link_path = tmp_dir.path().join("link");
+        symlink(tmp_dir.path().join("target"), &link_path).expect("failed to create symlink");
+        let link = SymLink::from(link_path.as_path());
+
+        assert_eq!(
+            Some("target".to_string()),
+            link.symlink_string(SymlinkTarget::Relative)
+        );
+        assert_eq!(
+            Some(tmp_dir.path().join("target").to_string_lossy().to_string()),
+            link.symlink_string(SymlinkTarget::Absolute)
+        );
+    }
+
+    #[test]
+    fn test_normalize_and_relative_to() {
+        use super::{normalize, relative_to};
+        use std::path::{Path, PathBuf};
+
+        assert_eq!(PathBuf::from("/a/c"), normalize(Path::new("/a/./b/../c")));
+        assert_eq!(PathBuf::from("/c"), normalize(Path::new("/../c")));
+        assert_eq!(
+            PathBuf::from("../../d"),
+            relative_to(Path::new("/a/d"), Path::new("/a/b/c"))
+        );
+        assert_eq!(
+            PathBuf::from("."),
+            relative_to(Path::new("/a/b"), Path::new("/a/b"))
+        );
+    }
 }
@@ -0,0 +1,142 @@
+use crate::color::{ColoredString, Colors, Elem};
+use std::path::Path;
+
+/// The names of the extended attributes of a file.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct XAttrs {
+    names: Vec<String>,
+}
+
+impl From<&Path> for XAttrs {
+    fn from(path: &Path) -> Self {
+        Self {
+            names: list_names(path),
+        }
+    }
+}
+
+impl XAttrs {
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Render an `@` marker when the file has extended attributes, or a blank otherwise.
+    pub fn render(&self, colors: &Colors) -> ColoredString<'_> {
+        if self.names.is_empty() {
+            ColoredString::from(" ")
+        } else {
+            colors.colorize(String::from("@"), &Elem::XAttr)
+        }
+    }
+}
+
+/// List the names of the extended attributes of the file at `path`, without following symlinks.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn list_names(path: &Path) -> Vec<String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = match CString::new(path.as_os_str().as_bytes()) {
+        Ok(path) => path,
+        Err(_) => return Vec::new(),
+    };
+
+    // The first call only gets the size of the buffer needed to hold the names.
+    let size = unsafe { listxattr(&path, std::ptr::null_mut(), 0) };
+    if size <= 0 {
+        return Vec::new();
+    }
+
+    let mut buffer = vec![0u8; size as usize];
+    let size = unsafe {
+        listxattr(
+            &path,
+            buffer.as_mut_ptr() as *mut libc::c_char,
+            buffer.len(),
+        )
+    };
+    if size <= 0 {
+        return Vec::new();
+    }
+    buffer.truncate(size as usize);
+
+    buffer
+        .split(|byte| *byte == 0)
+        .filter(|name| !name.is_empty())
+        .map(|name| String::from_utf8_lossy(name).to_string())
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+unsafe fn listxattr(path: &std::ffi::CStr, buffer: *mut libc::c_char, size: usize) -> isize {
+    libc::llistxattr(path.as_ptr(), buffer, size)
+}
+
+#[cfg(target_os = "macos")]
+unsafe fn listxattr(path: &std::ffi::CStr, buffer: *mut libc::c_char, size: usize) -> isize {
+    libc::listxattr(path.as_ptr(), buffer, size, libc::XATTR_NOFOLLOW)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn list_names(_: &Path) -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(test)]
+#[cfg(target_os = "linux")]
+mod tests {
+    use super::XAttrs;
+    use crate::color::{Colors, Theme};
+    use std::ffi::CString;
+    use std::fs::File;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    /// Set an extended attribute on the file, returning false if the file system does not support
+    /// them.
+    fn set_xattr(path: &Path, name: &str) -> bool {
+        let path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        let name = CString::new(name).unwrap();
+        let value = b"lsd";
+
+        unsafe {
+            libc::lsetxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                value.as_ptr() as *const libc::c_void,
+                value.len(),
+                0,
+            ) == 0
+        }
+    }
+
+    #[test]
+    fn test_without_xattrs() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+
+        let xattrs = XAttrs::from(file_path.as_path());
+
+        assert!(xattrs.names().is_empty());
+        assert_eq!(" ", xattrs.render(&Colors::new(Theme::NoColor)).to_string());
+    }
+
+    #[test]
+    fn test_with_xattrs() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let file_path = tmp_dir.path().join("file");
+        File::create(&file_path).expect("failed to create file");
+        if !set_xattr(&file_path, "user.lsd.first") || !set_xattr(&file_path, "user.lsd.second") {
+            return;
+        }
+
+        let xattrs = XAttrs::from(file_path.as_path());
+
+        let mut names = xattrs.names().to_vec();
+        names.sort();
+        assert_eq!(vec!["user.lsd.first", "user.lsd.second"], names);
+        assert_eq!("@", xattrs.render(&Colors::new(Theme::NoColor)).to_string());
+    }
+}
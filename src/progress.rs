@@ -0,0 +1,228 @@
+//! This module provides the spinner shown on the standard error while the entries of a slow
+//! listing are read, with the number of entries read so far.
+
+use std::io::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+/// The frames of the spinner, drawn in turn.
+const FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+/// The time between two frames of the spinner.
+const INTERVAL: Duration = Duration::from_millis(100);
+/// Return to the start of the line and clear it.
+const CLEAR_LINE: &str = "\r\x1b[K";
+
+/// The number of entries read so far, shown by the spinner.
+static ENTRIES_READ: AtomicUsize = AtomicUsize::new(0);
+
+lazy_static! {
+    /// The line of the running spinner, which is cleared before the errors are printed.
+    static ref ACTIVE_LINE: Mutex<Option<Arc<Mutex<Line>>>> = Mutex::new(None);
+}
+
+/// Record that `count` more entries were read.
+pub fn entries_read(count: usize) {
+    ENTRIES_READ.fetch_add(count, Ordering::Relaxed);
+}
+
+/// Run `print`, which writes an error, with the line of the running spinner cleared, so that
+/// they do not interleave. The spinner is not drawn again until `print` returns.
+pub fn suspend<F: FnOnce()>(print: F) {
+    let line = ACTIVE_LINE.lock().unwrap().clone();
+    match line {
+        Some(line) => suspend_line(&line, print),
+        None => print(),
+    }
+}
+
+fn suspend_line<F: FnOnce()>(line: &Mutex<Line>, print: F) {
+    let mut line = line.lock().unwrap();
+    line.clear();
+    print();
+}
+
+/// The line the spinner is drawn on.
+struct Line {
+    out: Box<dyn Write + Send>,
+    frame: usize,
+    drawn: bool,
+}
+
+impl Line {
+    fn new(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            out,
+            frame: 0,
+            drawn: false,
+        }
+    }
+
+    /// Draw the next frame, with the number of entries read so far.
+    fn tick(&mut self) {
+        let count = ENTRIES_READ.load(Ordering::Relaxed);
+        // The spinner is only a hint, so the errors writing it are ignored.
+        let _ = write!(
+            self.out,
+            "{}{} {} entries read",
+            CLEAR_LINE,
+            FRAMES[self.frame % FRAMES.len()],
+            count
+        );
+        let _ = self.out.flush();
+        self.frame += 1;
+        self.drawn = true;
+    }
+
+    /// Clear the line, if a frame is drawn on it.
+    fn clear(&mut self) {
+        if self.drawn {
+            let _ = write!(self.out, "{}", CLEAR_LINE);
+            let _ = self.out.flush();
+            self.drawn = false;
+        }
+    }
+}
+
+/// What the thread of the spinner is told.
+enum Event {
+    Tick,
+    Stop,
+}
+
+/// A spinner drawn on a thread of its own until it is stopped.
+pub struct Spinner {
+    events: Sender<Event>,
+    thread: JoinHandle<()>,
+}
+
+impl Spinner {
+    /// Start drawing the spinner on `out`, a frame at every interval. The first frame is only
+    /// drawn after an interval, so that the fast listings do not flicker. Meanwhile, the errors
+    /// printed clear its line first.
+    pub fn start<W: Write + Send + 'static>(out: W) -> Self {
+        let line = Arc::new(Mutex::new(Line::new(Box::new(out))));
+        *ACTIVE_LINE.lock().unwrap() = Some(Arc::clone(&line));
+
+        Self::spawn(line, Some(INTERVAL))
+    }
+
+    /// Draw the spinner on `line` at every `interval`, or only at each [Event::Tick] without one.
+    fn spawn(line: Arc<Mutex<Line>>, interval: Option<Duration>) -> Self {
+        let (events, receiver) = mpsc::channel();
+
+        let thread = thread::spawn(move || {
+            while let Event::Tick = next_event(&receiver, interval) {
+                line.lock().unwrap().tick();
+            }
+            line.lock().unwrap().clear();
+        });
+
+        Self { events, thread }
+    }
+
+    /// Stop the spinner and clear its line, returning once it is cleared so that it can not
+    /// interleave with the listing.
+    pub fn stop(self) {
+        let _ = self.events.send(Event::Stop);
+        let _ = self.thread.join();
+        *ACTIVE_LINE.lock().unwrap() = None;
+    }
+}
+
+/// Wait for the next event, which is a tick when the `interval` elapses without any.
+fn next_event(receiver: &Receiver<Event>, interval: Option<Duration>) -> Event {
+    let event = match interval {
+        Some(interval) => match receiver.recv_timeout(interval) {
+            Err(RecvTimeoutError::Timeout) => return Event::Tick,
+            event => event.ok(),
+        },
+        None => receiver.recv().ok(),
+    };
+    // The spinner is stopped as well when it is dropped.
+    event.unwrap_or(Event::Stop)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{suspend_line, Event, Line, Spinner, CLEAR_LINE};
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    /// A writer keeping what is written, shared with the test.
+    #[derive(Clone, Default)]
+    struct Output(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Output {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Output {
+        fn contents(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+
+        /// The contents with the counts of entries replaced by `N`, as the other tests read
+        /// entries meanwhile.
+        fn contents_without_counts(&self) -> String {
+            self.contents()
+                .split(' ')
+                .map(|word| match word.parse::<usize>() {
+                    Ok(_) => "N",
+                    Err(_) => word,
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
+        }
+    }
+
+    #[test]
+    fn test_spinner_is_cleared_when_stopped() {
+        let output = Output::default();
+        let line = Arc::new(Mutex::new(Line::new(Box::new(output.clone()))));
+        let spinner = Spinner::spawn(line, None);
+        spinner.events.send(Event::Tick).unwrap();
+        spinner.events.send(Event::Tick).unwrap();
+        spinner.stop();
+
+        assert_eq!(
+            format!("{0}| N entries read{0}/ N entries read{0}", CLEAR_LINE),
+            output.contents_without_counts()
+        );
+    }
+
+    #[test]
+    fn test_spinner_stopped_early_writes_nothing() {
+        let output = Output::default();
+        let line = Arc::new(Mutex::new(Line::new(Box::new(output.clone()))));
+        Spinner::spawn(line, None).stop();
+
+        assert_eq!("", output.contents());
+    }
+
+    #[test]
+    fn test_errors_clear_the_spinner_line() {
+        let output = Output::default();
+        let line = Mutex::new(Line::new(Box::new(output.clone())));
+
+        // Nothing is cleared before the first frame.
+        suspend_line(&line, || output.clone().write_all(b"error\n").unwrap());
+        line.lock().unwrap().tick();
+        suspend_line(&line, || output.clone().write_all(b"error\n").unwrap());
+        suspend_line(&line, || output.clone().write_all(b"error\n").unwrap());
+
+        assert_eq!(
+            format!("error\n{0}| N entries read{0}error\nerror\n", CLEAR_LINE),
+            output.contents_without_counts()
+        );
+    }
+}
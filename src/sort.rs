@@ -1,4 +1,4 @@
-use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder};
+use crate::flags::{DirGrouping, Flags, SortColumn, SortOrder, TimeKind};
 use crate::meta::Meta;
 use human_sort::compare;
 use std::cmp::Ordering;
@@ -16,18 +16,50 @@ pub fn assemble_sorters(flags: &Flags) -> Vec<(SortOrder, SortFn)> {
         }
         DirGrouping::None => {}
     };
-    let other_sort = match flags.sorting.column {
+    let column_sort = match column_sorter(flags.sorting.column, flags) {
+        Some(sorter) => sorter,
+        None => return sorters,
+    };
+    sorters.push((flags.sorting.order, column_sort));
+    // The columns breaking the ties are reversed along with the first one.
+    for column in &flags.sorting.then_by {
+        if let Some(sorter) = column_sorter(*column, flags) {
+            sorters.push((flags.sorting.order, sorter));
+        }
+    }
+    // The ties are broken in the same order whether the sort is reversed or not, so that the
+    // output does not depend on the order the entries were read in.
+    sorters.push((SortOrder::Default, by_name_bytes));
+    sorters
+}
+
+/// The function comparing the metas by `column`, or [None] when the entries are not sorted by it.
+fn column_sorter(column: SortColumn, flags: &Flags) -> Option<SortFn> {
+    let sorter: SortFn = match column {
+        SortColumn::None => return None,
+        SortColumn::Name if flags.sorting.locale.0 => by_name_locale,
         SortColumn::Name => by_name,
         SortColumn::Size => by_size,
-        SortColumn::Time => by_date,
+        SortColumn::Time => match flags.time_kind {
+            TimeKind::Modified => |a, b| by_date(a, b, TimeKind::Modified),
+            TimeKind::Accessed => |a, b| by_date(a, b, TimeKind::Accessed),
+            TimeKind::Changed => |a, b| by_date(a, b, TimeKind::Changed),
+            TimeKind::Created => |a, b| by_date(a, b, TimeKind::Created),
+        },
         SortColumn::Version => by_version,
         SortColumn::Extension => by_extension,
     };
-    sorters.push((flags.sorting.order, other_sort));
-    sorters
+    Some(sorter)
 }
 
+/// Compare the metas with the sorters, in order, until one of them tells them apart. The implied
+/// `.` and `..` directories come first whatever the sorters.
 pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering {
+    let ordering = implied_dirs_first(a, b);
+    if ordering != Ordering::Equal {
+        return ordering;
+    }
+
     for (direction, sorter) in sorters.iter() {
         match (sorter)(a, b) {
             Ordering::Equal => continue,
@@ -42,6 +74,19 @@ pub fn by_meta(sorters: &[(SortOrder, SortFn)], a: &Meta, b: &Meta) -> Ordering
     Ordering::Equal
 }
 
+fn implied_dirs_first(a: &Meta, b: &Meta) -> Ordering {
+    let rank = |meta: &Meta| {
+        if !meta.is_implied_dir() {
+            2
+        } else if meta.name.name == "." {
+            0
+        } else {
+            1
+        }
+    };
+    rank(a).cmp(&rank(b))
+}
+
 fn with_dirs_first(a: &Meta, b: &Meta) -> Ordering {
     b.file_type.is_dirlike().cmp(&a.file_type.is_dirlike())
 }
@@ -50,20 +95,72 @@ fn by_size(a: &Meta, b: &Meta) -> Ordering {
     b.size.get_bytes().cmp(&a.size.get_bytes())
 }
 
+/// The names are compared ignoring their case, so `Zebra` comes after `apple`.
 fn by_name(a: &Meta, b: &Meta) -> Ordering {
     a.name.cmp(&b.name)
 }
 
-fn by_date(a: &Meta, b: &Meta) -> Ordering {
-    b.date.cmp(&a.date).then(a.name.cmp(&b.name))
+/// The names are compared with the collation of the locale, like `ls` does, so that `Ávila` comes
+/// between `apple` and `banana` in most of them. The names are only compared, they are displayed
+/// as they are.
+fn by_name_locale(a: &Meta, b: &Meta) -> Ordering {
+    collate(&a.name.name, &b.name.name)
+}
+
+/// Set up the collation of the `LC_COLLATE` locale of the environment, which the names are
+/// compared with by `--sort-locale`. As `setlocale` is not thread safe, this is called once at the
+/// start, before any thread is spawned.
+#[cfg(unix)]
+pub fn set_up_collation() {
+    unsafe {
+        libc::setlocale(libc::LC_COLLATE, b"\0".as_ptr() as *const libc::c_char);
+    }
+}
+
+/// The collation of the locale is not read on Windows.
+#[cfg(not(unix))]
+pub fn set_up_collation() {}
+
+/// Compare the strings with `strcoll`, in the collation of the `LC_COLLATE` locale set up by
+/// [set_up_collation]. This is the byte order in the C locale, and for the strings which can not
+/// be passed to the C library.
+#[cfg(unix)]
+fn collate(a: &str, b: &str) -> Ordering {
+    use std::ffi::CString;
+
+    match (CString::new(a), CString::new(b)) {
+        (Ok(a), Ok(b)) => unsafe { libc::strcoll(a.as_ptr(), b.as_ptr()) }.cmp(&0),
+        _ => a.as_bytes().cmp(b.as_bytes()),
+    }
+}
+
+/// The names are compared byte by byte on Windows.
+#[cfg(not(unix))]
+fn collate(a: &str, b: &str) -> Ordering {
+    a.as_bytes().cmp(b.as_bytes())
+}
+
+/// The names are compared byte by byte, then the paths for the entries of different directories.
+fn by_name_bytes(a: &Meta, b: &Meta) -> Ordering {
+    a.name
+        .name
+        .as_bytes()
+        .cmp(b.name.name.as_bytes())
+        .then_with(|| a.path.cmp(&b.path))
+}
+
+fn by_date(a: &Meta, b: &Meta, kind: TimeKind) -> Ordering {
+    b.date.sort_key(kind).cmp(&a.date.sort_key(kind))
 }
 
 fn by_version(a: &Meta, b: &Meta) -> Ordering {
     compare(&a.name.name, &b.name.name)
 }
 
+/// Compare the extensions regardless of their case. The files without an extension come first.
 fn by_extension(a: &Meta, b: &Meta) -> Ordering {
-    a.name.extension().cmp(&b.name.extension())
+    let extension = |meta: &Meta| meta.name.extension().map(str::to_lowercase);
+    extension(a).cmp(&extension(b))
 }
 
 #[cfg(test)]
@@ -184,6 +281,36 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
     }
 
+    #[test]
+    fn test_sort_assemble_sorters_by_name_ignoring_case() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let names = ["Cherry", "banana", "Apple"];
+        let mut metas: Vec<Meta> = names
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false).expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.dir_grouping = DirGrouping::None;
+
+        let sorter = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorter, a, b));
+        let sorted: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["Apple", "banana", "Cherry"], sorted);
+
+        flags.sorting.order = SortOrder::Reverse;
+
+        let sorter = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorter, a, b));
+        let sorted: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["Cherry", "banana", "Apple"], sorted);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_time() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -232,6 +359,51 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_sort_assemble_sorters_by_time_kind() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let touch = |args: &[&str], path: &std::path::Path| {
+            let success = Command::new("touch")
+                .args(args)
+                .arg(path)
+                .status()
+                .unwrap()
+                .success();
+            assert!(success, "failed to change file timestamp");
+        };
+
+        // The file "aaa" was modified before "zzz", but accessed after it.
+        let path_a = tmp_dir.path().join("aaa");
+        File::create(&path_a).expect("failed to create file");
+        touch(&["-m", "-t", "198511160000"], &path_a);
+        touch(&["-a", "-t", "199001010000"], &path_a);
+        let meta_a = Meta::from_path(&path_a, false).expect("failed to get meta");
+
+        let path_z = tmp_dir.path().join("zzz");
+        File::create(&path_z).expect("failed to create file");
+        touch(&["-m", "-t", "198701010000"], &path_z);
+        touch(&["-a", "-t", "198601010000"], &path_z);
+        let meta_z = Meta::from_path(&path_z, false).expect("failed to get meta");
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Time;
+
+        // Sort by modification time
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
+
+        // Sort by access time
+        flags.time_kind = TimeKind::Accessed;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
+
+        // Sort by access time reversed
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Greater);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_extension() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -259,9 +431,9 @@ mod tests {
         let mut flags = Flags::default();
         flags.sorting.column = SortColumn::Extension;
 
-        // Sort by extension
+        // Sort by extension, then by name for the same extension
         let sorter = assemble_sorters(&flags);
-        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Equal);
+        assert_eq!(by_meta(&sorter, &meta_a, &meta_z), Ordering::Less);
 
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_a, &meta_j), Ordering::Greater);
@@ -270,6 +442,27 @@ mod tests {
         assert_eq!(by_meta(&sorter, &meta_a, &meta_t), Ordering::Less);
     }
 
+    #[test]
+    fn test_sort_by_extension_groups_the_extensions() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut metas: Vec<Meta> = ["c.txt", "b.md", "d", "E.TXT", "a.txt"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false).expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Extension;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["d", "b.md", "E.TXT", "a.txt", "c.txt"], names);
+    }
+
     #[test]
     fn test_sort_assemble_sorters_by_version() {
         let tmp_dir = tempdir().expect("failed to create temp dir");
@@ -295,4 +488,269 @@ mod tests {
         let sorter = assemble_sorters(&flags);
         assert_eq!(by_meta(&sorter, &meta_b, &meta_c), Ordering::Less);
     }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_version_numbered_files() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = ["img10.png", "img2.png", "img1.png"]
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false).expect("failed to get meta")
+            })
+            .collect();
+
+        let path_dir = tmp_dir.path().join("img3");
+        create_dir(&path_dir).expect("failed to create dir");
+        metas.push(Meta::from_path(&path_dir, false).expect("failed to get meta"));
+
+        let names = |metas: &[Meta]| -> Vec<String> {
+            metas.iter().map(|meta| meta.name.name.clone()).collect()
+        };
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Version;
+
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            names(&metas),
+            vec!["img1.png", "img2.png", "img3", "img10.png"]
+        );
+
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            names(&metas),
+            vec!["img10.png", "img3", "img2.png", "img1.png"]
+        );
+
+        // The directories stay first when reversing
+        flags.sorting.dir_grouping = DirGrouping::First;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(
+            names(&metas),
+            vec!["img3", "img10.png", "img2.png", "img1.png"]
+        );
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_with_dirs_last_mixed() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let mut metas: Vec<Meta> = Vec::new();
+        for name in &["b_file", "d_file"] {
+            let path = tmp_dir.path().join(name);
+            File::create(&path).expect("failed to create file");
+            metas.push(Meta::from_path(&path, false).expect("failed to get meta"));
+        }
+        for name in &["a_dir", "c_dir"] {
+            let path = tmp_dir.path().join(name);
+            create_dir(&path).expect("failed to create dir");
+            metas.push(Meta::from_path(&path, false).expect("failed to get meta"));
+        }
+
+        let names = |metas: &[Meta]| -> Vec<String> {
+            metas.iter().map(|meta| meta.name.name.clone()).collect()
+        };
+
+        let mut flags = Flags::default();
+        flags.sorting.dir_grouping = DirGrouping::Last;
+
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["b_file", "d_file", "a_dir", "c_dir"]);
+
+        // The directories stay last when reversing
+        flags.sorting.order = SortOrder::Reverse;
+        let sorter = assemble_sorters(&flags);
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        assert_eq!(names(&metas), vec!["d_file", "b_file", "c_dir", "a_dir"]);
+    }
+
+    #[test]
+    fn test_sort_implied_dirs_first() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        for name in &["a", "b"] {
+            File::create(tmp_dir.path().join(name)).expect("failed to create file");
+        }
+
+        let flags = Flags {
+            display: crate::flags::Display::All,
+            sorting: crate::flags::Sorting {
+                column: SortColumn::Name,
+                then_by: Vec::new(),
+                order: SortOrder::Reverse,
+                dir_grouping: DirGrouping::Last,
+                locale: crate::flags::sorting::SortLocale(false),
+            },
+            ..Flags::default()
+        };
+        let dir = Meta::from_path(tmp_dir.path(), false).expect("failed to get meta");
+        let mut metas = dir
+            .recurse_into(1, &flags)
+            .expect("failed to read dir")
+            .expect("no content");
+
+        let sorter = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorter, a, b));
+        let sorted: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec![".", "..", "b", "a"], sorted);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_none() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let names = ["b", "c", "a"];
+        let mut metas: Vec<Meta> = names
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false).expect("failed to get meta")
+            })
+            .collect();
+
+        let flags = Flags {
+            sorting: crate::flags::Sorting {
+                column: SortColumn::None,
+                then_by: Vec::new(),
+                order: SortOrder::Reverse,
+                dir_grouping: DirGrouping::None,
+                locale: crate::flags::sorting::SortLocale(false),
+            },
+            ..Flags::default()
+        };
+
+        let sorter = assemble_sorters(&flags);
+        assert!(sorter.is_empty());
+        metas.sort_by(|a, b| by_meta(&sorter, a, b));
+        let sorted: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["b", "c", "a"], sorted);
+    }
+
+    #[test]
+    fn test_sort_ties_are_broken_by_name() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+
+        let names = ["b", "B", "a", "c", "A"];
+        let metas: Vec<Meta> = names
+            .iter()
+            .map(|name| {
+                let path = tmp_dir.path().join(name);
+                File::create(&path).expect("failed to create file");
+                Meta::from_path(&path, false).expect("failed to get meta")
+            })
+            .collect();
+
+        let mut flags = Flags {
+            sorting: crate::flags::Sorting {
+                column: SortColumn::Size,
+                then_by: Vec::new(),
+                order: SortOrder::Default,
+                dir_grouping: DirGrouping::None,
+                locale: crate::flags::sorting::SortLocale(false),
+            },
+            ..Flags::default()
+        };
+
+        for order in &[SortOrder::Default, SortOrder::Reverse] {
+            flags.sorting.order = *order;
+            let sorter = assemble_sorters(&flags);
+
+            // The same-size files are in the same order whatever the order they are read in.
+            for rotation in 0..metas.len() {
+                let mut sorted_metas = metas.clone();
+                sorted_metas.rotate_left(rotation);
+                sorted_metas.sort_by(|a, b| by_meta(&sorter, a, b));
+                let sorted: Vec<&str> = sorted_metas
+                    .iter()
+                    .map(|meta| meta.name.name.as_str())
+                    .collect();
+                assert_eq!(vec!["A", "B", "a", "b", "c"], sorted);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_size_with_dirs_first() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut metas = Vec::new();
+        for (name, len) in &[("small", 1), ("large", 100), ("medium", 10)] {
+            let path = tmp_dir.path().join(name);
+            File::create(&path)
+                .and_then(|file| file.set_len(*len))
+                .expect("failed to create file");
+            metas.push(Meta::from_path(&path, false).expect("failed to get meta"));
+        }
+        for name in &["dir_b", "dir_a"] {
+            let path = tmp_dir.path().join(name);
+            create_dir(&path).expect("failed to create dir");
+            metas.push(Meta::from_path(&path, false).expect("failed to get meta"));
+        }
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Size;
+        flags.sorting.dir_grouping = DirGrouping::First;
+
+        let sorters = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorters, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["dir_a", "dir_b", "large", "medium", "small"], names);
+
+        flags.sorting.order = SortOrder::Reverse;
+
+        let sorters = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorters, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["dir_a", "dir_b", "small", "medium", "large"], names);
+    }
+
+    #[test]
+    fn test_sort_assemble_sorters_by_extension_then_size() {
+        let tmp_dir = tempdir().expect("failed to create temp dir");
+        let mut metas = Vec::new();
+        for (name, len) in &[
+            ("a.txt", 1),
+            ("b.rs", 10),
+            ("c.txt", 100),
+            ("d.rs", 1),
+            ("e.rs", 100),
+        ] {
+            let path = tmp_dir.path().join(name);
+            File::create(&path)
+                .and_then(|file| file.set_len(*len))
+                .expect("failed to create file");
+            metas.push(Meta::from_path(&path, false).expect("failed to get meta"));
+        }
+
+        let mut flags = Flags::default();
+        flags.sorting.column = SortColumn::Extension;
+        flags.sorting.then_by = vec![SortColumn::Size];
+
+        let sorters = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorters, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["e.rs", "b.rs", "d.rs", "c.txt", "a.txt"], names);
+
+        // The whole chain is reversed.
+        flags.sorting.order = SortOrder::Reverse;
+
+        let sorters = assemble_sorters(&flags);
+        metas.sort_unstable_by(|a, b| by_meta(&sorters, a, b));
+        let names: Vec<&str> = metas.iter().map(|meta| meta.name.name.as_str()).collect();
+        assert_eq!(vec!["a.txt", "c.txt", "d.rs", "b.rs", "e.rs"], names);
+    }
+
+    #[test]
+    fn test_sort_collate_falls_back_to_the_bytes() {
+        // The names can not hold a NUL character, but the strings passed to strcoll can not
+        // either.
+        assert_eq!(Ordering::Less, collate("a\0b", "a\0c"));
+    }
 }
@@ -398,7 +398,12 @@ fn test_bad_utf_8_name() {
 }
 
 fn cmd() -> Command {
-    Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap()
+    let mut cmd = Command::cargo_bin(env!("CARGO_PKG_NAME")).unwrap();
+    // The width of the grid would depend on the shell running the tests.
+    cmd.env_remove("COLUMNS");
+    // The colors would depend on the shell running the tests too.
+    cmd.env_remove("CLICOLOR_FORCE");
+    cmd
 }
 
 fn tempdir() -> assert_fs::TempDir {
@@ -468,3 +473,2681 @@ fn test_upper_case_ext_icon_match() {
         .assert()
         .stdout(predicate::str::contains("\u{f410}"));
 }
+
+#[test]
+fn test_json_respects_all_flag() {
+    let dir = tempdir();
+    dir.child("visible").touch().unwrap();
+    dir.child(".hidden").touch().unwrap();
+
+    cmd()
+        .arg("--json")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::starts_with("[{\"name\":\"visible\""))
+        .stdout(predicate::str::contains("\"name\":\".hidden\"").not());
+
+    cmd()
+        .arg("--json")
+        .arg("--almost-all")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::starts_with("[{\"name\":\".hidden\""))
+        .stdout(predicate::str::contains("\"name\":\"visible\""));
+}
+
+#[test]
+fn test_git_status_block() {
+    let dir = tempdir();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(args)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    };
+    if !git(&["init", "-q"]) {
+        // git is not available
+        return;
+    }
+    dir.child("tracked").write_str("a").unwrap();
+    assert!(git(&["add", "tracked"]));
+    dir.child("untracked").touch().unwrap();
+
+    cmd()
+        .arg("--git")
+        .arg("--oneline")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("A. tracked\n?? untracked\n"));
+}
+
+#[test]
+fn test_git_status_block_without_long_keeps_the_names_with_their_status() {
+    let dir = tempdir();
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("-C")
+            .arg(dir.path())
+            .args(args)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false)
+    };
+    if !git(&["init", "-q"]) {
+        // git is not available
+        return;
+    }
+    dir.child("tracked").write_str("a").unwrap();
+    assert!(git(&["add", "tracked"]));
+    dir.child("untracked").touch().unwrap();
+
+    cmd()
+        .arg("--git")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .env("COLUMNS", "80")
+        .assert()
+        .stdout(predicate::eq("A. tracked\n?? untracked\n"));
+}
+
+#[test]
+fn test_git_status_block_outside_of_repository() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("--git")
+        .arg("--oneline")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("   file\n"));
+}
+
+#[test]
+fn test_version_sort_numbered_file_names() {
+    let dir = tempdir();
+    dir.child("img10.png").touch().unwrap();
+    dir.child("img2.png").touch().unwrap();
+    dir.child("img1.png").touch().unwrap();
+
+    cmd()
+        .arg("-v")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("img1.png\nimg2.png\nimg10.png\n"));
+
+    cmd()
+        .arg("--sort")
+        .arg("version")
+        .arg("--reverse")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("img10.png\nimg2.png\nimg1.png\n"));
+}
+
+#[test]
+fn test_header_aligned_with_blocks() {
+    let dir = tempdir();
+    dir.child("file").write_str("ab").unwrap();
+
+    let output = cmd()
+        .arg("--long")
+        .arg("--header")
+        .arg("--blocks")
+        .arg("permission,size,name")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(2, lines.len());
+    assert!(lines[0].starts_with("Permissions "));
+    assert_eq!(lines[0].find("Size"), lines[1].find("2 B"));
+    assert_eq!(lines[0].find("Name"), lines[1].find("file"));
+}
+
+#[test]
+fn test_header_not_in_grid() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("--header")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\n"));
+}
+
+#[test]
+fn test_no_color_env_overridden_by_color_flag() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+    dir.child("two").create_dir_all().unwrap();
+
+    cmd()
+        .env("NO_COLOR", "1")
+        .arg("--color")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}["));
+}
+
+#[test]
+fn test_quoting_style_file_with_space() {
+    let dir = tempdir();
+    dir.child("a b.txt").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("a b.txt\n"));
+
+    cmd()
+        .arg("--quoting-style")
+        .arg("shell")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("'a b.txt'\n"));
+
+    cmd()
+        .arg("--quoting-style")
+        .arg("c")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("\"a b.txt\"\n"));
+}
+
+#[test]
+fn test_quoting_style_is_included_in_width() {
+    let dir = tempdir();
+    dir.child("a b.txt").touch().unwrap();
+    dir.child("c").touch().unwrap();
+
+    cmd()
+        .arg("--long")
+        .arg("--blocks")
+        .arg("name,size")
+        .arg("--quoting-style")
+        .arg("shell")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("'a b.txt' 0 B\nc         0 B\n"));
+}
+
+#[test]
+fn test_type_filter_only_directories() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+    dir.child("two").create_dir_all().unwrap();
+    dir.child("file.txt").touch().unwrap();
+    #[cfg(unix)]
+    fs::symlink(dir.path().join("one"), dir.path().join("link")).unwrap();
+
+    cmd()
+        .arg("--type")
+        .arg("dir")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\ntwo\n"));
+}
+
+#[test]
+fn test_type_filter_union() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+    dir.child("file.txt").touch().unwrap();
+    dir.child("other.txt").touch().unwrap();
+
+    cmd()
+        .arg("--type")
+        .arg("dir")
+        .arg("--type")
+        .arg("file")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("file.txt\none\nother.txt\n"));
+}
+
+#[test]
+fn test_type_filter_keeps_parents_in_tree() {
+    let dir = tempdir();
+    dir.child("empty").create_dir_all().unwrap();
+    dir.child("parent/file.txt").touch().unwrap();
+    dir.child("top.txt").touch().unwrap();
+    dir.child("parent/child").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--tree")
+        .arg("--type")
+        .arg("file")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(
+            "├── parent\n│  └── file.txt\n└── top.txt\n",
+        ))
+        .stdout(predicate::str::contains("empty").not())
+        .stdout(predicate::str::contains("child").not());
+}
+
+#[test]
+fn test_type_filter_keeps_implied_directories() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+    dir.child("file.txt").touch().unwrap();
+
+    cmd()
+        .arg("--all")
+        .arg("--type")
+        .arg("file")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(".\n..\nfile.txt\n"));
+}
+
+#[test]
+fn test_tree_keeps_the_lines_of_ancestors_with_remaining_siblings() {
+    let dir = tempdir();
+    dir.child("a/b/c/f").touch().unwrap();
+    dir.child("a/b/g").touch().unwrap();
+    dir.child("a/d/h").touch().unwrap();
+    dir.child("e/i").touch().unwrap();
+    dir.child("j").touch().unwrap();
+
+    let expected = "\
+├── a
+│  ├── b
+│  │  ├── c
+│  │  │  └── f
+│  │  └── g
+│  └── d
+│     └── h
+├── e
+│  └── i
+└── j
+";
+
+    cmd()
+        .arg("--tree")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(expected));
+}
+
+#[test]
+fn test_tree_depth_shows_grandchildren_only() {
+    let dir = tempdir();
+    dir.child("child/grandchild/great-grandchild")
+        .create_dir_all()
+        .unwrap();
+
+    cmd()
+        .arg("--tree")
+        .arg("--depth")
+        .arg("2")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with("└── child\n   └── grandchild\n"));
+}
+
+#[test]
+fn test_recursive_depth_one_lists_only_this_level() {
+    let dir = tempdir();
+    dir.child("child/grandchild").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--recursive")
+        .arg("--depth")
+        .arg("1")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("child\n"));
+}
+
+#[test]
+fn test_depth_zero_is_an_error() {
+    cmd()
+        .arg("--tree")
+        .arg("--depth")
+        .arg("0")
+        .arg("--ignore-config")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("--depth"));
+}
+
+#[test]
+fn test_color_always_when_piped() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+    dir.child("two").create_dir_all().unwrap();
+
+    cmd()
+        .env_remove("NO_COLOR")
+        .arg("--color")
+        .arg("always")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\x1B["))
+        .stdout(predicate::str::contains("\u{f115}"));
+}
+
+#[test]
+fn test_color_always_keeps_alignment_when_piped() {
+    let dir = tempdir();
+    dir.child("a").write_str("1234567890").unwrap();
+    dir.child("b").touch().unwrap();
+
+    let output = cmd()
+        .env_remove("NO_COLOR")
+        .arg("--color")
+        .arg("always")
+        .arg("--long")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(2, lines.len());
+    assert!(lines[0].contains("\x1B["));
+    assert!(lines[0].contains("\u{f016}"));
+    assert_eq!(
+        lines[0].find("\u{f016}"), // the default file icon
+        lines[1].find("\u{f016}")
+    );
+}
+
+#[test]
+fn test_color_always_respects_icon_never() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--color")
+        .arg("always")
+        .arg("--icon")
+        .arg("never")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{f115}").not());
+}
+
+#[test]
+fn test_block_size() {
+    let dir = tempdir();
+    dir.child("file").write_str(&"a".repeat(5000)).unwrap();
+
+    for args in &[vec!["--block-size", "1K"], vec!["-k"]] {
+        cmd()
+            .arg("--long")
+            .arg("--blocks")
+            .arg("size,name")
+            .args(args)
+            .arg("--ignore-config")
+            .arg(dir.path())
+            .assert()
+            .stdout(predicate::eq("5 file\n"));
+    }
+}
+
+/// Set an extended attribute on the file, returning false if the file system does not support
+/// them.
+#[cfg(target_os = "linux")]
+fn set_xattr(path: &std::path::Path, name: &str) -> bool {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).unwrap();
+    let name = CString::new(name).unwrap();
+    let value = b"lsd";
+
+    unsafe {
+        libc::lsetxattr(
+            path.as_ptr(),
+            name.as_ptr(),
+            value.as_ptr() as *const libc::c_void,
+            value.len(),
+            0,
+        ) == 0
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_xattr_marker() {
+    let dir = tempdir();
+    dir.child("plain").touch().unwrap();
+    dir.child("tagged").touch().unwrap();
+    if !set_xattr(&dir.path().join("tagged"), "user.lsd.test") {
+        return;
+    }
+
+    cmd()
+        .arg("--long")
+        .arg("--blocks")
+        .arg("xattr,name")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("  plain\n@ tagged\n"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_extended_lists_xattr_names() {
+    let dir = tempdir();
+    dir.child("plain").touch().unwrap();
+    dir.child("tagged").touch().unwrap();
+    if !set_xattr(&dir.path().join("tagged"), "user.lsd.test") {
+        return;
+    }
+
+    cmd()
+        .arg("--long")
+        .arg("--blocks")
+        .arg("name")
+        .arg("-@")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("  plain\n@ tagged\n    user.lsd.test\n"));
+
+    cmd()
+        .arg("-@")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .env("COLUMNS", "80")
+        .assert()
+        .stdout(predicate::eq("  plain\n@ tagged\n    user.lsd.test\n"));
+
+    cmd()
+        .arg("--tree")
+        .arg("-@")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(
+            "├──   plain\n└── @ tagged\n       user.lsd.test\n",
+        ));
+}
+
+#[test]
+fn test_width_sets_the_grid_columns() {
+    let dir = tempdir();
+    for i in 1..10 {
+        dir.child(format!("file-{}", i)).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width")
+        .arg("40")
+        .arg(dir.path())
+        .assert()
+        .stdout(
+            predicate::str::is_match(
+                "^file-1  file-3  file-5  file-7  file-9\nfile-2  file-4  file-6  file-8  \n$",
+            )
+            .unwrap(),
+        );
+}
+
+#[test]
+fn test_width_zero_displays_one_entry_per_line() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width")
+        .arg("0")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\ntwo\n"));
+}
+
+#[test]
+fn test_width_ignores_color_sequences_and_counts_icons() {
+    let dir = tempdir();
+    for i in 1..10 {
+        dir.child(format!("dir-{}", i)).create_dir_all().unwrap();
+    }
+
+    // Each entry is 7 columns wide with its icon, so 3 columns of entries fit into 30 columns.
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--color=always")
+        .arg("--icon=always")
+        .arg("--width")
+        .arg("30")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(3, stdout.lines().count());
+    assert!(stdout.lines().all(|line| line.matches("dir-").count() == 3));
+}
+
+#[test]
+fn test_width_rejects_invalid_values() {
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width")
+        .arg("wide")
+        .assert()
+        .failure();
+}
+
+#[test]
+fn test_padding_between_grid_columns() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width=80")
+        .arg("--padding=1")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one two\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width=80")
+        .arg("--padding=4")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one    two\n"));
+}
+
+#[test]
+fn test_padding_between_blocks() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=permission,name")
+        .arg("--padding=1")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^.......... one\n$").unwrap());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=permission,name")
+        .arg("--padding=4")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^..........    one\n$").unwrap());
+}
+
+#[test]
+fn test_padding_keeps_the_grid_within_the_width() {
+    let dir = tempdir();
+    for name in &["aaaa", "bbbb", "cccc"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    // 3 names of 4 columns fit into 15 columns with a padding of 1, but not with a padding of 2.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width=15")
+        .arg("--padding=1")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("aaaa bbbb cccc\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width=15")
+        .arg("--padding=2")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("^aaaa  cccc\nbbbb\\s*\n$").unwrap());
+}
+
+#[test]
+fn test_summary_counts_entries_and_bytes() {
+    let dir = tempdir();
+    dir.child("one").write_str("a").unwrap();
+    dir.child("two").write_str("bb").unwrap();
+    dir.child("three").write_str("ccc").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--summary")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with("\n3 entries, 6 B\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--summary")
+        .arg("--size=bytes")
+        .arg("--long")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(" two\n3 entries, 6\n"));
+}
+
+#[test]
+fn test_summary_respects_hidden_files() {
+    let dir = tempdir();
+    dir.child("one").write_str("a").unwrap();
+    dir.child(".hidden").write_str("bb").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--summary")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\n1 entry, 1 B\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--summary")
+        .arg("--almost-all")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(".hidden\none\n2 entries, 3 B\n"));
+}
+
+#[test]
+fn test_summary_is_not_displayed_in_tree() {
+    let dir = tempdir();
+    dir.child("one").write_str("a").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--summary")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("entry").not());
+}
+
+#[test]
+fn test_sort_by_name_ignores_case() {
+    let dir = tempdir();
+    dir.child("Cherry").touch().unwrap();
+    dir.child("banana").touch().unwrap();
+    dir.child("Apple").touch().unwrap();
+    dir.child("apricots").create_dir_all().unwrap();
+    dir.child("Dates").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("Apple\napricots\nbanana\nCherry\nDates\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--reverse")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("Dates\nCherry\nbanana\napricots\nApple\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--group-dirs=first")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("apricots\nDates\nApple\nbanana\nCherry\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_command_line_only_follows_the_inputs() {
+    let dir = tempdir();
+    dir.child("target").write_str("content").unwrap();
+    dir.child("dir").create_dir_all().unwrap();
+    let top_link = dir.path().join("top");
+    fs::symlink("target", &top_link).unwrap();
+    fs::symlink("../target", dir.path().join("dir").join("nested")).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--dereference-command-line")
+        .arg("--ignore-config")
+        .arg(&top_link)
+        .assert()
+        .stdout(predicate::str::starts_with(".rw"))
+        .stdout(predicate::str::contains("⇒").not());
+
+    cmd()
+        .arg("-l")
+        .arg("-H")
+        .arg("--ignore-config")
+        .arg(dir.path().join("dir"))
+        .assert()
+        .stdout(predicate::str::starts_with("l"))
+        .stdout(predicate::str::contains("nested ⇒ ../target"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_command_line_broken_link_is_listed_with_a_warning() {
+    let dir = tempdir();
+    let broken_link = dir.path().join("broken");
+    fs::symlink("missing", &broken_link).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("-H")
+        .arg("--blocks=size,name")
+        .arg("--ignore-config")
+        .arg(&broken_link)
+        .assert()
+        .stderr(predicate::str::contains("No such file or directory"))
+        .stdout(predicate::str::starts_with("- "));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_time_kind_drives_the_time_sort() {
+    let dir = tempdir();
+    let touch = |args: &[&str], name: &str| {
+        assert!(Command::new("touch")
+            .args(args)
+            .arg(dir.path().join(name))
+            .status()
+            .unwrap()
+            .success());
+    };
+    // "old" was modified before "new" but accessed after it.
+    touch(&["-m", "-t", "202001010000"], "old");
+    touch(&["-a", "-t", "202003010000"], "old");
+    touch(&["-m", "-t", "202002010000"], "new");
+    touch(&["-a", "-t", "202001010000"], "new");
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-t")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("new\nold\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-t")
+        .arg("--time=accessed")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("old\nnew\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-l")
+        .arg("--date=+%F")
+        .arg("--time=accessed")
+        .arg(dir.path().join("old"))
+        .assert()
+        .stdout(predicate::str::contains(" 2020-03-01 "));
+}
+
+#[test]
+fn test_gitignore_hides_ignored_entries() {
+    let dir = tempdir();
+    dir.child(".git").create_dir_all().unwrap();
+    dir.child(".gitignore").write_str("target/\n").unwrap();
+    dir.child("target/debug").create_dir_all().unwrap();
+    dir.child("src/target").touch().unwrap();
+    dir.child("Cargo.toml").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--gitignore")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("Cargo.toml\nsrc\n"));
+
+    // A file, unlike a directory, is not matched by "target/".
+    cmd()
+        .arg("--ignore-config")
+        .arg("--gitignore")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("target").count(1));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("Cargo.toml\nsrc\ntarget\n"));
+}
+
+#[test]
+fn test_gitignore_uses_the_global_excludes_file() {
+    let dir = tempdir();
+    dir.child("home").create_dir_all().unwrap();
+    dir.child("config/git/ignore").write_str("*.swp\n").unwrap();
+    dir.child("repo/.git").create_dir_all().unwrap();
+    dir.child("repo/main.rs").touch().unwrap();
+    dir.child("repo/main.rs.swp").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--gitignore")
+        .arg(dir.path().join("repo"))
+        .env("HOME", dir.path().join("home"))
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .assert()
+        .stdout(predicate::eq("main.rs\n"));
+
+    dir.child("home/.gitconfig")
+        .write_str("[core]\n    excludesFile = ~/excludes\n")
+        .unwrap();
+    dir.child("home/excludes").write_str("main.rs\n").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--gitignore")
+        .arg(dir.path().join("repo"))
+        .env("HOME", dir.path().join("home"))
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .assert()
+        .stdout(predicate::eq("main.rs.swp\n"));
+}
+
+#[test]
+fn test_gitignore_does_not_hide_the_arguments() {
+    let dir = tempdir();
+    dir.child(".git").create_dir_all().unwrap();
+    dir.child(".gitignore")
+        .write_str("target/\n*.log\n")
+        .unwrap();
+    dir.child("target/debug").create_dir_all().unwrap();
+    dir.child("build.log").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--gitignore")
+        .arg(dir.path().join("target"))
+        .assert()
+        .stdout(predicate::eq("debug\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--gitignore")
+        .arg(dir.path().join("build.log"))
+        .assert()
+        .stdout(predicate::str::contains("build.log"));
+}
+
+#[test]
+fn test_grid_aligns_double_width_names() {
+    let dir = tempdir();
+    for name in &[
+        "日本語.txt",
+        "a.txt",
+        "crab🦀.rs",
+        "bbbbbbbbb.txt",
+        "c.md",
+        "é.txt",
+    ] {
+        dir.child(name).touch().unwrap();
+    }
+
+    // "日本語.txt" and "crab🦀.rs" take 10 and 9 columns, unlike their number of chars.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--width=40")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "a.txt          c.md       é.txt\nbbbbbbbbb.txt  crab🦀.rs  日本語.txt\n",
+        ));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=name,size")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains(
+            "crab🦀.rs     0 B\né.txt         0 B\n日本語.txt    0 B\n",
+        ));
+}
+
+#[test]
+fn test_multiple_directories_have_headers() {
+    let dir = tempdir();
+    dir.child("one/first").touch().unwrap();
+    dir.child("two/second").touch().unwrap();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("two")
+        .arg("file")
+        .arg("one")
+        .assert()
+        .stdout(predicate::eq("file\n\none:\nfirst\n\ntwo:\nsecond\n"));
+}
+
+#[test]
+fn test_multiple_directories_without_files_do_not_start_with_a_blank_line() {
+    let dir = tempdir();
+    dir.child("one/first").touch().unwrap();
+    dir.child("two/second").touch().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("one")
+        .arg("two")
+        .assert()
+        .stdout(predicate::eq("one:\nfirst\n\ntwo:\nsecond\n"));
+}
+
+#[test]
+fn test_single_directory_has_no_header() {
+    let dir = tempdir();
+    dir.child("one/first").touch().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("one")
+        .assert()
+        .stdout(predicate::eq("first\n"));
+}
+
+#[test]
+fn test_theme_file_overrides_the_directory_color() {
+    let dir = tempdir();
+    dir.child("folder").create_dir_all().unwrap();
+    dir.child("theme.yaml")
+        .write_str("dir: 196\nnot-a-key: 1\n")
+        .unwrap();
+
+    cmd()
+        .env_remove("NO_COLOR")
+        .env_remove("LS_COLORS")
+        .env_remove("LSD_THEME")
+        .arg("--ignore-config")
+        .arg("--color=always")
+        .arg("--icon=never")
+        .arg("--theme")
+        .arg(dir.path().join("theme.yaml"))
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}[38;5;196mfolder\u{1b}[0m"))
+        .stderr(predicate::str::contains("Unknown theme key: not-a-key"));
+}
+
+#[test]
+fn test_theme_file_from_the_environment() {
+    let dir = tempdir();
+    dir.child("folder").create_dir_all().unwrap();
+    dir.child("theme.yaml").write_str("dir: 196\n").unwrap();
+
+    cmd()
+        .env_remove("NO_COLOR")
+        .env_remove("LS_COLORS")
+        .env("LSD_THEME", dir.path().join("theme.yaml"))
+        .arg("--ignore-config")
+        .arg("--color=always")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}[38;5;196mfolder\u{1b}[0m"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_numeric_uid_gid() {
+    use std::os::unix::fs::MetadataExt;
+
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+    let metadata = std::fs::metadata(dir.path().join("file")).unwrap();
+
+    let expected = format!("{} {} file\n", metadata.uid(), metadata.gid());
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=user,group,name")
+        .arg("-n")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(expected.as_str()));
+}
+
+#[test]
+fn test_large_directory_is_listed_in_order() {
+    let dir = tempdir();
+    let mut names: Vec<String> = (0..500).map(|index| format!("file{:03}", index)).collect();
+    for name in &names {
+        dir.child(name).touch().unwrap();
+    }
+    names.push(String::new());
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(names.join("\n").as_str()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_shows_the_size_of_the_target() {
+    let dir = tempdir();
+    dir.child("large").write_str(&"x".repeat(4096)).unwrap();
+    fs::symlink("large", dir.path().join("link")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--size=bytes")
+        .arg(dir.path().join("link"))
+        .assert()
+        .stdout(predicate::str::starts_with("5 "));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--size=bytes")
+        .arg("--dereference")
+        .arg(dir.path().join("link"))
+        .assert()
+        .stdout(predicate::str::starts_with("4096 "));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_broken_link_shows_a_dash() {
+    let dir = tempdir();
+    fs::symlink("missing", dir.path().join("broken")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--dereference")
+        .arg(dir.path())
+        .assert()
+        .code(1)
+        .stdout(predicate::eq("- broken\n"))
+        .stderr(predicate::str::contains(
+            "cannot dereference the broken link",
+        ));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_date_format_from_the_config_file() {
+    let dir = tempdir();
+    dir.child("config/lsd/config.yaml")
+        .write_str("date: \"+%Y-%m-%d %H:%M\"\n")
+        .unwrap();
+    dir.child("list/file").touch().unwrap();
+    assert!(Command::new("touch")
+        .env("TZ", "UTC")
+        .arg("-m")
+        .arg("-d")
+        .arg("2020-01-02 03:04:05")
+        .arg(dir.path().join("list/file"))
+        .status()
+        .unwrap()
+        .success());
+
+    cmd()
+        .env("TZ", "UTC")
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .arg("--long")
+        .arg("--blocks=date,name")
+        .arg(dir.path().join("list"))
+        .assert()
+        .stdout(predicate::eq("2020-01-02 03:04 file\n"));
+
+    cmd()
+        .env("TZ", "UTC")
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .arg("--long")
+        .arg("--blocks=date,name")
+        .arg("--date=+%d/%m/%Y")
+        .arg(dir.path().join("list"))
+        .assert()
+        .stdout(predicate::eq("02/01/2020 file\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_resolve_symlinks_shows_the_target_permissions() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    dir.child("script").touch().unwrap();
+    std::fs::set_permissions(
+        dir.path().join("script"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    fs::symlink("script", dir.path().join("link")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=permission,name")
+        .arg("link")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::str::starts_with("lrwxrwxrwx link ⇒ script"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=permission,name")
+        .arg("--resolve-symlinks")
+        .arg("link")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::str::starts_with(".rwxr-xr-x link ⇒ script"));
+}
+
+#[test]
+fn test_oneline_displays_one_name_per_line() {
+    let dir = tempdir();
+    for name in &["one", "two", "three", "four"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--width=200")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("four\none\nthree\ntwo\n"));
+}
+
+#[test]
+fn test_oneline_displays_the_requested_blocks() {
+    let dir = tempdir();
+    dir.child("one").write_str("1").unwrap();
+    dir.child("two").write_str("22").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-1")
+        .arg("--blocks=size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("1 B one\n2 B two\n"));
+}
+
+#[test]
+fn test_classic_mode_is_overridden_by_later_options() {
+    let dir = tempdir();
+    dir.child("file").write_str("content").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--size=bytes")
+        .arg("--classic")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("7 B file\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--classic")
+        .arg("--color=always")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}["));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_ignore_globs_from_the_config_file() {
+    let dir = tempdir();
+    dir.child("config/lsd/config.yaml")
+        .write_str("ignore-globs:\n  - '*.log'\n  - 'build'\n  - 'a{b'\n")
+        .unwrap();
+    for name in &[
+        "list/debug.log",
+        "list/build",
+        "list/file.tmp",
+        "list/main.rs",
+    ] {
+        dir.child(name).touch().unwrap();
+    }
+
+    cmd()
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .arg(dir.path().join("list"))
+        .assert()
+        .stdout(predicate::eq("file.tmp\nmain.rs\n"))
+        .stderr(predicate::str::contains(
+            "Not a valid ignore-globs value: a{b",
+        ));
+
+    cmd()
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .arg("--ignore-glob=*.tmp")
+        .arg(dir.path().join("list"))
+        .assert()
+        .stdout(predicate::eq("main.rs\n"));
+}
+
+#[test]
+fn test_all_lists_the_implied_dirs_first() {
+    let dir = tempdir();
+    dir.child("a").touch().unwrap();
+    dir.child(".hidden").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--all")
+        .arg("--reverse")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(".\n..\na\n.hidden\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--almost-all")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(".hidden\na\n"));
+}
+
+#[test]
+fn test_tree_all_does_not_list_the_implied_dirs() {
+    let dir = tempdir();
+    dir.child("folder/.hidden").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--all")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match("\n└── folder\n   └── .hidden\n$").unwrap());
+}
+
+#[test]
+fn test_no_sort_keeps_the_directory_order() {
+    let dir = tempdir();
+    for name in &["b", "e", "a", "d", "c", "f"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    let mut expected = String::new();
+    for entry in std::fs::read_dir(dir.path()).unwrap() {
+        expected += &entry.unwrap().file_name().to_string_lossy();
+        expected.push('\n');
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--sort=none")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(expected.as_str()));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-U")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(expected.as_str()));
+}
+
+#[test]
+fn test_truncate_names() {
+    let dir = tempdir();
+    let long_name = "a".repeat(46) + ".txt";
+    dir.child(&long_name).touch().unwrap();
+    dir.child("short").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--truncate-names=20")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            format!("{}…\nshort\n", "a".repeat(19)).as_str(),
+        ));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--truncate-names=20")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains(long_name.as_str()));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_only_the_executable_indicator() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    dir.child("script").touch().unwrap();
+    std::fs::set_permissions(
+        dir.path().join("script"),
+        std::fs::Permissions::from_mode(0o755),
+    )
+    .unwrap();
+    dir.child("folder").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--classify")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("folder/\nscript*\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--indicator-types=exec")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("folder\nscript*\n"));
+}
+
+#[test]
+fn test_dirsize_count() {
+    let dir = tempdir();
+    for name in &["folder/one", "folder/two", "folder/.three"] {
+        dir.child(name).touch().unwrap();
+    }
+    dir.child("file").write_str("content").unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--dirsize=count")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("7 B file\n3   folder\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dirsize_count_of_unreadable_dir() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    dir.child("locked/file").touch().unwrap();
+    std::fs::set_permissions(
+        dir.path().join("locked"),
+        std::fs::Permissions::from_mode(0o000),
+    )
+    .unwrap();
+    if std::fs::read_dir(dir.path().join("locked")).is_ok() {
+        // The permissions are not enforced for the superuser.
+        return;
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--dirsize=count")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("- locked\n"));
+}
+
+#[test]
+fn test_same_size_files_are_sorted_by_name() {
+    let dir = tempdir();
+    for name in &["d", "b", "c", "a"] {
+        dir.child(name).write_str("same").unwrap();
+    }
+    dir.child("large").write_str("larger content").unwrap();
+
+    for _ in 0..3 {
+        cmd()
+            .arg("--ignore-config")
+            .arg("--sizesort")
+            .arg(dir.path())
+            .assert()
+            .stdout(predicate::eq("large\na\nb\nc\nd\n"));
+
+        cmd()
+            .arg("--ignore-config")
+            .arg("--sizesort")
+            .arg("--reverse")
+            .arg(dir.path())
+            .assert()
+            .stdout(predicate::eq("a\nb\nc\nd\nlarge\n"));
+    }
+}
+
+#[cfg(windows)]
+#[test]
+fn test_hidden_attribute_is_only_listed_with_all() {
+    let dir = tempdir();
+    dir.child("hidden").touch().unwrap();
+    dir.child("visible").touch().unwrap();
+
+    Command::new("attrib")
+        .arg("+h")
+        .arg(dir.path().join("hidden"))
+        .assert()
+        .success();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("visible\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--all")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("hidden\n"));
+}
+
+#[test]
+fn test_size_filter_lists_the_files_in_the_range() {
+    let dir = tempdir();
+    for (name, size) in &[
+        ("tiny", 10),
+        ("small", 1024),
+        ("medium", 10 * 1024),
+        ("big", 100 * 1024),
+        ("huge", 1024 * 1024),
+    ] {
+        dir.child(name).write_binary(&vec![0; *size]).unwrap();
+    }
+    dir.child("folder").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--larger-than")
+        .arg("10K")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("big\nfolder\nhuge\nmedium\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--larger-than")
+        .arg("10K")
+        .arg("--smaller-than")
+        .arg("1M")
+        .arg("--total-size")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("big\nmedium\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_date_filter_splits_the_files_at_a_day() {
+    let dir = tempdir();
+    let touch = |hours_ago: i64, name: &str| {
+        let date = time::at(time::get_time() - time::Duration::hours(hours_ago));
+        assert!(Command::new("touch")
+            .arg("-m")
+            .arg("-t")
+            .arg(time::strftime("%Y%m%d%H%M.%S", &date).unwrap())
+            .arg(dir.path().join(name))
+            .status()
+            .unwrap()
+            .success());
+    };
+    touch(0, "now");
+    touch(23, "yesterday");
+    touch(25, "before-yesterday");
+    touch(24 * 30, "last-month");
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--newer-than")
+        .arg("1d")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("now\nyesterday\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--older-than")
+        .arg("1d")
+        .arg("--newer-than")
+        .arg("1w")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("before-yesterday\n"));
+}
+
+#[test]
+fn test_last_of_all_and_almost_all_wins() {
+    let dir = tempdir();
+    dir.child(".hidden").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-a")
+        .arg("-A")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(".hidden\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("-A")
+        .arg("-a")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(".\n..\n.hidden\n"));
+}
+
+#[test]
+fn test_filetype_block_displays_the_type_letter() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+    dir.child("folder").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks")
+        .arg("filetype,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("- file\nd folder\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_tree_marks_a_link_to_an_ancestor() {
+    let dir = tempdir();
+    dir.child("a/b").create_dir_all().unwrap();
+    fs::symlink("../..", dir.path().join("a/b/up")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg("--no-symlink")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::str::ends_with(
+            "└── a\n   └── b\n      └── up [recursion]\n",
+        ));
+}
+
+#[test]
+fn test_stdin_lists_the_given_paths_in_order() {
+    let dir = tempdir();
+    dir.child("b").touch().unwrap();
+    dir.child("a").create_dir_all().unwrap();
+    dir.child("a/inner").touch().unwrap();
+    dir.child("c").touch().unwrap();
+    let listed = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| format!("{}\n", dir.path().join(name).display()))
+            .collect::<String>()
+    };
+
+    let mut command = assert_cmd::Command::from_std(cmd());
+    command
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--stdin")
+        .write_stdin(listed(&["c", "a", "b"]))
+        .assert()
+        .stdout(predicate::eq(listed(&["c", "a", "b"]).as_str()));
+
+    let mut command = assert_cmd::Command::from_std(cmd());
+    command
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("-")
+        .arg("--sort")
+        .arg("extension")
+        .write_stdin(listed(&["c", "a", "b"]))
+        .assert()
+        .stdout(predicate::eq(listed(&["a", "b", "c"]).as_str()));
+}
+
+#[test]
+fn test_stdin_reads_the_paths_delimited_by_null() {
+    let dir = tempdir();
+    dir.child("two\nlines").touch().unwrap();
+    dir.child("other").touch().unwrap();
+
+    let mut command = assert_cmd::Command::from_std(cmd());
+    command
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--stdin")
+        .arg("--null")
+        .current_dir(dir.path())
+        .write_stdin("two\nlines\0other\0")
+        .assert()
+        .stdout(predicate::eq("two\\nlines\nother\n"));
+}
+
+#[test]
+fn test_missing_input_exits_with_2_after_listing_the_others() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg(dir.path())
+        .arg(dir.path().join("missing"))
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("file\n"))
+        .stderr(predicate::str::contains("missing"));
+}
+
+#[test]
+fn test_clean_run_exits_with_0() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .code(0);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_unreadable_subdir_exits_with_2_after_listing_the_others() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir();
+    dir.child("locked/file").touch().unwrap();
+    dir.child("open/file").touch().unwrap();
+    std::fs::set_permissions(
+        dir.path().join("locked"),
+        std::fs::Permissions::from_mode(0o000),
+    )
+    .unwrap();
+    if std::fs::read_dir(dir.path().join("locked")).is_ok() {
+        // The permissions are not enforced for the superuser.
+        return;
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .code(2)
+        .stdout(predicate::str::contains("open"))
+        .stderr(predicate::str::contains("locked"));
+}
+
+#[test]
+fn test_dumb_terminal_is_not_colored_in_auto_mode() {
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .env("TERM", "dumb")
+        .arg("--ignore-config")
+        .arg("--color=auto")
+        .arg("--icon=auto")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("file\n"));
+}
+
+#[test]
+fn test_dumb_terminal_is_colored_when_forced() {
+    let dir = tempdir();
+    dir.child("folder").create_dir_all().unwrap();
+
+    cmd()
+        .env("TERM", "dumb")
+        .arg("--ignore-config")
+        .arg("--color=always")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\x1b["));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_block_usage_of_a_sparse_file_differs_from_its_apparent_size() {
+    let dir = tempdir();
+    let file = std::fs::File::create(dir.path().join("sparse")).unwrap();
+    file.set_len(16 * 1024 * 1024).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--block-usage")
+        .arg("--apparent-size")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("16 MB sparse\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--blocks=size,name")
+        .arg("--block-usage")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("16 MB").not());
+}
+
+#[test]
+fn test_group_directories_first_with_size_sort() {
+    let dir = tempdir();
+    dir.child("small").write_str("1").unwrap();
+    dir.child("large").write_str("1234567890").unwrap();
+    dir.child("folder").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--sort")
+        .arg("size")
+        .arg("--group-directories-first")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("folder\nlarge\nsmall\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--sort")
+        .arg("size")
+        .arg("--reverse")
+        .arg("--group-directories-first")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("folder\nsmall\nlarge\n"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_mount_points_are_marked() {
+    use std::os::unix::fs::MetadataExt;
+
+    let device = |path: &str| std::fs::metadata(path).map(|meta| meta.dev()).ok();
+    if device("/proc").is_none() || device("/proc") == device("/") {
+        // The proc file system is not mounted.
+        return;
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--mount-points")
+        .arg("/")
+        .assert()
+        .stdout(predicate::str::contains("\nproc [mount]\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("/")
+        .assert()
+        .stdout(predicate::str::contains("[mount]").not());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_target_absolute_resolves_a_relative_target() {
+    let dir = tempdir();
+    dir.child("target").touch().unwrap();
+    let link = dir.path().join("link");
+    fs::symlink("target", &link).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::contains("⇒ target"));
+
+    let absolute = format!("⇒ {}", dir.path().join("target").display());
+    cmd()
+        .arg("-l")
+        .arg("--ignore-config")
+        .arg("--symlink-target")
+        .arg("absolute")
+        .arg(&link)
+        .assert()
+        .stdout(predicate::str::contains(absolute.as_str()));
+}
+
+#[test]
+fn test_columns_sets_the_grid_width() {
+    let dir = tempdir();
+    for i in 1..10 {
+        dir.child(format!("file-{}", i)).touch().unwrap();
+    }
+
+    cmd()
+        .arg("--ignore-config")
+        .env("COLUMNS", "120")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "file-1  file-2  file-3  file-4  file-5  file-6  file-7  file-8  file-9\n",
+        ));
+
+    cmd()
+        .arg("--ignore-config")
+        .env("COLUMNS", "40")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "file-1  file-3  file-5  file-7  file-9\nfile-2  file-4  file-6  file-8  \n",
+        ));
+}
+
+#[test]
+fn test_width_takes_precedence_over_columns() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .env("COLUMNS", "120")
+        .arg("--width")
+        .arg("0")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("one\ntwo\n"));
+}
+
+#[test]
+fn test_columns_ignores_invalid_values() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .env("COLUMNS", "wide")
+        .arg(dir.path())
+        .assert()
+        .success()
+        .stdout(predicate::eq("one\ntwo\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_summary_and_total_size_count_hard_links_once() {
+    let dir = tempdir();
+    dir.child("sub/file")
+        .write_binary(&[0; 1024 * 1024])
+        .unwrap();
+    std::fs::hard_link(dir.path().join("sub/file"), dir.path().join("sub/hard")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--summary")
+        .arg(dir.path().join("sub"))
+        .assert()
+        .stdout(predicate::str::ends_with("\n2 entries, 1.0 MB\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--total-size")
+        .arg("--blocks")
+        .arg("size,name")
+        .arg("--directory-only")
+        .arg(dir.path().join("sub"))
+        .assert()
+        .stdout(predicate::str::starts_with("1.0 MB"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_blocks_comma_string_from_the_config_file() {
+    let dir = tempdir();
+    dir.child("config/lsd/config.yaml")
+        .write_str("blocks: \"size,foo,name\"\nsize: bytes\n")
+        .unwrap();
+    dir.child("list/file").write_str("abc").unwrap();
+
+    cmd()
+        .env("XDG_CONFIG_HOME", dir.path().join("config"))
+        .arg("--long")
+        .arg(dir.path().join("list"))
+        .assert()
+        .stdout(predicate::eq("3  file\n"))
+        .stderr(predicate::str::contains("Not a valid block name: foo"));
+}
+
+#[test]
+fn test_clicolor_force_colorizes_piped_output() {
+    let dir = tempdir();
+    dir.child("one").create_dir_all().unwrap();
+
+    cmd()
+        .env_remove("NO_COLOR")
+        .env("CLICOLOR_FORCE", "1")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}["));
+
+    cmd()
+        .env_remove("NO_COLOR")
+        .env("CLICOLOR_FORCE", "1")
+        .arg("--color")
+        .arg("never")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains("\u{1b}[").not());
+}
+
+#[test]
+fn test_tree_counts_match_the_listed_children() {
+    let dir = tempdir();
+    dir.child("parent/child").create_dir_all().unwrap();
+    dir.child("parent/file.txt").touch().unwrap();
+    dir.child("parent/.hidden").touch().unwrap();
+    dir.child("parent/child/inner.txt").touch().unwrap();
+    dir.child("empty").create_dir_all().unwrap();
+    dir.child("top.txt").touch().unwrap();
+
+    let expected = " (3)
+├── empty (0)
+├── parent (2)
+│  ├── child (1)
+│  │  └── inner.txt
+│  └── file.txt
+└── top.txt
+";
+
+    cmd()
+        .arg("--tree")
+        .arg("--tree-counts")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(expected));
+}
+
+#[test]
+fn test_tree_counts_after_filtering() {
+    let dir = tempdir();
+    dir.child("parent/child").create_dir_all().unwrap();
+    dir.child("parent/file.txt").touch().unwrap();
+    dir.child("parent/other.txt").touch().unwrap();
+    dir.child("top.txt").touch().unwrap();
+
+    cmd()
+        .arg("--tree")
+        .arg("--tree-counts")
+        .arg("--type")
+        .arg("file")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(
+            " (2)\n├── parent (2)\n│  ├── file.txt\n│  └── other.txt\n└── top.txt\n",
+        ));
+}
+
+#[test]
+fn test_tree_counts_only_in_tree_layout() {
+    let dir = tempdir();
+    dir.child("parent/file.txt").touch().unwrap();
+
+    cmd()
+        .arg("--tree-counts")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("parent\n"));
+}
+
+#[test]
+fn test_directory_lists_the_directory_itself() {
+    let dir = tempdir();
+    dir.child("sub/file").touch().unwrap();
+
+    for flag in &["-d", "--directory", "--directory-only"] {
+        cmd()
+            .current_dir(dir.path())
+            .arg("--ignore-config")
+            .arg(flag)
+            .arg("sub")
+            .assert()
+            .stdout(predicate::eq("sub\n"));
+    }
+}
+
+#[test]
+fn test_directory_with_the_paths_of_a_glob() {
+    let dir = tempdir();
+    dir.child("one/file").touch().unwrap();
+    dir.child("two/file").touch().unwrap();
+
+    // The shell expands "*/" to the directories followed by a slash.
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("--directory")
+        .arg("one/")
+        .arg("two/")
+        .assert()
+        .stdout(predicate::eq("one/\ntwo/\n"));
+}
+
+#[test]
+fn test_directory_colors_the_directory() {
+    let dir = tempdir();
+    dir.child("sub/file").touch().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .env_remove("LS_COLORS")
+        .arg("--ignore-config")
+        .arg("--color")
+        .arg("always")
+        .arg("--icon")
+        .arg("never")
+        .arg("-d")
+        .arg("sub")
+        .assert()
+        .stdout(predicate::eq("\u{1b}[1;34msub\u{1b}[0m\n"));
+}
+
+#[test]
+fn test_oneline_unsorted_lists_the_files_then_the_directories() {
+    let dir = tempdir();
+    dir.child("sub/inner").touch().unwrap();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--sort=none")
+        .arg("sub")
+        .arg("file")
+        .assert()
+        .stdout(predicate::eq("file\n\nsub:\ninner\n"));
+}
+
+#[test]
+fn test_hide_unless_all() {
+    let dir = tempdir();
+    dir.child("main.c").touch().unwrap();
+    dir.child("main.o").touch().unwrap();
+    dir.child("notes~").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--hide")
+        .arg("*.o")
+        .arg("--hide=*~")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("main.c\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--hide")
+        .arg("*.o")
+        .arg("-a")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with("main.c\nmain.o\nnotes~\n"));
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--hide")
+        .arg("*.o")
+        .arg("--almost-all")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("main.c\nmain.o\nnotes~\n"));
+}
+
+#[test]
+fn test_hide_keeps_the_given_paths() {
+    let dir = tempdir();
+    dir.child("main.o").touch().unwrap();
+
+    cmd()
+        .current_dir(dir.path())
+        .arg("--ignore-config")
+        .arg("--hide")
+        .arg("*.o")
+        .arg("main.o")
+        .assert()
+        .stdout(predicate::eq("main.o\n"));
+}
+
+#[test]
+fn test_tree_indent() {
+    let dir = tempdir();
+    dir.child("one/two/three").touch().unwrap();
+
+    cmd()
+        .arg("--tree")
+        .arg("--tree-indent")
+        .arg("2")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::ends_with(
+            "└─ one\n  └─ two\n    └─ three\n",
+        ));
+
+    cmd()
+        .arg("--tree")
+        .arg("--tree-indent")
+        .arg("0")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .failure();
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_device_block_shows_the_device_numbers() {
+    cmd()
+        .arg("-l")
+        .arg("--blocks")
+        .arg("device,name")
+        .arg("/dev/null")
+        .assert()
+        .stdout(predicate::eq("1, 3 /dev/null\n"));
+}
+
+#[test]
+fn test_device_block_shows_the_size_of_regular_files() {
+    let dir = tempdir();
+    dir.child("file").write_str("abc").unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--blocks")
+        .arg("device,name")
+        .arg("file")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("3 B file\n"));
+}
+
+#[test]
+fn test_zero_ends_the_paths_with_nul_characters() {
+    let dir = tempdir();
+    dir.child("d/b").touch().unwrap();
+    dir.child("d/a").touch().unwrap();
+    dir.child("d/sub/c").touch().unwrap();
+
+    cmd()
+        .arg("--zero")
+        .arg("--color=always")
+        .arg("--icon=always")
+        .arg("-R")
+        .arg("d")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("d/a\0d/b\0d/sub\0d/sub/c\0"));
+}
+
+#[test]
+fn test_zero_respects_the_filters_and_the_sorting() {
+    let dir = tempdir();
+    dir.child("a").touch().unwrap();
+    dir.child("b").touch().unwrap();
+    dir.child(".hidden").touch().unwrap();
+
+    cmd()
+        .arg("--zero")
+        .arg("--reverse")
+        .arg("--color=always")
+        .arg(".")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("./b\0./a\0"));
+}
+
+#[test]
+fn test_zero_lists_the_full_paths_of_the_tree() {
+    let dir = tempdir();
+    dir.child("sub/inner/c").touch().unwrap();
+
+    cmd()
+        .arg("--zero")
+        .arg("--tree")
+        .arg("sub")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("sub/inner\0sub/inner/c\0"));
+}
+
+#[test]
+fn test_directory_config_applies_to_the_listed_directory() {
+    let dir = tempdir();
+    dir.child(".lsd.yaml").write_str("size: bytes\n").unwrap();
+    dir.child("file").write_str(&"a".repeat(2000)).unwrap();
+    dir.child("sub/file").write_str(&"a".repeat(2000)).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--blocks=size,name")
+        .arg("--directory-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::starts_with("2000  file\n"));
+
+    cmd()
+        .arg("-l")
+        .arg("--blocks=size,name")
+        .arg("--directory-config")
+        .arg(dir.path().join("sub"))
+        .assert()
+        .stdout(predicate::eq("2.0 KB file\n"));
+}
+
+#[test]
+fn test_arg_matches_take_precedence_over_the_directory_config() {
+    let dir = tempdir();
+    dir.child(".lsd.yaml").write_str("size: bytes\n").unwrap();
+    dir.child("file").write_str(&"a".repeat(2000)).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--blocks=size,name")
+        .arg("--size=short")
+        .arg("--directory-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("2.0K file\n"));
+
+    cmd()
+        .arg("-l")
+        .arg("--blocks=size,name")
+        .arg("--directory-config")
+        .arg("--ignore-config")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("2.0 KB file\n"));
+}
+
+#[test]
+fn test_directory_config_is_ignored_by_default() {
+    let dir = tempdir();
+    dir.child(".lsd.yaml").write_str("size: bytes\n").unwrap();
+    dir.child("file").write_str(&"a".repeat(2000)).unwrap();
+
+    cmd()
+        .arg("-l")
+        .arg("--blocks=size,name")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("2.0 KB file\n"));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_context_shows_a_question_mark_without_selinux() {
+    if std::path::Path::new("/sys/fs/selinux/enforce").exists() {
+        return;
+    }
+
+    let dir = tempdir();
+    dir.child("file").touch().unwrap();
+
+    cmd()
+        .arg("-Z")
+        .arg("file")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("? file\n"));
+}
+
+#[test]
+fn test_dim_ignored_styles_the_ignored_entries_apart() {
+    let dir = tempdir();
+    dir.child(".git").create_dir_all().unwrap();
+    dir.child(".gitignore").write_str("*.log\n").unwrap();
+    dir.child("build.log").touch().unwrap();
+    dir.child("main.rs").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--dim-ignored")
+        .arg("--color=always")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::starts_with(
+            "\u{1b}[2;38;5;240mbuild.log\u{1b}[0m\n",
+        ))
+        .stdout(predicate::str::contains("main.rs"))
+        .stdout(predicate::str::contains("\u{1b}[2;38;5;240mmain.rs").not());
+
+    // The ignored entries are only hidden by --gitignore.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--dim-ignored")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("build.log\nmain.rs\n"));
+}
+
+#[test]
+fn test_dim_ignored_styles_the_entries_of_ignored_directories() {
+    let dir = tempdir();
+    dir.child(".git").create_dir_all().unwrap();
+    dir.child(".gitignore").write_str("target/\n").unwrap();
+    dir.child("target/debug/lsd").touch().unwrap();
+    dir.child("src/main.rs").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--dim-ignored")
+        .arg("--color=always")
+        .arg("--icon=never")
+        .arg("--tree")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::contains(
+            "\u{1b}[2;38;5;240mtarget\u{1b}[0m\n",
+        ))
+        .stdout(predicate::str::contains(
+            "\u{1b}[2;38;5;240mdebug\u{1b}[0m\n",
+        ))
+        .stdout(predicate::str::contains("\u{1b}[2;38;5;240mlsd\u{1b}[0m\n"))
+        .stdout(predicate::str::contains("\u{1b}[2;38;5;240mmain.rs").not());
+}
+
+#[test]
+fn test_sort_keys_break_the_ties_in_order() {
+    let dir = tempdir();
+    dir.child("a.txt").write_str("a").unwrap();
+    dir.child("b.rs").write_str("bb").unwrap();
+    dir.child("c.txt").write_str("ccc").unwrap();
+    dir.child("d.rs").write_str("d").unwrap();
+
+    cmd()
+        .arg("--sort=extension")
+        .arg("--sort=size")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("b.rs\nd.rs\nc.txt\na.txt\n"));
+
+    cmd()
+        .arg("--sort=extension,size")
+        .arg("--reverse")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("a.txt\nc.txt\nd.rs\nb.rs\n"));
+}
+
+#[test]
+fn test_progress_is_silent_when_piped() {
+    let dir = tempdir();
+    dir.child("a/b/c").touch().unwrap();
+    dir.child("a/d").touch().unwrap();
+
+    cmd()
+        .arg("--progress")
+        .arg("--total-size")
+        .arg("-R")
+        .arg("a")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq("b\nd\n\na/b:\nc\n"))
+        .stderr(predicate::str::is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_dereference_columns_show_the_target_with_the_arrow() {
+    let dir = tempdir();
+    dir.child("big").write_binary(&[0; 2000]).unwrap();
+    fs::symlink("big", dir.path().join("link")).unwrap();
+    fs::symlink("missing", dir.path().join("broken")).unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--icon=never")
+        .arg("--size=bytes")
+        .arg("--blocks=permission,size,name")
+        .arg("--dereference-columns=size,type")
+        .arg("link")
+        .arg("broken")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "lrwxrwxrwx    7  broken ⇒ missing\n.rwxrwxrwx 2000  link ⇒ big\n",
+        ));
+}
+
+#[test]
+fn test_full_time_shows_the_long_layout_with_the_seconds() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    // One entry per line, like "2020-10-04 12:34:56.123456789 +0200 one".
+    let date = r"\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2}\.\d{9} [+-]\d{4}";
+    cmd()
+        .arg("--ignore-config")
+        .arg("--full-time")
+        .arg("--blocks=date,name")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match(format!("^{0} one\n{0} two\n$", date)).unwrap());
+}
+
+#[test]
+fn test_full_time_is_overridden_by_a_later_date() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--full-time")
+        .arg("--date=+%Y")
+        .arg("--blocks=date,name")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::str::is_match(r"^\d{4} one\n$").unwrap());
+}
+
+#[test]
+fn test_dired_prints_the_offsets_of_the_names() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("three").touch().unwrap();
+
+    let output = cmd()
+        .arg("--ignore-config")
+        .arg("--long")
+        .arg("--dired")
+        .arg("--blocks=permission,name")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    // Each line is indented by two spaces, so the first name starts after "  .rw-r--r-- ".
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        "  .rw-r--r-- one\n  .rw-r--r-- three\n//DIRED// 13 16 30 35\n",
+        stdout
+    );
+    assert_eq!("one", &stdout[13..16]);
+    assert_eq!("three", &stdout[30..35]);
+}
+
+#[test]
+fn test_dired_prints_the_offsets_of_the_listed_directories() {
+    let dir = tempdir();
+    dir.child("a/one").touch().unwrap();
+    dir.child("b").create_dir_all().unwrap();
+
+    cmd()
+        .arg("--ignore-config")
+        .arg("--dired")
+        .arg("--oneline")
+        .arg("--icon=never")
+        .arg("a")
+        .arg("b")
+        .current_dir(dir.path())
+        .assert()
+        .stdout(predicate::eq(
+            "  a:\n  one\n\n  b:\n//DIRED// 7 10\n//SUBDIRED// 2 3 14 15\n",
+        ));
+}
+
+#[test]
+fn test_dired_lists_one_name_per_line() {
+    let dir = tempdir();
+    dir.child("one").touch().unwrap();
+    dir.child("two").touch().unwrap();
+
+    // The names are not laid out in a grid, whose widths would not count their indentation.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--dired")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .env("COLUMNS", "12")
+        .assert()
+        .stdout(predicate::eq("  one\n  two\n//DIRED// 2 5 8 11\n"));
+}
+
+#[test]
+fn test_dired_conflicts_with_json() {
+    cmd().arg("--dired").arg("--json").assert().failure();
+}
+
+#[test]
+fn test_sort_locale_keeps_the_names_and_falls_back_to_the_bytes() {
+    let dir = tempdir();
+    for name in &["banana", "Ávila", "apple", "Cherry"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    // The names ignore their case by default, and are compared byte by byte in the C locale.
+    cmd()
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--icon=never")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("apple\nbanana\nCherry\nÁvila\n"));
+
+    cmd()
+        .env("LC_ALL", "C")
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--icon=never")
+        .arg("--sort-locale")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("Cherry\napple\nbanana\nÁvila\n"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_sort_locale_collates_the_accented_letters_with_the_plain_ones() {
+    let has_locale = Command::new("locale")
+        .arg("-a")
+        .output()
+        .map(|output| {
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .any(|locale| {
+                    let locale = locale.to_lowercase().replace('-', "");
+                    locale == "en_us.utf8"
+                })
+        })
+        .unwrap_or(false);
+    if !has_locale {
+        eprintln!("skipped: the en_US.UTF-8 locale is not available");
+        return;
+    }
+
+    let dir = tempdir();
+    for name in &["banana", "Ávila", "apple"] {
+        dir.child(name).touch().unwrap();
+    }
+
+    cmd()
+        .env("LC_ALL", "en_US.UTF-8")
+        .arg("--ignore-config")
+        .arg("--oneline")
+        .arg("--icon=never")
+        .arg("--sort-locale")
+        .arg(dir.path())
+        .assert()
+        .stdout(predicate::eq("apple\nÁvila\nbanana\n"));
+}